@@ -0,0 +1,38 @@
+//! Rendering benchmark
+//!
+//! Run with `cargo bench`.
+use std::time::Instant;
+
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Plot,
+};
+
+const POINTS: usize = 500_000;
+const RUNS: u32 = 10;
+
+fn main() {
+    let data: Vec<(f32, f32)> = (0..POINTS)
+        .map(|i| {
+            let x = i as f32;
+            (x, (x / 1000.0).sin() * 100.0)
+        })
+        .collect();
+    let domain = BBox::new(data.iter().cloned());
+    let chart = Chart::default()
+        .with_title("Benchmark")
+        .with_axis(Horizontal::new(domain))
+        .with_axis(Vertical::new(domain))
+        .with_line_plot(Plot::new("Line", &domain, data.iter().cloned()));
+    let mut len = 0;
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        len += chart.render().len();
+    }
+    let elapsed = start.elapsed() / RUNS;
+    println!(
+        "line plot, {POINTS} points: {elapsed:?} ({} bytes)",
+        len / RUNS as usize
+    );
+}
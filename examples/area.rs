@@ -10,10 +10,8 @@ fn main() {
     let data_b =
         vec![(22.0, 50.0), (105.0, 44.0), (120.0, 67.0), (180.0, 39.0)];
     let domain = BBox::new(data_a.iter().cloned());
-    let mut data_a = data_a.into_iter().map(Into::into);
-    let mut data_b = data_b.into_iter().map(Into::into);
-    let plot_a = Plot::new("Series A", &domain, &mut data_a);
-    let plot_b = Plot::new("Series B", &domain, &mut data_b);
+    let plot_a = Plot::new("Series A", &domain, data_a);
+    let plot_b = Plot::new("Series B", &domain, data_b);
     let chart = Chart::default()
         .with_title("Area Plot")
         .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
@@ -0,0 +1,27 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Band, Chart, Plot,
+};
+
+fn main() {
+    let data = vec![
+        (10.0, 40.0, 52.0),
+        (20.0, 44.0, 61.0),
+        (30.0, 38.0, 57.0),
+        (40.0, 50.0, 72.0),
+        (50.0, 47.0, 66.0),
+    ];
+    let domain = BBox::new([(10.0, 30.0), (50.0, 80.0)]);
+    let mean = data.iter().map(|(x, low, high)| (*x, (low + high) / 2.0));
+    let plot = Plot::new("Mean", &domain, mean);
+    let band = Band::new("Range", &domain, data);
+    let chart = Chart::default()
+        .with_title("Band Plot")
+        .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
+        .with_axis(Vertical::new(domain).with_name("Y Axis Name"))
+        .with_band_plot(band)
+        .with_line_plot(plot)
+        .render();
+    print!("{chart}");
+}
@@ -0,0 +1,29 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Label, Plot,
+};
+
+fn main() {
+    let data = vec![
+        (1.0, 74.0),
+        (2.0, 37.0),
+        (3.0, -12.0),
+        (4.0, 66.0),
+        (5.0, 45.0),
+    ];
+    let domain = {
+        let mut domain = BBox::new(data.iter().cloned());
+        domain.extend([(0.0, 0.0), (6.0, 0.0)]);
+        domain
+    };
+    let plot = Plot::new("Series", &domain, data)
+        .with_point_labels(Label::new().maximum().above());
+    let chart = Chart::default()
+        .with_title("Bar Plot")
+        .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
+        .with_axis(Vertical::new(domain).with_name("Y Axis Name"))
+        .with_bar_plot(plot)
+        .render();
+    print!("{chart}");
+}
@@ -0,0 +1,23 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    BubblePlot, Chart,
+};
+
+fn main() {
+    let data = vec![
+        (13.0, 74.0, 12.0),
+        (111.0, 37.0, 40.0),
+        (125.0, 52.0, 0.0),
+        (190.0, 66.0, 25.0),
+    ];
+    let domain = BBox::new(data.iter().map(|(x, y, _)| (*x, *y)));
+    let plot = BubblePlot::new("Series", &domain, data);
+    let chart = Chart::default()
+        .with_title("Bubble Plot")
+        .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
+        .with_axis(Vertical::new(domain).with_name("Y Axis Name"))
+        .with_bubble_plot(plot)
+        .render();
+    print!("{chart}");
+}
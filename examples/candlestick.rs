@@ -0,0 +1,24 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Candlestick, Chart, Ohlc,
+};
+
+fn main() {
+    let data: Vec<Ohlc> = vec![
+        (1.0, 52.0, 60.0, 48.0, 57.0).into(),
+        (2.0, 57.0, 63.0, 55.0, 61.0).into(),
+        (3.0, 61.0, 62.0, 50.0, 53.0).into(),
+        (4.0, 53.0, 58.0, 45.0, 47.0).into(),
+        (5.0, 47.0, 56.0, 46.0, 55.0).into(),
+    ];
+    let domain = BBox::new([(0.0, 40.0), (6.0, 70.0)]);
+    let plot = Candlestick::new("Price", &domain, data);
+    let chart = Chart::default()
+        .with_title("Candlestick Plot")
+        .with_axis(Horizontal::new(domain).with_name("Day"))
+        .with_axis(Vertical::new(domain).with_name("Price"))
+        .with_candlestick_plot(plot)
+        .render();
+    print!("{chart}");
+}
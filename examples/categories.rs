@@ -0,0 +1,29 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Plot,
+};
+
+fn main() {
+    let days = ["Mon", "Tue", "Wed", "Thu", "Fri"];
+    let data = vec![
+        (0.0, 12.0),
+        (1.0, 19.0),
+        (2.0, 7.0),
+        (3.0, 15.0),
+        (4.0, 22.0),
+    ];
+    let domain = {
+        let mut domain = BBox::new(data.iter().cloned());
+        domain.extend([(0.0, 0.0)]);
+        domain
+    };
+    let plot = Plot::new("Visits", &domain, data);
+    let chart = Chart::default()
+        .with_title("Categorical Axis")
+        .with_axis(Horizontal::categories(days).with_name("Day"))
+        .with_axis(Vertical::new(domain).with_name("Visits"))
+        .with_bar_plot(plot)
+        .render();
+    print!("{chart}");
+}
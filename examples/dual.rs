@@ -0,0 +1,47 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Plot,
+};
+
+fn main() {
+    let temperature = vec![
+        (0.0, 12.0),
+        (6.0, 9.0),
+        (12.0, 24.0),
+        (18.0, 31.0),
+        (24.0, 15.0),
+    ];
+    let pressure = vec![
+        (0.0, 1013.0),
+        (6.0, 1009.0),
+        (12.0, 998.0),
+        (18.0, 1002.0),
+        (24.0, 1016.0),
+    ];
+    let temp_domain = BBox::new(temperature.iter().cloned());
+    let pres_domain = BBox::new(pressure.iter().cloned());
+    let plot_a = Plot::new("Temperature", &temp_domain, temperature)
+        .with_y_axis("temperature");
+    let plot_b =
+        Plot::new("Pressure", &pres_domain, pressure).with_y_axis("pressure");
+    let chart = Chart::default()
+        .with_title("Dual Axis Plot")
+        .with_axis(Horizontal::new(temp_domain).with_name("Hour"))
+        .with_axis(
+            Vertical::new(temp_domain)
+                .with_id("temperature")
+                .with_name("Temperature (°C)"),
+        )
+        .with_axis(
+            Vertical::new(pres_domain)
+                .with_id("pressure")
+                .with_name("Pressure (hPa)")
+                .on_right(),
+        )
+        .with_line_plot(plot_a)
+        .with_line_plot(plot_b)
+        .with_legend_axes()
+        .render();
+    print!("{chart}");
+}
@@ -0,0 +1,35 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Plot, Row,
+};
+
+fn main() {
+    let price = vec![(1.0, 74.0), (2.0, 77.0), (3.0, 71.0), (4.0, 80.0)];
+    let volume = vec![(1.0, 1200.0), (2.0, 900.0), (3.0, 1500.0), (4.0, 700.0)];
+    let price_domain = {
+        let mut domain = BBox::new(price.iter().cloned());
+        domain.extend([(0.5, 60.0), (4.5, 90.0)]);
+        domain
+    };
+    let volume_domain = {
+        let mut domain = BBox::new(volume.iter().cloned());
+        domain.extend([(0.5, 0.0), (4.5, 2000.0)]);
+        domain
+    };
+    let chart = Chart::default()
+        .with_title("Facets")
+        .with_axis(Horizontal::new(price_domain).with_name("Day"))
+        .with_row(
+            Row::new()
+                .with_axis(Vertical::new(price_domain).with_name("Price"))
+                .with_line_plot(Plot::new("Price", &price_domain, price)),
+        )
+        .with_row(
+            Row::new()
+                .with_axis(Vertical::new(volume_domain).with_name("Volume"))
+                .with_bar_plot(Plot::new("Volume", &volume_domain, volume)),
+        )
+        .render();
+    print!("{chart}");
+}
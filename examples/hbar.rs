@@ -0,0 +1,22 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Plot,
+};
+
+fn main() {
+    let data = vec![(74.0, 1.0), (37.0, 2.0), (52.0, 3.0), (66.0, 4.0)];
+    let domain = {
+        let mut domain = BBox::new(data.iter().cloned());
+        domain.extend([(0.0, 0.0), (0.0, 5.0)]);
+        domain
+    };
+    let plot = Plot::new("Series", &domain, data);
+    let chart = Chart::default()
+        .with_title("Horizontal Bar Plot")
+        .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
+        .with_axis(Vertical::new(domain).with_name("Y Axis Name"))
+        .with_hbar_plot(plot)
+        .render();
+    print!("{chart}");
+}
@@ -0,0 +1,21 @@
+use pointy::BBox;
+use splotch::{
+    axis::{ColorBar, Horizontal, Vertical},
+    Chart, Heatmap,
+};
+
+fn main() {
+    let rows: Vec<Vec<f32>> = (0..8)
+        .map(|r| (0..12).map(|c| ((r * c) as f32).sqrt()).collect())
+        .collect();
+    let domain = BBox::new([(0.0, 0.0), (12.0, 8.0)]);
+    let plot = Heatmap::new("Values", &domain, &rows).with_range(0.0, 9.0);
+    let chart = Chart::default()
+        .with_title("Heatmap")
+        .with_axis(Horizontal::new(domain).with_name("Column"))
+        .with_axis(Vertical::new(domain).with_name("Row"))
+        .with_axis(ColorBar::new(0.0, 9.0).with_name("Value"))
+        .with_heatmap(plot)
+        .render();
+    print!("{chart}");
+}
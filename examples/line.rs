@@ -19,17 +19,15 @@ fn main() {
         domain.extend(data_b.iter().cloned());
         domain
     };
-    let mut data_a = data_a.into_iter().map(Into::into);
-    let mut data_b = data_b.into_iter().map(Into::into);
-    let plot_a = Plot::new("Series A", &domain, &mut data_a);
-    let plot_b = Plot::new("Series B", &domain, &mut data_b);
+    let plot_a = Plot::new("Series A", &domain, data_a).with_markers();
+    let plot_b = Plot::new("Series B", &domain, data_b);
     let chart = Chart::default()
         .with_title("Line Plot")
         .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
         .with_axis(Vertical::new(domain).with_name("Y Axis Name"))
         .with_axis(Vertical::new(domain).on_right())
         .with_line_plot(plot_a)
-        .with_line_plot(plot_b)
+        .with_smooth_plot(plot_b)
         .render();
     print!("{chart}");
 }
@@ -0,0 +1,40 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Legend, Page, Plot,
+};
+
+fn main() {
+    let data = vec![(13.0, 74.0), (111.0, 37.0), (125.0, 52.0), (190.0, 66.0)];
+    let domain = {
+        let mut domain = BBox::new(data.iter().cloned());
+        domain.extend([(0.0, 0.0), (200.0, 100.0)]);
+        domain
+    };
+    let chart = |title: &str| {
+        Chart::default()
+            .with_title(title)
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain))
+            .with_legend(Legend::default().inside_top_right())
+    };
+    let page = Page::new(2, 2)
+        .with_title("Dashboard")
+        .with_chart_span(
+            chart("Line").with_line_plot(Plot::new("A", &domain, data.clone())),
+            1,
+            2,
+        )
+        .with_chart(chart("Area").with_area_plot(Plot::new(
+            "B",
+            &domain,
+            data.clone(),
+        )))
+        .with_chart(chart("Bar").with_bar_plot(Plot::new(
+            "C",
+            &domain,
+            data.clone(),
+        )))
+        .render();
+    print!("{page}");
+}
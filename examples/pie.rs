@@ -0,0 +1,10 @@
+use splotch::{Chart, Pie};
+
+fn main() {
+    let slices = [("Rent", 1200.0), ("Food", 450.0), ("Travel", 300.0)];
+    let chart = Chart::default()
+        .with_title("Donut Chart")
+        .with_pie(Pie::new(&slices).with_inner_radius(0.5))
+        .render();
+    print!("{chart}");
+}
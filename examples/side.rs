@@ -0,0 +1,19 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Plot, Title,
+};
+
+fn main() {
+    let data = vec![(13.0, 74.0), (111.0, 37.0), (125.0, 52.0), (190.0, 66.0)];
+    let domain = BBox::new(data.iter().cloned());
+    let plot = Plot::new("Series", &domain, data).with_markers();
+    let chart = Chart::default()
+        .with_title(Title::new("Side").on_left())
+        .with_title(Title::subtitle("Rotated title"))
+        .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
+        .with_axis(Vertical::new(domain).with_name("Y Axis Name"))
+        .with_line_plot(plot)
+        .render();
+    print!("{chart}");
+}
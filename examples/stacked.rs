@@ -0,0 +1,20 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Plot,
+};
+
+fn main() {
+    let data_a = vec![(10.0, 24.0), (20.0, 37.0), (30.0, 32.0), (40.0, 26.0)];
+    let data_b = vec![(10.0, 30.0), (20.0, 24.0), (30.0, 37.0), (40.0, 39.0)];
+    let domain = BBox::new([(10.0, 0.0), (40.0, 80.0)]);
+    let plot_a = Plot::new("Series A", &domain, data_a);
+    let plot_b = Plot::new("Series B", &domain, data_b);
+    let chart = Chart::default()
+        .with_title("Stacked Area Plot")
+        .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
+        .with_axis(Vertical::new(domain).with_name("Y Axis Name"))
+        .with_stacked_area_plots(vec![plot_a, plot_b])
+        .render();
+    print!("{chart}");
+}
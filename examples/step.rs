@@ -0,0 +1,23 @@
+use pointy::BBox;
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Plot, Step,
+};
+
+fn main() {
+    let data_a =
+        vec![(13.0, 74.0), (111.0, 37.0), (125.0, 52.0), (190.0, 66.0)];
+    let data_b =
+        vec![(22.0, 50.0), (105.0, 44.0), (120.0, 67.0), (180.0, 39.0)];
+    let domain = BBox::new(data_a.iter().cloned());
+    let plot_a = Plot::new("Series A", &domain, data_a);
+    let plot_b = Plot::new("Series B", &domain, data_b);
+    let chart = Chart::default()
+        .with_title("Step Plot")
+        .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
+        .with_axis(Vertical::new(domain).with_name("Y Axis Name"))
+        .with_step_plot(plot_a)
+        .with_step_plot_at(plot_b, Step::Pre)
+        .render();
+    print!("{chart}");
+}
@@ -0,0 +1,22 @@
+use splotch::{
+    axis::{Horizontal, Vertical},
+    Chart, Waterfall,
+};
+
+fn main() {
+    let deltas = [
+        ("Revenue", 420.0),
+        ("Costs", -180.0),
+        ("Taxes", -60.0),
+        ("Grants", 45.0),
+    ];
+    let plot = Waterfall::new("Profit", &deltas).with_total_label("Profit");
+    let domain = plot.domain();
+    let chart = Chart::default()
+        .with_title("Waterfall Plot")
+        .with_axis(Horizontal::new(domain))
+        .with_axis(Vertical::new(domain).with_name("Amount"))
+        .with_waterfall_plot(plot)
+        .render();
+    print!("{chart}");
+}
@@ -0,0 +1,385 @@
+// annotation.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Chart annotations
+
+use std::{fmt, fmt::Write};
+
+use crate::{
+    plot::Mapping,
+    text::{Anchor, Escape},
+};
+
+/// Length of annotation arrow heads
+const ARROW_LEN: f32 = 16.0;
+
+/// Reference line at a data value
+///
+/// The line spans the full width or height of the plot area, and is not
+/// drawn if the value is outside of the axis scale.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefLine {
+    vertical: bool,
+    value: f32,
+    label: Option<String>,
+    class: Option<String>,
+    dash: Option<Vec<f32>>,
+    label_at_start: bool,
+}
+
+/// Shaded region between two data values
+///
+/// The region spans the full height (or width) of the plot area, and is
+/// clipped to the visible portion of the axis scale.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Region {
+    vertical: bool,
+    start: f32,
+    end: f32,
+    label: Option<String>,
+}
+
+/// Text note pinned to a data point
+///
+/// The text is offset from the point, and can be connected to it with an
+/// arrow.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotation {
+    text: String,
+    point: (f32, f32),
+    offset: (f32, f32),
+    anchor: Anchor,
+    arrow: bool,
+}
+
+impl RefLine {
+    /// Create a horizontal line at a `Y` value
+    pub fn horizontal(y: f32) -> Self {
+        Self {
+            vertical: false,
+            value: y,
+            label: None,
+            class: None,
+            dash: None,
+            label_at_start: false,
+        }
+    }
+
+    /// Create a vertical line at an `X` value
+    pub fn vertical(x: f32) -> Self {
+        Self {
+            vertical: true,
+            ..Self::horizontal(x)
+        }
+    }
+
+    /// Add a text label, at the right or top end of the line
+    pub fn with_label<L>(mut self, label: L) -> Self
+    where
+        L: Into<String>,
+    {
+        let label = label.into();
+        self.label = if label.is_empty() { None } else { Some(label) };
+        self
+    }
+
+    /// Add a class name, for styling
+    pub fn with_class<C>(mut self, class: C) -> Self
+    where
+        C: Into<String>,
+    {
+        self.class = Some(class.into());
+        self
+    }
+
+    /// Set the dash pattern, replacing the default
+    pub fn with_dash(mut self, dash: &[f32]) -> Self {
+        self.dash = Some(dash.to_vec());
+        self
+    }
+
+    /// Place the label at the left or bottom end of the line
+    pub fn label_at_start(mut self) -> Self {
+        self.label_at_start = true;
+        self
+    }
+
+    /// Display the reference line
+    pub(crate) fn display(
+        &self,
+        f: &mut dyn Write,
+        map: &Mapping,
+    ) -> fmt::Result {
+        let visible = if self.vertical {
+            map.x_contains(self.value)
+        } else {
+            map.y_contains(self.value)
+        };
+        if !visible {
+            return Ok(());
+        }
+        let rect = map.rect();
+        let (x_min, x_max) = (rect.x_min() as i32, rect.x_max() as i32);
+        let (y_min, y_max) = (rect.y_min() as i32, rect.y_max() as i32);
+        write!(f, "<path class='ref-line")?;
+        if let Some(class) = &self.class {
+            write!(f, " {}", Escape(class))?;
+        }
+        write!(f, "'")?;
+        if let Some(dash) = &self.dash {
+            let dash: Vec<String> =
+                dash.iter().map(|d| d.to_string()).collect();
+            write!(f, " style='stroke-dasharray: {}'", dash.join(" "))?;
+        }
+        if self.vertical {
+            let x = map.x_map(self.value);
+            writeln!(f, " d='M{x} {y_min}V{y_max}'/>")?;
+            if let Some(label) = &self.label {
+                let (y, dy) = if self.label_at_start {
+                    (y_max, -0.5)
+                } else {
+                    (y_min, 1.0)
+                };
+                write!(f, "<text class='ref-label' x='{}' y='{y}'", x + 8)?;
+                write!(f, " dy='{dy}em' text-anchor='start'>")?;
+                writeln!(f, "{}</text>", Escape(label))?;
+            }
+        } else {
+            let y = map.y_map(self.value);
+            writeln!(f, " d='M{x_min} {y}H{x_max}'/>")?;
+            if let Some(label) = &self.label {
+                let (x, anchor) = if self.label_at_start {
+                    (x_min + 8, "start")
+                } else {
+                    (x_max - 8, "end")
+                };
+                write!(f, "<text class='ref-label' x='{x}' y='{y}'")?;
+                write!(f, " dy='-0.5em' text-anchor='{anchor}'>")?;
+                writeln!(f, "{}</text>", Escape(label))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Region {
+    /// Create a horizontal region between two `Y` values
+    pub fn horizontal(y0: f32, y1: f32) -> Self {
+        Self {
+            vertical: false,
+            start: y0.min(y1),
+            end: y0.max(y1),
+            label: None,
+        }
+    }
+
+    /// Create a vertical region between two `X` values
+    pub fn vertical(x0: f32, x1: f32) -> Self {
+        Self {
+            vertical: true,
+            ..Self::horizontal(x0, x1)
+        }
+    }
+
+    /// Add a text label, at the top-left corner of the region
+    pub fn with_label<L>(mut self, label: L) -> Self
+    where
+        L: Into<String>,
+    {
+        let label = label.into();
+        self.label = if label.is_empty() { None } else { Some(label) };
+        self
+    }
+
+    /// Display the region
+    pub(crate) fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+    ) -> fmt::Result {
+        let rect = map.rect();
+        let (x_min, x_max) = (rect.x_min() as i32, rect.x_max() as i32);
+        let (y_min, y_max) = (rect.y_min() as i32, rect.y_max() as i32);
+        let (x0, x1, y0, y1) = if self.vertical {
+            let (a, b) = (map.x_map(self.start), map.x_map(self.end));
+            let (a, b) = (a.min(b).max(x_min), a.max(b).min(x_max));
+            (a, b, y_min, y_max)
+        } else {
+            let (a, b) = (map.y_map(self.start), map.y_map(self.end));
+            let (a, b) = (a.min(b).max(y_min), a.max(b).min(y_max));
+            (x_min, x_max, a, b)
+        };
+        if x1 <= x0 || y1 <= y0 {
+            return Ok(());
+        }
+        let (width, height) = (x1 - x0, y1 - y0);
+        write!(f, "<rect class='region band-{num}' x='{x0}' y='{y0}'")?;
+        writeln!(f, " width='{width}' height='{height}'/>")?;
+        if let Some(label) = &self.label {
+            write!(f, "<text class='region-label' x='{}'", x0 + 8)?;
+            write!(f, " y='{y0}' dy='1em' text-anchor='start'>")?;
+            writeln!(f, "{}</text>", Escape(label))?;
+        }
+        Ok(())
+    }
+}
+
+impl Annotation {
+    /// Create a new annotation at an `(x, y)` data point
+    ///
+    /// By default, the text is centered above the point.
+    pub fn new<T>(text: T, point: (f32, f32)) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            text: text.into(),
+            point,
+            offset: (0.0, -40.0),
+            anchor: Anchor::Middle,
+            arrow: false,
+        }
+    }
+
+    /// Set the offset of the text from the point (in SVG units)
+    ///
+    /// Negative `Y` offsets are above the point.
+    pub fn with_offset(mut self, dx: f32, dy: f32) -> Self {
+        self.offset = (dx, dy);
+        self
+    }
+
+    /// Anchor the text at its start
+    pub fn start(mut self) -> Self {
+        self.anchor = Anchor::Start;
+        self
+    }
+
+    /// Anchor the text at its end
+    pub fn end(mut self) -> Self {
+        self.anchor = Anchor::End;
+        self
+    }
+
+    /// Draw an arrow from the text to the point
+    pub fn with_arrow(mut self) -> Self {
+        self.arrow = true;
+        self
+    }
+
+    /// Display the annotation
+    pub(crate) fn display(
+        &self,
+        f: &mut dyn Write,
+        map: &Mapping,
+    ) -> fmt::Result {
+        let (x, y) = self.point;
+        if !map.x_contains(x) || !map.y_contains(y) {
+            return Ok(());
+        }
+        let (px, py) = (map.x_map(x) as f32, map.y_map(y) as f32);
+        let (dx, dy) = self.offset;
+        let (tx, ty) = ((px + dx).round(), (py + dy).round());
+        writeln!(f, "<g class='annotation'>")?;
+        let len = dx.hypot(dy);
+        if self.arrow && len > ARROW_LEN {
+            // Unit vector from the text to the point
+            let (ux, uy) = (-dx / len, -dy / len);
+            let (bx, by) = (px - ux * ARROW_LEN, py - uy * ARROW_LEN);
+            let (nx, ny) = (-uy * ARROW_LEN / 2.0, ux * ARROW_LEN / 2.0);
+            write!(f, "<path class='annotation-line' d='M{tx} {ty}")?;
+            writeln!(f, "L{bx:.0} {by:.0}'/>")?;
+            write!(f, "<path class='annotation-arrow' d='M{px} {py}")?;
+            write!(f, "L{:.0} {:.0}", bx + nx, by + ny)?;
+            writeln!(f, "L{:.0} {:.0}z'/>", bx - nx, by - ny)?;
+        }
+        let em = if dy > 0.0 { 1.0 } else { -0.33 };
+        write!(f, "<text class='annotation-text' x='{tx}' y='{ty}'")?;
+        write!(f, " dy='{em}em'")?;
+        self.anchor.display(f)?;
+        writeln!(f, ">{}</text>", Escape(&self.text))?;
+        writeln!(f, "</g>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pointy::BBox;
+
+    use super::*;
+    use crate::scale::Numeric;
+
+    fn render(line: RefLine) -> String {
+        let scale = Numeric::new(0.0, 100.0);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 200.0)]);
+        let map = Mapping::from_scales(scale.clone(), scale, rect);
+        let mut out = String::new();
+        line.display(&mut out, &map).unwrap();
+        out
+    }
+
+    #[test]
+    fn ref_lines() {
+        let out = render(RefLine::horizontal(25.0).with_label("SLA"));
+        assert!(out.contains("<path class='ref-line' d='M0 150H400'/>"));
+        assert!(out.contains("x='392' y='150' dy='-0.5em' text-anchor='end'"));
+        let out = render(
+            RefLine::vertical(50.0)
+                .with_class("release")
+                .with_dash(&[2.0, 2.0]),
+        );
+        assert_eq!(
+            out,
+            "<path class='ref-line release' \
+            style='stroke-dasharray: 2 2' d='M200 0V200'/>\n"
+        );
+        assert_eq!(render(RefLine::horizontal(150.0)), "");
+        assert_eq!(render(RefLine::vertical(-1.0)), "");
+    }
+
+    #[test]
+    fn annotation() {
+        let scale = Numeric::new(0.0, 100.0);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 200.0)]);
+        let map = Mapping::from_scales(scale.clone(), scale, rect);
+        let mut out = String::new();
+        Annotation::new("outlier", (50.0, 50.0))
+            .with_offset(0.0, -60.0)
+            .with_arrow()
+            .display(&mut out, &map)
+            .unwrap();
+        assert_eq!(
+            out,
+            "<g class='annotation'>\n\
+            <path class='annotation-line' d='M200 40L200 84'/>\n\
+            <path class='annotation-arrow' d='M200 100L192 84L208 84z'/>\n\
+            <text class='annotation-text' x='200' y='40' dy='-0.33em' \
+            text-anchor='middle'>outlier</text>\n\
+            </g>\n"
+        );
+    }
+
+    #[test]
+    fn regions() {
+        let scale = Numeric::new(0.0, 100.0);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 200.0)]);
+        let map = Mapping::from_scales(scale.clone(), scale, rect);
+        let render = |region: Region| {
+            let mut out = String::new();
+            region.display(&mut out, 1, &map).unwrap();
+            out
+        };
+        let out = render(Region::vertical(25.0, 50.0).with_label("Window"));
+        assert!(out.starts_with(
+            "<rect class='region band-1' x='100' y='0' \
+            width='100' height='200'/>"
+        ));
+        assert!(out.contains(">Window</text>"));
+        // Clipped to the visible portion
+        let out = render(Region::horizontal(80.0, 120.0));
+        assert!(out.contains("x='0' y='0' width='400' height='40'"));
+        assert_eq!(render(Region::horizontal(120.0, 150.0)), "");
+    }
+}
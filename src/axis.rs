@@ -8,14 +8,16 @@
 // FIXME
 #![allow(dead_code)]
 
-use std::{fmt, fmt::Write};
+use std::{fmt, fmt::Write, rc::Rc};
 
 use pointy::BBox;
 
 use crate::{
+    heatmap::ColorRamp,
+    number::NumberFormat,
     page::Edge,
-    scale::Numeric,
-    text::{Anchor, Label, Text, Tick},
+    scale::{Numeric, Scale},
+    text::{Anchor, Coord, Escape, Label, Link, Text, Tick},
 };
 
 /// Private module for sealed Axis trait
@@ -24,48 +26,183 @@ mod sealed {
 
     use pointy::BBox;
 
+    use crate::scale::Numeric;
+
     pub trait Axis {
+        fn x_scale(&self) -> Option<Numeric> {
+            None
+        }
+        fn y_scale(&self) -> Option<Numeric> {
+            None
+        }
+        fn id(&self) -> Option<&str> {
+            None
+        }
+        fn name(&self) -> Option<&str> {
+            None
+        }
+        fn bound_x_scale(&self) -> Option<Numeric> {
+            None
+        }
+        fn bound_y_scale(&self) -> Option<Numeric> {
+            None
+        }
+        fn set_auto_domain(&mut self, _domain: BBox<f32>) {}
         fn split(&self, area: &mut BBox<f32>) -> BBox<f32>;
         fn display(
             &self,
             f: &mut dyn Write,
             rect: BBox<f32>,
             area: BBox<f32>,
+            precision: u8,
         ) -> fmt::Result;
         fn display_grid(
             &self,
             f: &mut dyn Write,
             area: BBox<f32>,
+            precision: u8,
         ) -> fmt::Result;
+        fn display_bands(
+            &self,
+            _f: &mut dyn Write,
+            _area: BBox<f32>,
+            _precision: u8,
+        ) -> fmt::Result {
+            Ok(())
+        }
     }
 }
 
 /// Axis for drawing labels on a `Chart`
 ///
-/// This trait is *sealed* to hide details.  There are two implementors:
+/// This trait is *sealed* to hide details.  There are three implementors:
 /// - `axis::Horizontal`
 /// - `axis::Vertical`
+/// - `axis::ColorBar`
 pub trait Axis: sealed::Axis {}
 
 /// Horizontal `X` axis
 #[derive(Debug, PartialEq)]
 pub struct Horizontal {
     edge: Edge,
-    ticks: Vec<Tick>,
+    ticker: Ticker,
+    id: Option<String>,
     name: Option<String>,
+    name_link: Option<Link>,
     label: Label,
+    rotation: f32,
+    label_every: Option<usize>,
+    char_width: f32,
+    inner_ticks: bool,
+    tick_len: f32,
+    class: Option<String>,
+    name_height: u16,
+    label_dy: f32,
+    space: Option<u16>,
+    parts: Parts,
+    grid: bool,
+    auto: bool,
 }
 
 /// Vertical `Y` axis
 #[derive(Debug, PartialEq)]
 pub struct Vertical {
     edge: Edge,
-    ticks: Vec<Tick>,
+    ticker: Ticker,
+    id: Option<String>,
     name: Option<String>,
+    name_link: Option<Link>,
     label: Label,
+    label_every: Option<usize>,
+    char_width: f32,
+    inner_ticks: bool,
+    tick_len: f32,
+    class: Option<String>,
+    name_height: u16,
+    label_dy: f32,
+    space: Option<u16>,
+    parts: Parts,
+    grid: bool,
+    auto: bool,
+}
+
+/// Visible parts of an axis
+#[derive(Debug, PartialEq)]
+struct Parts {
+    line: bool,
+    ticks: bool,
+    labels: bool,
+}
+
+/// Tick label formatter
+#[derive(Clone)]
+struct Formatter(Rc<dyn Fn(f32) -> String>);
+
+/// Tick generator for an axis
+#[derive(Debug, PartialEq)]
+struct Ticker {
+    scale: Numeric,
+    categories: Option<Vec<String>>,
+    custom: Option<Vec<(f32, Option<String>)>>,
+    formatter: Option<Formatter>,
+    count: Option<usize>,
+    min_spacing: Option<f32>,
+    ranged: bool,
+    nice: bool,
+    zero: bool,
+    reversed: bool,
+    percent: Option<usize>,
+    si_unit: Option<String>,
+    integer: bool,
+    unit: Option<(String, bool)>,
+    sci_thresholds: (f32, f32),
+    sci_pretty: bool,
+    number_format: NumberFormat,
+}
+
+/// Notation for generated tick values
+#[derive(Clone, Copy)]
+enum Notation {
+    /// Fixed decimal places
+    Fixed(usize),
+    /// Scientific notation, with the precision of a step
+    Scientific(f32),
+}
+
+/// Color bar showing the values of a `ColorRamp`
+///
+/// The color bar is attached to the right side of a `Chart`, with a vertical
+/// scale of values.
+#[derive(Debug, PartialEq)]
+pub struct ColorBar {
+    ramp: ColorRamp,
+    axis: Vertical,
 }
 
 impl sealed::Axis for Horizontal {
+    fn x_scale(&self) -> Option<Numeric> {
+        self.ticker.scale_override()
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn bound_x_scale(&self) -> Option<Numeric> {
+        Some(self.ticker.scale())
+    }
+
+    fn set_auto_domain(&mut self, domain: BBox<f32>) {
+        if self.auto {
+            let scale = Numeric::from_data(domain, |pt| pt.x());
+            self.ticker.set_auto_scale(scale);
+        }
+    }
+
     fn split(&self, area: &mut BBox<f32>) -> BBox<f32> {
         self.edge.split(area, f32::from(self.space()))
     }
@@ -73,47 +210,166 @@ impl sealed::Axis for Horizontal {
     fn display(
         &self,
         f: &mut dyn Write,
-        mut rect: BBox<f32>,
+        rect: BBox<f32>,
         area: BBox<f32>,
+        precision: u8,
     ) -> fmt::Result {
-        intersect_horiz(&mut rect, &area);
+        let Some(mut rect) = intersect_horiz(rect, area) else {
+            return Ok(());
+        };
         if let Some(name) = &self.name {
-            let r = self.edge.split(&mut rect, f32::from(self.space() / 2));
-            let text =
-                Text::new(self.edge).with_rect(r).with_class_name("axis");
+            let r = self.edge.split(&mut rect, f32::from(self.name_height));
+            let class = class_list("axis", self.class.as_deref());
+            let text = Text::new(self.edge)
+                .with_rect(r)
+                .with_class_name(&class)
+                .with_link(self.name_link.as_ref());
             text.display(f)?;
-            writeln!(f, "{}", name)?;
+            writeln!(f, "{}", Escape(name))?;
             text.display_done(f)?;
         }
-        self.display_tick_lines(f, rect)?;
-        self.display_tick_labels(f, rect)
+        self.display_tick_lines(f, rect, precision)?;
+        if self.parts.labels {
+            self.display_tick_labels(f, rect)?;
+        }
+        Ok(())
     }
 
-    fn display_grid(&self, f: &mut dyn Write, area: BBox<f32>) -> fmt::Result {
-        write!(f, "<path class='grid-x' d='")?;
-        for tick in self.ticks.iter() {
-            let x = tick.x(self.edge, area, 0.0);
+    fn display_grid(
+        &self,
+        f: &mut dyn Write,
+        area: BBox<f32>,
+        precision: u8,
+    ) -> fmt::Result {
+        let ticks = self.ticks(Some(area.x_span()));
+        if !self.grid || ticks.is_empty() {
+            return Ok(());
+        }
+        let class = class_list("grid-x", self.class.as_deref());
+        write!(f, "<path class='{}' d='", Escape(&class))?;
+        for tick in ticks.iter() {
+            let x = Coord::new(tick.x(self.edge, area, 0.0), precision);
             write!(f, "M{} {}v{}", x, area.y_min(), area.y_span())?;
         }
         writeln!(f, "'/>")
     }
+
+    fn display_bands(
+        &self,
+        f: &mut dyn Write,
+        area: BBox<f32>,
+        precision: u8,
+    ) -> fmt::Result {
+        let ticks = self.ticks(Some(area.x_span()));
+        let xs = band_edges(
+            ticks.iter().map(|t| t.x(self.edge, area, 0.0)),
+            (area.x_min(), area.x_max()),
+            precision,
+        );
+        if xs.len() < 2 {
+            return Ok(());
+        }
+        writeln!(f, "<g class='band-x'>")?;
+        for x in xs.windows(2).step_by(2) {
+            write!(f, "<rect x='{}' y='{}'", x[0], area.y_min())?;
+            let width = x[1].delta(x[0]);
+            writeln!(f, " width='{width}' height='{}'/>", area.y_span())?;
+        }
+        writeln!(f, "</g>")
+    }
 }
 
 impl Axis for Horizontal {}
 
 impl Horizontal {
+    /// Default space reserved for the axis name
+    const NAME_SPACE: u16 = 80;
+
     /// Create a new horizontal axis
     pub fn new(domain: BBox<f32>) -> Self {
         let x_scale = Numeric::from_data(domain, |pt| pt.x());
 
         Self {
             edge: Edge::Bottom,
-            ticks: x_scale.ticks(),
+            ticker: Ticker::new(x_scale),
+            id: None,
+            name: None,
+            name_link: None,
+            label: Label::new(),
+            rotation: 0.0,
+            label_every: None,
+            char_width: Tick::CHAR_WIDTH,
+            inner_ticks: false,
+            tick_len: Tick::LEN,
+            class: None,
+            name_height: Self::NAME_SPACE,
+            label_dy: Tick::DY,
+            space: None,
+            parts: Parts::default(),
+            grid: true,
+            auto: false,
+        }
+    }
+
+    /// Create a new axis with an automatic domain
+    ///
+    /// The domain is computed from all automatic plots on a `Chart`.
+    pub fn auto() -> Self {
+        Self {
+            auto: true,
+            ..Self::new(BBox::new([(0.0, 0.0), (1.0, 1.0)]))
+        }
+    }
+
+    /// Create a new categorical axis
+    ///
+    /// Each category is centered within an evenly spaced band, and labeled
+    /// with its name.  Plots on the same `Chart` use category indices as their
+    /// `X` values: `0.0` for the first category, `1.0` for the second, etc.
+    pub fn categories<I, C>(categories: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<String>,
+    {
+        Self {
+            edge: Edge::Bottom,
+            ticker: Ticker::categories(categories),
+            id: None,
             name: None,
+            name_link: None,
             label: Label::new(),
+            rotation: 0.0,
+            label_every: None,
+            char_width: Tick::CHAR_WIDTH,
+            inner_ticks: false,
+            tick_len: Tick::LEN,
+            class: None,
+            name_height: Self::NAME_SPACE,
+            label_dy: Tick::DY,
+            space: None,
+            parts: Parts::default(),
+            grid: true,
+            auto: false,
         }
     }
 
+    /// Get the width of each category band
+    ///
+    /// This is a fraction of the axis length, or `None` if the axis is not
+    /// categorical.
+    pub fn band_width(&self) -> Option<f32> {
+        self.ticker.band_width()
+    }
+
+    /// Set the id of the axis, for binding plots
+    pub fn with_id<I>(mut self, id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.id = Some(id.into());
+        self
+    }
+
     /// Set the name of the axis
     pub fn with_name<N>(mut self, name: N) -> Self
     where
@@ -123,6 +379,299 @@ impl Horizontal {
         self
     }
 
+    /// Link the axis name to a URL
+    pub fn with_name_href<L: Into<Link>>(mut self, link: L) -> Self {
+        self.name_link = Some(link.into());
+        self
+    }
+
+    /// Reverse the axis, so values increase right-to-left
+    ///
+    /// Plots are mirrored along with the ticks and grid lines.
+    pub fn reversed(mut self) -> Self {
+        self.ticker.reversed = true;
+        self
+    }
+
+    /// Set a fixed range, independent of the data domain
+    ///
+    /// Plots bound to this axis are mapped through the range too.  This has
+    /// no effect on categorical axes.
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.ticker.set_range(min, max);
+        self
+    }
+
+    /// Set a custom scale, instead of a linear numeric one (default)
+    ///
+    /// Ticks are placed at the scale's tick values, and plots bound to this
+    /// axis are mapped through the scale.  This has no effect on categorical
+    /// axes.
+    pub fn with_scale(mut self, scale: impl Scale + 'static) -> Self {
+        self.ticker.set_scale(Rc::new(scale));
+        self
+    }
+
+    /// Extend the range to include zero
+    ///
+    /// Plots bound to this axis are mapped through the extended range.  This
+    /// has no effect on categorical axes or with a fixed range.
+    pub fn include_zero(mut self) -> Self {
+        self.ticker.zero = true;
+        self
+    }
+
+    /// Round the range outward to tick steps
+    ///
+    /// Plots bound to this axis are mapped through the rounded range, so the
+    /// data doesn't touch the edges.  A positive range starting near zero is
+    /// extended to zero.  This has no effect on categorical axes or with a
+    /// fixed range.
+    pub fn nice(mut self) -> Self {
+        self.ticker.nice = true;
+        self
+    }
+
+    /// Enable or disable grid lines (enabled by default)
+    pub fn with_grid(mut self, grid: bool) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    /// Hide the axis line
+    pub fn hide_line(mut self) -> Self {
+        self.parts.line = false;
+        self
+    }
+
+    /// Hide the tick marks
+    ///
+    /// Tick labels and grid lines are still drawn.
+    pub fn hide_ticks(mut self) -> Self {
+        self.parts.ticks = false;
+        self
+    }
+
+    /// Hide the tick labels
+    pub fn hide_labels(mut self) -> Self {
+        self.parts.labels = false;
+        self
+    }
+
+    /// Set the space reserved for the axis, in pixels
+    ///
+    /// By default, this is estimated from the tick labels and name.
+    pub fn with_space(mut self, space: u16) -> Self {
+        self.space = Some(space);
+        self
+    }
+
+    /// Set explicit tick positions
+    ///
+    /// These replace the generated ticks.  Values outside the axis domain are
+    /// dropped.
+    pub fn with_ticks(mut self, values: &[f32]) -> Self {
+        self.ticker.custom = Some(values.iter().map(|v| (*v, None)).collect());
+        self
+    }
+
+    /// Set explicit tick positions with labels
+    ///
+    /// These replace the generated ticks.  Values outside the axis domain are
+    /// dropped.
+    pub fn with_labeled_ticks<I, L>(mut self, ticks: I) -> Self
+    where
+        I: IntoIterator<Item = (f32, L)>,
+        L: Into<String>,
+    {
+        self.ticker.custom = Some(
+            ticks
+                .into_iter()
+                .map(|(v, label)| (v, Some(label.into())))
+                .collect(),
+        );
+        self
+    }
+
+    /// Set the approximate number of ticks
+    ///
+    /// Tick steps are still chosen as 1, 2 or 5 times a power of 10.
+    pub fn with_tick_count(mut self, count: usize) -> Self {
+        self.ticker.count = Some(count);
+        self
+    }
+
+    /// Set the minimum spacing between ticks, in pixels
+    ///
+    /// Ticks are thinned to fit the rendered axis length.
+    pub fn with_min_tick_spacing(mut self, px: f32) -> Self {
+        self.ticker.min_spacing = Some(px);
+        self
+    }
+
+    /// Only place ticks at whole numbers
+    ///
+    /// This is useful for counts.  With a span less than 1, a single tick is
+    /// placed at the nearest whole number, if it's within the axis.
+    pub fn with_integer_ticks(mut self) -> Self {
+        self.ticker.integer = true;
+        self
+    }
+
+    /// Format tick labels with SI prefixes, such as `2.5M` or `300k`
+    ///
+    /// All labels on the axis share the prefix of the largest tick.
+    pub fn with_si_labels(self) -> Self {
+        self.with_si_unit("")
+    }
+
+    /// Format tick labels with SI prefixes and a unit, such as `2.5 MB`
+    pub fn with_si_unit<U>(mut self, unit: U) -> Self
+    where
+        U: Into<String>,
+    {
+        self.ticker.si_unit = Some(unit.into());
+        self
+    }
+
+    /// Append a unit to each tick label, such as `5 ms`
+    pub fn with_unit<U>(mut self, unit: U) -> Self
+    where
+        U: Into<String>,
+    {
+        self.ticker.unit = Some((unit.into(), false));
+        self
+    }
+
+    /// Append a unit only to the label of the last (largest) tick
+    pub fn with_unit_on_last_tick<U>(mut self, unit: U) -> Self
+    where
+        U: Into<String>,
+    {
+        self.ticker.unit = Some((unit.into(), true));
+        self
+    }
+
+    /// Format tick labels as percentages
+    ///
+    /// Values are multiplied by 100, with `%` appended.  A formatter set with
+    /// `with_tick_formatter` takes precedence.
+    pub fn with_percent_labels(self) -> Self {
+        self.with_percent_decimals(0)
+    }
+
+    /// Format tick labels as percentages with decimal places
+    pub fn with_percent_decimals(mut self, places: usize) -> Self {
+        self.ticker.percent = Some(places);
+        self
+    }
+
+    /// Set the tick steps outside of which labels use scientific notation
+    ///
+    /// By default, steps below `1e-4` or above `1e6` use scientific notation.
+    pub fn with_sci_thresholds(mut self, small: f32, large: f32) -> Self {
+        self.ticker.sci_thresholds = (small, large);
+        self
+    }
+
+    /// Write scientific notation with superscript exponents, like `2×10⁻⁷`
+    pub fn with_pretty_sci(mut self) -> Self {
+        self.ticker.sci_pretty = true;
+        self
+    }
+
+    /// Set the number format of tick labels
+    ///
+    /// A formatter set with `with_tick_formatter` takes precedence.
+    pub fn with_number_format(mut self, format: NumberFormat) -> Self {
+        self.ticker.number_format = format;
+        self
+    }
+
+    /// Set a formatter for tick labels
+    ///
+    /// The formatter is called with the value of each tick.
+    pub fn with_tick_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(f32) -> String + 'static,
+    {
+        self.ticker.formatter = Some(Formatter(Rc::new(formatter)));
+        self
+    }
+
+    /// Rotate tick labels, in degrees
+    ///
+    /// Labels are rotated counter-clockwise around their tick, reading up
+    /// toward it.  Useful values are 45 or 90.
+    pub fn with_label_rotation(mut self, degrees: f32) -> Self {
+        self.rotation = degrees.clamp(0.0, 90.0);
+        self
+    }
+
+    /// Set the length of tick marks (default 20)
+    ///
+    /// Tick labels are offset from the axis line by a distance derived from
+    /// the length.
+    pub fn with_tick_len(mut self, len: f32) -> Self {
+        self.tick_len = len.max(0.0);
+        self
+    }
+
+    /// Set the space reserved for the axis name (default 80)
+    ///
+    /// The rest of the axis space is used for ticks and their labels.
+    pub fn with_name_height(mut self, height: u16) -> Self {
+        self.name_height = height;
+        self
+    }
+
+    /// Set the vertical offset of tick labels, in em (default 0.33)
+    ///
+    /// The offset is relative to the label font size, so the default centers
+    /// labels on their ticks at any size.  Adjust it for fonts with unusual
+    /// proportions.
+    pub fn with_label_dy(mut self, dy: f32) -> Self {
+        self.label_dy = dy;
+        self
+    }
+
+    /// Add a class to the axis line, grid, tick labels and name
+    ///
+    /// Classes can be added more than once, or separated by spaces.
+    pub fn with_class(mut self, class: &str) -> Self {
+        let class = class.split_whitespace();
+        let classes = self.class.iter().flat_map(|c| c.split_whitespace());
+        let classes: Vec<_> = classes.chain(class).collect();
+        self.class = (!classes.is_empty()).then(|| classes.join(" "));
+        self
+    }
+
+    /// Draw tick marks pointing into the plot area
+    ///
+    /// Tick labels stay outside of the plot area.
+    pub fn with_inner_ticks(mut self) -> Self {
+        self.inner_ticks = true;
+        self
+    }
+
+    /// Label only every `n`th tick, starting with the first
+    ///
+    /// By default, labels which would overlap are skipped automatically.
+    /// Tick marks and grid lines are still drawn for unlabeled ticks.
+    pub fn with_label_every(mut self, n: usize) -> Self {
+        self.label_every = Some(n.max(1));
+        self
+    }
+
+    /// Set the estimated width of one tick label character, in pixels
+    ///
+    /// This is used to skip overlapping labels, and to reserve space for
+    /// rotated labels.  The default is 16.
+    pub fn with_label_char_width(mut self, px: f32) -> Self {
+        self.char_width = px.max(0.0);
+        self
+    }
+
     /// Attach to the top of a `Chart`
     ///
     /// By default, a `Horizontal` axis is attached to the bottom of a `Chart`.
@@ -131,10 +680,34 @@ impl Horizontal {
         self
     }
 
+    fn ticks(&self, len: Option<f32>) -> Vec<Tick> {
+        self.ticker.ticks(false, len)
+    }
+
+    /// Minimum space reserved for ticks and their labels
+    fn min_tick_space(&self) -> u16 {
+        (Tick::vlen(self.tick_len) + Tick::HEIGHT).ceil() as u16
+    }
+
     fn space(&self) -> u16 {
+        if let Some(space) = self.space {
+            return space;
+        }
+        let ticks = if self.rotation > 0.0 {
+            // Make room for the longest rotated label
+            let ticks = self.ticks(None);
+            let chars = ticks.iter().map(|t| t.text().chars().count());
+            let width = chars.max().unwrap_or(0) as f32 * self.char_width;
+            let (sin, cos) = self.rotation.to_radians().sin_cos();
+            let height = width * sin + Tick::HEIGHT / 2.0 * cos;
+            let height = height + Tick::hlen(self.tick_len);
+            (height.ceil() as u16).max(self.min_tick_space())
+        } else {
+            self.min_tick_space()
+        };
         match self.name {
-            Some(_) => 160,
-            None => 80,
+            Some(_) => self.name_height + ticks,
+            None => ticks,
         }
     }
 
@@ -142,23 +715,36 @@ impl Horizontal {
         &self,
         f: &mut dyn Write,
         rect: BBox<f32>,
+        precision: u8,
     ) -> fmt::Result {
         let x = rect.x_min();
+        let len = if self.inner_ticks {
+            -self.tick_len
+        } else {
+            self.tick_len
+        };
         let (y, height) = match self.edge {
-            Edge::Top => (rect.y_max(), Tick::LEN),
-            Edge::Bottom => (rect.y_min(), -Tick::LEN),
+            Edge::Top => (rect.y_max(), len.round() as i32),
+            Edge::Bottom => (rect.y_min(), -len.round() as i32),
             _ => unreachable!(),
         };
-        write!(
-            f,
-            "<path class='axis-line' d='M{} {}h{}",
-            x,
-            y,
-            rect.x_span()
-        )?;
-        for tick in self.ticks.iter() {
-            let x = tick.x(self.edge, rect, Tick::LEN as f32) as i32;
-            let y = tick.y(self.edge, rect, Tick::LEN as f32) as i32;
+        if !self.parts.line && !self.parts.ticks {
+            return Ok(());
+        }
+        let class = class_list("axis-line", self.class.as_deref());
+        write!(f, "<path class='{}' d='", Escape(&class))?;
+        if self.parts.line {
+            write!(f, "M{} {}h{}", x, y, rect.x_span())?;
+        }
+        let ticks = if self.parts.ticks {
+            self.ticks(Some(rect.x_span()))
+        } else {
+            vec![]
+        };
+        for tick in ticks.iter() {
+            let x = tick.x(self.edge, rect, len);
+            let x = Coord::new(x, precision);
+            let y = tick.y(self.edge, rect, len) as i32;
             let y0 = y.min(y + height);
             let h = y.max(y + height) - y0;
             write!(f, "M{} {}v{}", x, y0, h)?;
@@ -171,16 +757,69 @@ impl Horizontal {
         f: &mut dyn Write,
         rect: BBox<f32>,
     ) -> fmt::Result {
-        let text = Text::new(Edge::Top).with_class_name("tick");
+        let ticks = self.ticks(Some(rect.x_span()));
+        let width = |tick: &Tick| {
+            if self.rotation > 0.0 {
+                Tick::HEIGHT / self.rotation.to_radians().sin()
+            } else {
+                tick.text().chars().count() as f32 * self.char_width
+            }
+        };
+        let labels =
+            thin_labels(&ticks, rect.x_span(), self.label_every, width);
+        let class = class_list("tick", self.class.as_deref());
+        if self.rotation > 0.0 {
+            for tick in labels {
+                let span = tick.rotated_tspan(
+                    self.edge,
+                    rect,
+                    self.tick_len,
+                    self.label_dy,
+                );
+                Tick::display_rotated(
+                    f,
+                    span,
+                    self.edge,
+                    self.rotation,
+                    &class,
+                )?;
+            }
+            return Ok(());
+        }
+        let text = Text::new(Edge::Top).with_class_name(&class);
         text.display(f)?;
-        for tick in &self.ticks {
-            tick.tspan(self.edge, rect).display(f)?;
+        for tick in labels {
+            tick.tspan(self.edge, rect, self.tick_len, self.label_dy)
+                .display(f)?;
         }
         text.display_done(f)
     }
 }
 
 impl sealed::Axis for Vertical {
+    fn y_scale(&self) -> Option<Numeric> {
+        self.ticker.scale_override()
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn bound_y_scale(&self) -> Option<Numeric> {
+        Some(self.ticker.scale())
+    }
+
+    fn set_auto_domain(&mut self, domain: BBox<f32>) {
+        if self.auto {
+            let scale = Numeric::from_data(domain, |pt| pt.y());
+            self.ticker.set_auto_scale(scale);
+        }
+    }
+
     fn split(&self, area: &mut BBox<f32>) -> BBox<f32> {
         self.edge.split(area, self.space().into())
     }
@@ -188,49 +827,166 @@ impl sealed::Axis for Vertical {
     fn display(
         &self,
         f: &mut dyn Write,
-        mut rect: BBox<f32>,
+        rect: BBox<f32>,
         area: BBox<f32>,
+        precision: u8,
     ) -> fmt::Result {
-        intersect_vert(&mut rect, &area);
+        let Some(mut rect) = intersect_vert(rect, area) else {
+            return Ok(());
+        };
         if let Some(name) = &self.name {
-            let r = self.edge.split(&mut rect, f32::from(self.space() / 2));
-            let text =
-                Text::new(self.edge).with_rect(r).with_class_name("axis");
+            let r = self.edge.split(&mut rect, f32::from(self.name_height));
+            let class = class_list("axis", self.class.as_deref());
+            let text = Text::new(self.edge)
+                .with_rect(r)
+                .with_class_name(&class)
+                .with_link(self.name_link.as_ref());
             text.display(f)?;
-            writeln!(f, "{}", name)?;
+            writeln!(f, "{}", Escape(name))?;
             text.display_done(f)?;
         }
-        self.display_tick_lines(f, rect)?;
-        self.display_tick_labels(f, rect)
+        self.display_tick_lines(f, rect, precision)?;
+        if self.parts.labels {
+            self.display_tick_labels(f, rect)?;
+        }
+        Ok(())
     }
 
-    fn display_grid(&self, f: &mut dyn Write, area: BBox<f32>) -> fmt::Result {
-        write!(f, "<path class='grid-y' d='")?;
-        for tick in self.ticks.iter() {
-            let y = tick.y(self.edge, area, 0.0);
+    fn display_grid(
+        &self,
+        f: &mut dyn Write,
+        area: BBox<f32>,
+        precision: u8,
+    ) -> fmt::Result {
+        let ticks = self.ticks(Some(area.y_span()));
+        if !self.grid || ticks.is_empty() {
+            return Ok(());
+        }
+        let class = class_list("grid-y", self.class.as_deref());
+        write!(f, "<path class='{}' d='", Escape(&class))?;
+        for tick in ticks.iter() {
+            let y = Coord::new(tick.y(self.edge, area, 0.0), precision);
             write!(f, "M{} {}h{}", area.x_min(), y, area.x_span())?;
         }
         writeln!(f, "'/>")
     }
+
+    fn display_bands(
+        &self,
+        f: &mut dyn Write,
+        area: BBox<f32>,
+        precision: u8,
+    ) -> fmt::Result {
+        let ticks = self.ticks(Some(area.y_span()));
+        let ys = band_edges(
+            ticks.iter().map(|t| t.y(self.edge, area, 0.0)),
+            (area.y_min(), area.y_max()),
+            precision,
+        );
+        if ys.len() < 2 {
+            return Ok(());
+        }
+        writeln!(f, "<g class='band-y'>")?;
+        for y in ys.windows(2).step_by(2) {
+            write!(f, "<rect x='{}' y='{}'", area.x_min(), y[0])?;
+            let height = y[1].delta(y[0]);
+            writeln!(f, " width='{}' height='{height}'/>", area.x_span())?;
+        }
+        writeln!(f, "</g>")
+    }
 }
 
 impl Axis for Vertical {}
 
 impl Vertical {
+    /// Default space reserved for the axis name
+    const NAME_SPACE: u16 = 80;
+
     /// Create a new vertical axis
     pub fn new(domain: BBox<f32>) -> Self {
         let y_scale = Numeric::from_data(domain, |pt| pt.y());
 
         Self {
             edge: Edge::Left,
-            ticks: y_scale.inverted().ticks(),
+            ticker: Ticker::new(y_scale),
+            id: None,
             name: None,
+            name_link: None,
             label: Label::new(),
+            label_every: None,
+            char_width: Tick::CHAR_WIDTH,
+            inner_ticks: false,
+            tick_len: Tick::LEN,
+            class: None,
+            name_height: Self::NAME_SPACE,
+            label_dy: Tick::DY,
+            space: None,
+            parts: Parts::default(),
+            grid: true,
+            auto: false,
         }
     }
 
-    /// Set the name of the axis
-    pub fn with_name<N>(mut self, name: N) -> Self
+    /// Create a new axis with an automatic domain
+    ///
+    /// The domain is computed from all automatic plots on a `Chart`.
+    pub fn auto() -> Self {
+        Self {
+            auto: true,
+            ..Self::new(BBox::new([(0.0, 0.0), (1.0, 1.0)]))
+        }
+    }
+
+    /// Create a new categorical axis
+    ///
+    /// Each category is centered within an evenly spaced band, and labeled
+    /// with its name.  Plots on the same `Chart` use category indices as their
+    /// `Y` values: `0.0` for the bottom category, `1.0` for the next, etc.
+    pub fn categories<I, C>(categories: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<String>,
+    {
+        Self {
+            edge: Edge::Left,
+            ticker: Ticker::categories(categories),
+            id: None,
+            name: None,
+            name_link: None,
+            label: Label::new(),
+            label_every: None,
+            char_width: Tick::CHAR_WIDTH,
+            inner_ticks: false,
+            tick_len: Tick::LEN,
+            class: None,
+            name_height: Self::NAME_SPACE,
+            label_dy: Tick::DY,
+            space: None,
+            parts: Parts::default(),
+            grid: true,
+            auto: false,
+        }
+    }
+
+    /// Get the height of each category band
+    ///
+    /// This is a fraction of the axis length, or `None` if the axis is not
+    /// categorical.
+    pub fn band_width(&self) -> Option<f32> {
+        self.ticker.band_width()
+    }
+
+    /// Set the id of the axis, for binding plots
+    pub fn with_id<I>(mut self, id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set the name of the axis
+    pub fn with_name<N>(mut self, name: N) -> Self
     where
         N: Into<String>,
     {
@@ -238,6 +994,289 @@ impl Vertical {
         self
     }
 
+    /// Link the axis name to a URL
+    pub fn with_name_href<L: Into<Link>>(mut self, link: L) -> Self {
+        self.name_link = Some(link.into());
+        self
+    }
+
+    /// Reverse the axis, so values increase downward
+    ///
+    /// Plots are mirrored along with the ticks and grid lines.
+    pub fn reversed(mut self) -> Self {
+        self.ticker.reversed = true;
+        self
+    }
+
+    /// Set a fixed range, independent of the data domain
+    ///
+    /// Plots bound to this axis are mapped through the range too.  This has
+    /// no effect on categorical axes.
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.ticker.set_range(min, max);
+        self
+    }
+
+    /// Set a custom scale, instead of a linear numeric one (default)
+    ///
+    /// Ticks are placed at the scale's tick values, and plots bound to this
+    /// axis are mapped through the scale.  This has no effect on categorical
+    /// axes.
+    pub fn with_scale(mut self, scale: impl Scale + 'static) -> Self {
+        self.ticker.set_scale(Rc::new(scale));
+        self
+    }
+
+    /// Extend the range to include zero
+    ///
+    /// Plots bound to this axis are mapped through the extended range.  This
+    /// has no effect on categorical axes or with a fixed range.
+    pub fn include_zero(mut self) -> Self {
+        self.ticker.zero = true;
+        self
+    }
+
+    /// Round the range outward to tick steps
+    ///
+    /// Plots bound to this axis are mapped through the rounded range, so the
+    /// data doesn't touch the edges.  A positive range starting near zero is
+    /// extended to zero.  This has no effect on categorical axes or with a
+    /// fixed range.
+    pub fn nice(mut self) -> Self {
+        self.ticker.nice = true;
+        self
+    }
+
+    /// Enable or disable grid lines (enabled by default)
+    pub fn with_grid(mut self, grid: bool) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    /// Hide the axis line
+    pub fn hide_line(mut self) -> Self {
+        self.parts.line = false;
+        self
+    }
+
+    /// Hide the tick marks
+    ///
+    /// Tick labels and grid lines are still drawn.
+    pub fn hide_ticks(mut self) -> Self {
+        self.parts.ticks = false;
+        self
+    }
+
+    /// Hide the tick labels
+    pub fn hide_labels(mut self) -> Self {
+        self.parts.labels = false;
+        self
+    }
+
+    /// Set the space reserved for the axis, in pixels
+    ///
+    /// By default, this is estimated from the tick labels and name.
+    pub fn with_space(mut self, space: u16) -> Self {
+        self.space = Some(space);
+        self
+    }
+
+    /// Set explicit tick positions
+    ///
+    /// These replace the generated ticks.  Values outside the axis domain are
+    /// dropped.
+    pub fn with_ticks(mut self, values: &[f32]) -> Self {
+        self.ticker.custom = Some(values.iter().map(|v| (*v, None)).collect());
+        self
+    }
+
+    /// Set explicit tick positions with labels
+    ///
+    /// These replace the generated ticks.  Values outside the axis domain are
+    /// dropped.
+    pub fn with_labeled_ticks<I, L>(mut self, ticks: I) -> Self
+    where
+        I: IntoIterator<Item = (f32, L)>,
+        L: Into<String>,
+    {
+        self.ticker.custom = Some(
+            ticks
+                .into_iter()
+                .map(|(v, label)| (v, Some(label.into())))
+                .collect(),
+        );
+        self
+    }
+
+    /// Set the approximate number of ticks
+    ///
+    /// Tick steps are still chosen as 1, 2 or 5 times a power of 10.
+    pub fn with_tick_count(mut self, count: usize) -> Self {
+        self.ticker.count = Some(count);
+        self
+    }
+
+    /// Set the minimum spacing between ticks, in pixels
+    ///
+    /// Ticks are thinned to fit the rendered axis length.
+    pub fn with_min_tick_spacing(mut self, px: f32) -> Self {
+        self.ticker.min_spacing = Some(px);
+        self
+    }
+
+    /// Only place ticks at whole numbers
+    ///
+    /// This is useful for counts.  With a span less than 1, a single tick is
+    /// placed at the nearest whole number, if it's within the axis.
+    pub fn with_integer_ticks(mut self) -> Self {
+        self.ticker.integer = true;
+        self
+    }
+
+    /// Format tick labels with SI prefixes, such as `2.5M` or `300k`
+    ///
+    /// All labels on the axis share the prefix of the largest tick.
+    pub fn with_si_labels(self) -> Self {
+        self.with_si_unit("")
+    }
+
+    /// Format tick labels with SI prefixes and a unit, such as `2.5 MB`
+    pub fn with_si_unit<U>(mut self, unit: U) -> Self
+    where
+        U: Into<String>,
+    {
+        self.ticker.si_unit = Some(unit.into());
+        self
+    }
+
+    /// Append a unit to each tick label, such as `5 ms`
+    pub fn with_unit<U>(mut self, unit: U) -> Self
+    where
+        U: Into<String>,
+    {
+        self.ticker.unit = Some((unit.into(), false));
+        self
+    }
+
+    /// Append a unit only to the label of the last (largest) tick
+    pub fn with_unit_on_last_tick<U>(mut self, unit: U) -> Self
+    where
+        U: Into<String>,
+    {
+        self.ticker.unit = Some((unit.into(), true));
+        self
+    }
+
+    /// Format tick labels as percentages
+    ///
+    /// Values are multiplied by 100, with `%` appended.  A formatter set with
+    /// `with_tick_formatter` takes precedence.
+    pub fn with_percent_labels(self) -> Self {
+        self.with_percent_decimals(0)
+    }
+
+    /// Format tick labels as percentages with decimal places
+    pub fn with_percent_decimals(mut self, places: usize) -> Self {
+        self.ticker.percent = Some(places);
+        self
+    }
+
+    /// Set the tick steps outside of which labels use scientific notation
+    ///
+    /// By default, steps below `1e-4` or above `1e6` use scientific notation.
+    pub fn with_sci_thresholds(mut self, small: f32, large: f32) -> Self {
+        self.ticker.sci_thresholds = (small, large);
+        self
+    }
+
+    /// Write scientific notation with superscript exponents, like `2×10⁻⁷`
+    pub fn with_pretty_sci(mut self) -> Self {
+        self.ticker.sci_pretty = true;
+        self
+    }
+
+    /// Set the number format of tick labels
+    ///
+    /// A formatter set with `with_tick_formatter` takes precedence.
+    pub fn with_number_format(mut self, format: NumberFormat) -> Self {
+        self.ticker.number_format = format;
+        self
+    }
+
+    /// Set a formatter for tick labels
+    ///
+    /// The formatter is called with the value of each tick.
+    pub fn with_tick_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(f32) -> String + 'static,
+    {
+        self.ticker.formatter = Some(Formatter(Rc::new(formatter)));
+        self
+    }
+
+    /// Set the length of tick marks (default 20)
+    ///
+    /// Tick labels are offset from the axis line by a distance derived from
+    /// the length.
+    pub fn with_tick_len(mut self, len: f32) -> Self {
+        self.tick_len = len.max(0.0);
+        self
+    }
+
+    /// Set the space reserved for the axis name (default 80)
+    ///
+    /// The rest of the axis space is used for ticks and their labels.
+    pub fn with_name_height(mut self, height: u16) -> Self {
+        self.name_height = height;
+        self
+    }
+
+    /// Set the vertical offset of tick labels, in em (default 0.33)
+    ///
+    /// The offset is relative to the label font size, so the default centers
+    /// labels on their ticks at any size.  Adjust it for fonts with unusual
+    /// proportions.
+    pub fn with_label_dy(mut self, dy: f32) -> Self {
+        self.label_dy = dy;
+        self
+    }
+
+    /// Add a class to the axis line, grid, tick labels and name
+    ///
+    /// Classes can be added more than once, or separated by spaces.
+    pub fn with_class(mut self, class: &str) -> Self {
+        let class = class.split_whitespace();
+        let classes = self.class.iter().flat_map(|c| c.split_whitespace());
+        let classes: Vec<_> = classes.chain(class).collect();
+        self.class = (!classes.is_empty()).then(|| classes.join(" "));
+        self
+    }
+
+    /// Draw tick marks pointing into the plot area
+    ///
+    /// Tick labels stay outside of the plot area.
+    pub fn with_inner_ticks(mut self) -> Self {
+        self.inner_ticks = true;
+        self
+    }
+
+    /// Label only every `n`th tick, starting with the first
+    ///
+    /// By default, labels which would overlap are skipped automatically.
+    /// Tick marks and grid lines are still drawn for unlabeled ticks.
+    pub fn with_label_every(mut self, n: usize) -> Self {
+        self.label_every = Some(n.max(1));
+        self
+    }
+
+    /// Set the estimated width of one tick label character, in pixels
+    ///
+    /// This is used to reserve space for tick labels.  The default is 16.
+    pub fn with_label_char_width(mut self, px: f32) -> Self {
+        self.char_width = px.max(0.0);
+        self
+    }
+
     /// Attach to the right side of a `Chart`
     ///
     /// By default, a `Vertical` axis is attached to the left side of a `Chart`.
@@ -246,10 +1285,28 @@ impl Vertical {
         self
     }
 
+    /// Minimum space reserved for ticks and their labels
+    fn min_tick_space(&self) -> u16 {
+        (Tick::vlen(self.tick_len) + Tick::HEIGHT).ceil() as u16
+    }
+
+    fn ticks(&self, len: Option<f32>) -> Vec<Tick> {
+        self.ticker.ticks(true, len)
+    }
+
     fn space(&self) -> u16 {
+        if let Some(space) = self.space {
+            return space;
+        }
+        // Make room for the widest tick label
+        let ticks = self.ticks(None);
+        let chars = ticks.iter().map(|t| t.text().chars().count());
+        let width = chars.max().unwrap_or(0) as f32 * self.char_width;
+        let ticks = (width + Tick::hlen(self.tick_len)).ceil() as u16;
+        let ticks = ticks.max(self.min_tick_space());
         match self.name {
-            Some(_) => 160,
-            None => 80,
+            Some(_) => self.name_height + ticks,
+            None => ticks,
         }
     }
 
@@ -257,17 +1314,35 @@ impl Vertical {
         &self,
         f: &mut dyn Write,
         rect: BBox<f32>,
+        precision: u8,
     ) -> fmt::Result {
+        let len = if self.inner_ticks {
+            -self.tick_len
+        } else {
+            self.tick_len
+        };
         let (x, width) = match self.edge {
-            Edge::Left => (rect.x_max(), Tick::LEN),
-            Edge::Right => (rect.x_min(), -Tick::LEN),
+            Edge::Left => (rect.x_max(), len.round() as i32),
+            Edge::Right => (rect.x_min(), -len.round() as i32),
             _ => unreachable!(),
         };
-        write!(f, "<path class='axis-line'")?;
-        write!(f, " d='M{} {}v{}", x, rect.y_min(), rect.y_span())?;
-        for tick in self.ticks.iter() {
-            let x = tick.x(self.edge, rect, Tick::LEN as f32) as i32;
-            let y = tick.y(self.edge, rect, Tick::LEN as f32) as i32;
+        if !self.parts.line && !self.parts.ticks {
+            return Ok(());
+        }
+        let class = class_list("axis-line", self.class.as_deref());
+        write!(f, "<path class='{}' d='", Escape(&class))?;
+        if self.parts.line {
+            write!(f, "M{} {}v{}", x, rect.y_min(), rect.y_span())?;
+        }
+        let ticks = if self.parts.ticks {
+            self.ticks(Some(rect.y_span()))
+        } else {
+            vec![]
+        };
+        for tick in ticks.iter() {
+            let x = tick.x(self.edge, rect, len) as i32;
+            let y = tick.y(self.edge, rect, len);
+            let y = Coord::new(y, precision);
             let x0 = x.min(x + width);
             let w = x.max(x + width) - x0;
             write!(f, " M{} {}h{}", x0, y, w)?;
@@ -285,27 +1360,1033 @@ impl Vertical {
             Edge::Right => Anchor::Start,
             _ => unreachable!(),
         };
+        let ticks = self.ticks(Some(rect.y_span()));
+        let labels =
+            thin_labels(&ticks, rect.y_span(), self.label_every, |_| {
+                Tick::HEIGHT
+            });
+        let class = class_list("tick", self.class.as_deref());
         let text = Text::new(Edge::Top)
             .with_anchor(anchor)
-            .with_class_name("tick");
+            .with_class_name(&class);
         text.display(f)?;
-        for tick in &self.ticks {
-            tick.tspan(self.edge, rect).display(f)?;
+        for tick in labels {
+            tick.tspan(self.edge, rect, self.tick_len, self.label_dy)
+                .display(f)?;
         }
         text.display_done(f)
     }
 }
 
-fn intersect_horiz(this: &mut BBox<f32>, rhs: &BBox<f32>) {
-    *this = BBox::new([
-        (this.x_min().max(rhs.x_min()), this.y_min()),
-        (this.x_max().min(rhs.x_max()), this.y_max()),
-    ]);
+/// Get a class list, with extra classes appended
+fn class_list(base: &str, extra: Option<&str>) -> String {
+    match extra {
+        Some(extra) => format!("{base} {extra}"),
+        None => base.to_string(),
+    }
+}
+
+/// Select tick labels to display, skipping any which would overlap
+///
+/// The `span` is the rendered axis length, and `size` estimates the extent of
+/// a label along the axis.  The first and last labels are always kept.  With
+/// `every`, labels are instead kept at a fixed interval.
+fn thin_labels(
+    ticks: &[Tick],
+    span: f32,
+    every: Option<usize>,
+    size: impl Fn(&Tick) -> f32,
+) -> Vec<&Tick> {
+    if let Some(every) = every {
+        return ticks.iter().step_by(every.max(1)).collect();
+    }
+    let overlaps = |a: &Tick, b: &Tick| {
+        (a.value() - b.value()).abs() * span < (size(a) + size(b)) / 2.0
+    };
+    let mut kept: Vec<&Tick> = vec![];
+    for (i, tick) in ticks.iter().enumerate() {
+        if i + 1 == ticks.len() {
+            while kept.len() > 1 && overlaps(kept[kept.len() - 1], tick) {
+                kept.pop();
+            }
+            kept.push(tick);
+        } else if kept.last().is_none_or(|last| !overlaps(last, tick)) {
+            kept.push(tick);
+        }
+    }
+    kept
+}
+
+impl sealed::Axis for ColorBar {
+    fn split(&self, area: &mut BBox<f32>) -> BBox<f32> {
+        let space = f32::from(self.axis.space() + ColorBar::WIDTH);
+        Edge::Right.split(area, space)
+    }
+
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        rect: BBox<f32>,
+        area: BBox<f32>,
+        precision: u8,
+    ) -> fmt::Result {
+        let Some(mut rect) = intersect_vert(rect, area) else {
+            return Ok(());
+        };
+        let strip = Edge::Left.split(&mut rect, f32::from(ColorBar::WIDTH));
+        let x = strip.x_min() + f32::from(ColorBar::GAP);
+        let width = f32::from(ColorBar::WIDTH - ColorBar::GAP);
+        let height = strip.y_span() / ColorBar::STEPS as f32;
+        writeln!(f, "<g class='color-bar'>")?;
+        for i in 0..ColorBar::STEPS {
+            let t = (i as f32 + 0.5) / ColorBar::STEPS as f32;
+            let y = strip.y_max() - (i + 1) as f32 * height;
+            write!(f, "<rect x='{x}' y='{}'", y.floor())?;
+            // Overlap steps slightly to avoid hairline gaps
+            write!(f, " width='{width}' height='{}'", height.ceil() + 1.0)?;
+            write!(f, " fill='")?;
+            self.ramp.display(f, t)?;
+            writeln!(f, "'/>")?;
+        }
+        writeln!(f, "</g>")?;
+        sealed::Axis::display(&self.axis, f, rect, area, precision)
+    }
+
+    fn display_grid(
+        &self,
+        _f: &mut dyn Write,
+        _area: BBox<f32>,
+        _precision: u8,
+    ) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl Axis for ColorBar {}
+
+impl ColorBar {
+    const GAP: u16 = 20;
+    const STEPS: usize = 64;
+    const WIDTH: u16 = 80;
+
+    /// Create a new color bar for a range of values
+    pub fn new(min: f32, max: f32) -> Self {
+        let domain = BBox::new([(0.0, min), (0.0, max)]);
+        Self {
+            ramp: ColorRamp::default(),
+            axis: Vertical::new(domain).on_right(),
+        }
+    }
+
+    /// Set the color ramp
+    pub fn with_ramp(mut self, ramp: ColorRamp) -> Self {
+        self.ramp = ramp;
+        self
+    }
+
+    /// Set the name of the color bar
+    pub fn with_name<N>(mut self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.axis = self.axis.with_name(name);
+        self
+    }
+}
+
+impl Default for Parts {
+    fn default() -> Self {
+        Self {
+            line: true,
+            ticks: true,
+            labels: true,
+        }
+    }
+}
+
+impl fmt::Debug for Formatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Formatter")
+    }
+}
+
+impl PartialEq for Formatter {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Ticker {
+    /// Tick steps outside of which labels use scientific notation
+    const SCI_THRESHOLDS: (f32, f32) = (1e-4, 1e6);
+    /// Fraction of the stop within which a nice start snaps to zero
+    const ZERO_SNAP: f32 = 0.2;
+
+    /// Create a new numeric tick generator
+    fn new(scale: Numeric) -> Self {
+        Self {
+            scale,
+            categories: None,
+            custom: None,
+            formatter: None,
+            count: None,
+            min_spacing: None,
+            ranged: false,
+            nice: false,
+            zero: false,
+            reversed: false,
+            percent: None,
+            si_unit: None,
+            integer: false,
+            unit: None,
+            sci_thresholds: Ticker::SCI_THRESHOLDS,
+            sci_pretty: false,
+            number_format: NumberFormat::default(),
+        }
+    }
+
+    /// Create a new categorical tick generator
+    fn categories<I, C>(categories: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<String>,
+    {
+        let names: Vec<String> =
+            categories.into_iter().map(Into::into).collect();
+        Self {
+            scale: Numeric::exact(-0.5, names.len() as f32 - 0.5),
+            categories: Some(names),
+            custom: None,
+            formatter: None,
+            count: None,
+            min_spacing: None,
+            ranged: false,
+            nice: false,
+            zero: false,
+            reversed: false,
+            percent: None,
+            si_unit: None,
+            integer: false,
+            unit: None,
+            sci_thresholds: Ticker::SCI_THRESHOLDS,
+            sci_pretty: false,
+            number_format: NumberFormat::default(),
+        }
+    }
+
+    /// Get the scale overriding plot domains, for categories or ranges
+    fn scale_override(&self) -> Option<Numeric> {
+        if self.reversed
+            || self.scale.is_custom()
+            || self.categories.is_some()
+            || self.ranged
+            || self.nice
+            || self.zero
+        {
+            Some(self.scale())
+        } else {
+            None
+        }
+    }
+
+    /// Get the scale for mapping plot values
+    fn scale(&self) -> Numeric {
+        if self.reversed {
+            self.base().inverted()
+        } else {
+            self.base()
+        }
+    }
+
+    /// Get the scale, including zero and expanded to tick steps if nice
+    fn base(&self) -> Numeric {
+        if self.categories.is_some() || self.ranged || self.scale.is_custom() {
+            return self.scale.clone();
+        }
+        let scale = match self.zero {
+            true => self.scale.with_zero(),
+            false => self.scale.clone(),
+        };
+        if self.nice {
+            let step = self.step_for(&scale, None);
+            let step = step.unwrap_or(scale.tick_spacing());
+            scale.expanded(step.abs(), Self::ZERO_SNAP)
+        } else {
+            scale
+        }
+    }
+
+    /// Set the scale from an automatic domain, unless it has a fixed range
+    fn set_auto_scale(&mut self, scale: Numeric) {
+        if self.categories.is_none() && !self.ranged && !self.scale.is_custom()
+        {
+            self.scale = scale;
+        }
+    }
+
+    /// Set a fixed range
+    fn set_range(&mut self, min: f32, max: f32) {
+        if self.categories.is_none() {
+            self.scale = Numeric::range(min, max);
+            self.ranged = true;
+        }
+    }
+
+    /// Set a custom scale
+    fn set_scale(&mut self, scale: Rc<dyn Scale>) {
+        if self.categories.is_none() {
+            self.scale = Numeric::custom(scale);
+            self.ranged = false;
+        }
+    }
+
+    /// Get the width of each category band
+    fn band_width(&self) -> Option<f32> {
+        self.categories.as_ref().map(|c| 1.0 / c.len() as f32)
+    }
+
+    /// Get the tick step, if adjusted by count or spacing
+    ///
+    /// The `len` is the rendered axis length, in pixels.
+    fn step(&self, len: Option<f32>) -> Option<f32> {
+        self.step_for(&self.base(), len)
+    }
+
+    /// Get the tick step for a scale, if adjusted by count or spacing
+    fn step_for(&self, scale: &Numeric, len: Option<f32>) -> Option<f32> {
+        if self.count.is_none() && self.min_spacing.is_none() && !self.integer {
+            return None;
+        }
+        let span = scale.span();
+        let mut step = match self.count {
+            Some(count) => Numeric::nice_step(span / count.max(1) as f32),
+            None => scale.tick_spacing().abs(),
+        };
+        if let (Some(px), Some(len)) = (self.min_spacing, len) {
+            if len > 0.0 && step * len / span < px {
+                step = Numeric::nice_step_above(span * px / len);
+            }
+        }
+        if self.integer && step.fract() != 0.0 {
+            step = Numeric::nice_step_above(step.max(1.0));
+        }
+        Some(step)
+    }
+
+    /// Generate ticks, inverted for vertical axes
+    ///
+    /// The `len` is the rendered axis length, in pixels.
+    fn ticks(&self, inverted: bool, len: Option<f32>) -> Vec<Tick> {
+        let scale = if inverted != self.reversed {
+            self.base().inverted()
+        } else {
+            self.base()
+        };
+        if let Some(names) = &self.categories {
+            return names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| Tick::new(scale.normalize(i as f32), name))
+                .collect();
+        }
+        // Generated ticks are displayed with the precision of the step
+        let step = self.step(len).unwrap_or(scale.tick_spacing().abs());
+        let (small, large) = self.sci_thresholds;
+        let notation = match self.custom {
+            Some(_) => None,
+            None if step < small || step > large => {
+                Some(Notation::Scientific(step))
+            }
+            None => Some(Notation::Fixed(Numeric::precision(step))),
+        };
+        let values = match &self.custom {
+            Some(custom) => custom.clone(),
+            None => {
+                let values = match self.step(len) {
+                    // No whole numbers to step between; use nearest one
+                    Some(_) if self.integer && scale.span() < 1.0 => {
+                        vec![scale.center().round()]
+                    }
+                    Some(step) => scale.tick_values_step(step),
+                    None => scale.tick_values(),
+                };
+                values.into_iter().map(|v| (v, None)).collect()
+            }
+        };
+        let values: Vec<_> = values
+            .into_iter()
+            .filter(|(value, _)| scale.contains(*value))
+            .collect();
+        // All SI labels share the prefix of the largest value
+        let largest = values.iter().fold(0.0, |m: f32, (v, _)| m.max(v.abs()));
+        let top = values.iter().fold(f32::MIN, |m, (v, _)| m.max(*v));
+        values
+            .into_iter()
+            .map(|(value, label)| {
+                let text = match (label, &self.unit) {
+                    (Some(label), _) => label,
+                    (None, Some((unit, last))) if !last || value == top => {
+                        let label = self.label(value, largest, notation);
+                        // Separate unit with a thin space
+                        format!("{label}\u{2009}{unit}")
+                    }
+                    (None, _) => self.label(value, largest, notation),
+                };
+                Tick::new(scale.normalize(value), text)
+            })
+            .collect()
+    }
+
+    /// Get the label text for a tick value
+    ///
+    /// The `notation` is only used for generated ticks.
+    fn label(
+        &self,
+        value: f32,
+        largest: f32,
+        notation: Option<Notation>,
+    ) -> String {
+        if let Some(Formatter(formatter)) = &self.formatter {
+            return formatter(value);
+        }
+        let text = if let Some(unit) = &self.si_unit {
+            si_label(value, largest, unit)
+        } else if let Some(places) = self.percent {
+            format!("{:.*}%", places, value * 100.0)
+        } else if let Some(Notation::Fixed(places)) = notation {
+            format!("{:.*}", places, value)
+        } else if let Some(Notation::Scientific(step)) = notation {
+            sci_label(value, step, self.sci_pretty)
+        } else {
+            format!("{}", value)
+        };
+        self.number_format.localize(&text)
+    }
+}
+
+/// Format a value in scientific notation, with the precision of a step
+///
+/// Pretty labels use a `×10` with a superscript exponent, such as `2×10⁻⁷`.
+fn sci_label(value: f32, step: f32, pretty: bool) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    let mut exp = value.abs().log10().floor() as i32;
+    let places = Numeric::precision(step / 10_f32.powi(exp));
+    let mut mantissa = format!("{:.*}", places, value / 10_f32.powi(exp));
+    // Rounding can carry into the next power of ten
+    if mantissa.trim_start_matches('-').starts_with("10") {
+        exp += 1;
+        mantissa = format!("{:.*}", places, value / 10_f32.powi(exp));
+    }
+    if !pretty {
+        return format!("{mantissa}e{exp}");
+    }
+    let sup: String = exp
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            c => ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹']
+                [c.to_digit(10).unwrap_or(0) as usize],
+        })
+        .collect();
+    format!("{mantissa}×10{sup}")
+}
+
+/// Format a value with an SI prefix, chosen from the largest value
+fn si_label(value: f32, largest: f32, unit: &str) -> String {
+    const PREFIXES: [&str; 8] = ["n", "µ", "m", "", "k", "M", "G", "T"];
+    let sep = if unit.is_empty() { "" } else { " " };
+    if value == 0.0 {
+        return format!("0{sep}{unit}");
+    }
+    let power = if largest > 0.0 {
+        (largest.log10() / 3.0).floor().clamp(-3.0, 4.0) as i32
+    } else {
+        0
+    };
+    let scaled = value / 1000_f32.powi(power);
+    // Round away floating-point noise
+    let scaled = (scaled * 1000.0).round() / 1000.0;
+    let prefix = PREFIXES[(power + 3) as usize];
+    if unit.is_empty() {
+        format!("{scaled}{prefix}")
+    } else {
+        format!("{scaled} {prefix}{unit}")
+    }
 }
 
-fn intersect_vert(this: &mut BBox<f32>, rhs: &BBox<f32>) {
-    *this = BBox::new([
-        (this.x_min(), this.y_min().max(rhs.y_min())),
-        (this.x_max(), this.y_max().min(rhs.y_max())),
-    ]);
+/// Limit a rect to the horizontal span of another, or `None` if empty
+fn intersect_horiz(this: BBox<f32>, rhs: BBox<f32>) -> Option<BBox<f32>> {
+    let x_min = this.x_min().max(rhs.x_min());
+    let x_max = this.x_max().min(rhs.x_max());
+    (x_min < x_max)
+        .then(|| BBox::new([(x_min, this.y_min()), (x_max, this.y_max())]))
+}
+
+/// Limit a rect to the vertical span of another, or `None` if empty
+fn intersect_vert(this: BBox<f32>, rhs: BBox<f32>) -> Option<BBox<f32>> {
+    let y_min = this.y_min().max(rhs.y_min());
+    let y_max = this.y_max().min(rhs.y_max());
+    (y_min < y_max)
+        .then(|| BBox::new([(this.x_min(), y_min), (this.x_max(), y_max)]))
+}
+
+/// Get sorted band edges from tick positions, limited to a range
+fn band_edges(
+    pos: impl Iterator<Item = f32>,
+    (min, max): (f32, f32),
+    precision: u8,
+) -> Vec<Coord> {
+    let mut pos: Vec<f32> = pos
+        .filter(|p| p.is_finite())
+        .map(|p| Coord::new(p.clamp(min, max), precision).value())
+        .collect();
+    pos.sort_by(f32::total_cmp);
+    pos.dedup();
+    pos.into_iter().map(|p| Coord::new(p, precision)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sealed::Axis as _, *};
+    use crate::plot::{Mapping, Scales};
+
+    #[test]
+    fn categories() {
+        let axis = Horizontal::categories(["A", "B&C", "D", "E"]);
+        assert_eq!(axis.band_width(), Some(0.25));
+        let rect = BBox::new([(0.0, 0.0), (400.0, 100.0)]);
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert!(out.contains("<tspan x='50' y='40' dy='0.33em'>A</tspan>"));
+        assert!(out.contains(">B&amp;C</tspan>"));
+        assert!(out.contains("<tspan x='350' "));
+    }
+
+    #[test]
+    fn categories_skip_overlap() {
+        let axis = Horizontal::categories(["Monday", "Tuesday", "Wednesday"]);
+        let rect = BBox::new([(0.0, 0.0), (300.0, 100.0)]);
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert!(out.contains(">Monday</tspan>"));
+        assert!(!out.contains(">Tuesday</tspan>"));
+        assert!(out.contains(">Wednesday</tspan>"));
+    }
+
+    #[test]
+    fn label_thinning() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let rect = BBox::new([(0.0, 0.0), (210.0, 200.0)]);
+        let labels = |out: &str| {
+            let spans =
+                out.split("</tspan>").filter_map(|t| t.split_once("<tspan"));
+            spans
+                .map(|(_, t)| t.rsplit_once('>').unwrap().1.to_string())
+                .collect::<Vec<_>>()
+        };
+        let axis = Horizontal::new(domain);
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert_eq!(labels(&out), ["0", "20", "40", "60", "80", "100"]);
+        let mut out = String::new();
+        axis.display_tick_lines(&mut out, rect, 0).unwrap();
+        assert_eq!(out.matches('v').count(), 11);
+        let axis = Horizontal::new(domain).with_label_every(5);
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert_eq!(labels(&out), ["0", "50", "100"]);
+        let axis = Vertical::new(domain);
+        let rect = BBox::new([(0.0, 0.0), (200.0, 100.0)]);
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert_eq!(labels(&out), ["100", "60", "0"]);
+    }
+
+    #[test]
+    fn sci_labels() {
+        let text = |axis: &Horizontal| {
+            let ticks = axis.ticks(None);
+            ticks
+                .iter()
+                .map(|t| t.text().to_string())
+                .collect::<Vec<_>>()
+        };
+        let domain = BBox::new([(1e-7, 0.0), (3e-7, 1.0)]);
+        let axis = Horizontal::new(domain);
+        assert_eq!(text(&axis)[..3], ["1.0e-7", "1.5e-7", "2.0e-7"]);
+        let domain = BBox::new([(0.0, 0.0), (4e7, 1.0)]);
+        let axis = Horizontal::new(domain).with_tick_count(4).with_pretty_sci();
+        assert_eq!(text(&axis), ["0", "1×10⁷", "2×10⁷", "3×10⁷", "4×10⁷"]);
+        let domain = BBox::new([(-100.0, 0.0), (100.0, 1.0)]);
+        let axis = Horizontal::new(domain).with_tick_count(2);
+        assert_eq!(text(&axis), ["-100", "0", "100"]);
+        let axis = axis.with_sci_thresholds(1e-4, 10.0);
+        assert_eq!(text(&axis), ["-1e2", "0", "1e2"]);
+        assert_eq!(sci_label(9.96e-5, 1e-6, false), "1.0e-4");
+    }
+
+    #[test]
+    fn number_format() {
+        let domain = BBox::new([(0.0, -2500.0), (5000.0, 2500.0)]);
+        let axis =
+            Horizontal::new(domain).with_number_format(NumberFormat::en());
+        assert_eq!(axis.ticks(None).last().unwrap().text(), "5,000");
+        let axis = Vertical::new(domain)
+            .with_tick_count(2)
+            .with_number_format(NumberFormat::de().with_minus('\u{2212}'));
+        let ticks = axis.ticks(None);
+        let text: Vec<_> = ticks.iter().map(|t| t.text()).collect();
+        assert_eq!(text, ["\u{2212}2.000", "0", "2.000"]);
+        let domain = BBox::new([(0.0, 0.0), (0.5, 0.5)]);
+        let axis = Horizontal::new(domain)
+            .with_percent_decimals(1)
+            .with_number_format(NumberFormat::de());
+        assert_eq!(axis.ticks(None)[1].text(), "10,0%");
+    }
+
+    #[test]
+    fn inner_ticks() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let rect = BBox::new([(0.0, 400.0), (400.0, 480.0)]);
+        let lines = |axis: Horizontal| {
+            let mut out = String::new();
+            axis.with_ticks(&[50.0])
+                .display_tick_lines(&mut out, rect, 0)?;
+            Ok::<_, fmt::Error>(out)
+        };
+        let out = lines(Horizontal::new(domain)).unwrap();
+        assert!(out.ends_with("M200 400v20'/>\n"));
+        let out = lines(Horizontal::new(domain).with_inner_ticks()).unwrap();
+        assert!(out.ends_with("M200 380v20'/>\n"));
+        let rect = BBox::new([(0.0, 0.0), (80.0, 400.0)]);
+        let axis = Vertical::new(domain).with_ticks(&[50.0]).with_inner_ticks();
+        let mut out = String::new();
+        axis.display_tick_lines(&mut out, rect, 0).unwrap();
+        assert!(out.ends_with(" M80 200h20'/>\n"));
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert!(out.contains("<tspan x='52' y='200'"));
+    }
+
+    #[test]
+    fn tick_len() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        assert_eq!(Horizontal::new(domain).space(), 80);
+        let axis = Horizontal::new(domain).with_tick_len(10.0);
+        assert_eq!(axis.space(), 60);
+        let rect = BBox::new([(0.0, 400.0), (400.0, 460.0)]);
+        let axis = axis.with_ticks(&[50.0]);
+        let mut out = String::new();
+        axis.display_tick_lines(&mut out, rect, 0).unwrap();
+        assert!(out.ends_with("M200 400v10'/>\n"));
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert!(out.contains("<tspan x='200' y='420'"));
+        let axis =
+            Vertical::new(domain).with_labeled_ticks([(50.0, "a long label")]);
+        let space = axis.space();
+        assert_eq!(axis.with_tick_len(40.0).space(), space + 20);
+    }
+
+    #[test]
+    fn classes() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let axis = Vertical::new(domain)
+            .with_name("Pressure")
+            .with_class("pressure-axis")
+            .with_class(" right  a'b ");
+        let rect = BBox::new([(0.0, 0.0), (200.0, 400.0)]);
+        let mut out = String::new();
+        axis.display(&mut out, rect, rect, 0).unwrap();
+        axis.display_grid(&mut out, rect, 0).unwrap();
+        let class = "pressure-axis right a&apos;b";
+        assert!(out.contains(&format!("<text class='axis {class}'")));
+        assert!(out.contains(&format!("<path class='axis-line {class}'")));
+        assert!(out.contains(&format!("<text class='tick {class}'")));
+        assert!(out.contains(&format!("<path class='grid-y {class}'")));
+        let axis = Horizontal::new(domain).with_label_rotation(45.0);
+        let mut out = String::new();
+        let axis = axis.with_class("x");
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert!(out.starts_with("<text class='tick x' transform="));
+    }
+
+    #[test]
+    fn name_layout() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let label_y = |axis: Horizontal, y: f32| {
+            let rect =
+                BBox::new([(0.0, y), (400.0, y + f32::from(axis.space()))]);
+            let mut out = String::new();
+            axis.with_ticks(&[50.0]).display(&mut out, rect, rect, 0)?;
+            let (_, tspan) = out.split_once("<tspan x='200' y='").unwrap();
+            Ok::<_, fmt::Error>(tspan.split_once('\'').unwrap().0.to_string())
+        };
+        let axis = || Horizontal::new(domain);
+        assert_eq!(label_y(axis(), 400.0).unwrap(), "440");
+        let named = axis().with_name("X");
+        assert_eq!(named.space(), 160);
+        assert_eq!(label_y(named, 400.0).unwrap(), "440");
+        let named = axis().with_name("X").with_name_height(45);
+        assert_eq!(named.space(), 125);
+        assert_eq!(label_y(named, 400.0).unwrap(), "440");
+        let top = axis().with_name("X").on_top();
+        assert_eq!(label_y(top, 0.0).unwrap(), "120");
+        let top = axis().with_name("X").with_name_height(45).on_top();
+        assert_eq!(label_y(top, 0.0).unwrap(), "85");
+    }
+
+    #[test]
+    fn label_dy() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let rect = BBox::new([(0.0, 0.0), (160.0, 400.0)]);
+        let area = BBox::new([(160.0, 0.0), (560.0, 400.0)]);
+        let labels = |axis: &Vertical| {
+            let mut out = String::new();
+            axis.display(&mut out, rect, area, 0).unwrap();
+            out.split("<tspan ")
+                .skip(1)
+                .map(|t| {
+                    let (_, t) = t.split_once("y='").unwrap();
+                    let (y, t) = t.split_once('\'').unwrap();
+                    let (_, t) = t.split_once("dy='").unwrap();
+                    (y.to_string(), t.split_once('\'').unwrap().0.to_string())
+                })
+                .collect::<Vec<_>>()
+        };
+        let axis = Vertical::new(domain).with_ticks(&[0.0, 25.0, 50.0, 100.0]);
+        let mut grid = String::new();
+        axis.display_grid(&mut grid, area, 0).unwrap();
+        let grid: Vec<_> = grid
+            .split('M')
+            .skip(1)
+            .map(|m| m.split(' ').nth(1).unwrap().split('h').next().unwrap())
+            .collect();
+        assert_eq!(grid, ["400", "300", "200", "0"]);
+        let spans = labels(&axis);
+        assert_eq!(spans.len(), grid.len());
+        for ((y, dy), grid_y) in spans.iter().zip(&grid) {
+            assert_eq!(y, grid_y);
+            assert_eq!(dy, "0.33em");
+        }
+        let axis = axis.with_label_dy(0.4);
+        for ((y, dy), grid_y) in labels(&axis).iter().zip(&grid) {
+            assert_eq!(y, grid_y);
+            assert_eq!(dy, "0.4em");
+        }
+    }
+
+    #[test]
+    fn intersect() {
+        let rect = BBox::new([(0.0, 400.0), (400.0, 480.0)]);
+        let area = BBox::new([(100.0, 0.0), (500.0, 400.0)]);
+        let r = intersect_horiz(rect, area).unwrap();
+        assert_eq!((r.x_min(), r.x_max(), r.y_min()), (100.0, 400.0, 400.0));
+        let area = BBox::new([(500.0, 0.0), (900.0, 400.0)]);
+        assert_eq!(intersect_horiz(rect, area), None);
+        assert_eq!(intersect_vert(area, rect), None);
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let mut out = String::new();
+        let axis = Horizontal::new(domain).with_name("X");
+        axis.display(&mut out, rect, area, 0).unwrap();
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn tick_formatter() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let axis = Vertical::new(domain)
+            .with_tick_formatter(|v| format!("<{v:.1}> units"));
+        let rect = BBox::new([(0.0, 0.0), (400.0, 400.0)]);
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert!(out.contains(">&lt;100.0&gt; units</tspan>"));
+        assert!(axis.space() > Vertical::new(domain).space());
+    }
+
+    #[test]
+    fn explicit_ticks() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let axis = Horizontal::new(domain).with_ticks(&[0.0, 25.0, 150.0]);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 400.0)]);
+        let mut out = String::new();
+        axis.display_grid(&mut out, rect, 0).unwrap();
+        assert_eq!(out, "<path class='grid-x' d='M0 0v400M100 0v400'/>\n");
+        let axis = Vertical::new(domain).with_labeled_ticks([(50.0, "half")]);
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert!(out.contains(">half</tspan>"));
+        assert!(!out.contains(">0</tspan>"));
+    }
+
+    #[test]
+    fn tick_density() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let axis = Horizontal::new(domain).with_tick_count(4);
+        assert_eq!(axis.ticks(None).len(), 6);
+        let axis = Horizontal::new(domain).with_min_tick_spacing(100.0);
+        assert_eq!(axis.ticks(Some(200.0)).len(), 3);
+        assert_eq!(axis.ticks(Some(1000.0)).len(), 11);
+    }
+
+    #[test]
+    fn label_rotation() {
+        let axis = Horizontal::categories(["January", "February"])
+            .with_label_rotation(45.0);
+        assert!(axis.space() > 80);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 100.0)]);
+        let mut out = String::new();
+        axis.display_tick_labels(&mut out, rect).unwrap();
+        assert!(out.contains("transform='rotate(-45 100 28)'"));
+        assert!(out.contains("text-anchor='end'"));
+    }
+
+    #[test]
+    fn range() {
+        let domain = BBox::new([(3.0, 3.0), (47.0, 47.0)]);
+        let axis = Vertical::new(domain).with_range(0.0, 100.0);
+        assert_eq!(axis.y_scale(), Some(Numeric::range(0.0, 100.0)));
+        let ticks = axis.ticks(None);
+        assert_eq!(ticks.len(), 11);
+        assert_eq!(ticks[0].text(), "100");
+        assert_eq!(Horizontal::new(domain).x_scale(), None);
+    }
+
+    #[test]
+    fn nice() {
+        let domain = BBox::new([(23.0, 23.0), (96.7, 96.7)]);
+        let axis = Vertical::new(domain).nice();
+        let scale = axis.y_scale().unwrap();
+        assert_eq!((scale.normalize(0.0), scale.normalize(100.0)), (0.0, 1.0));
+        let ticks = axis.ticks(None);
+        assert_eq!(ticks.len(), 11);
+        assert_eq!(ticks[10].text(), "0");
+        assert_eq!(Vertical::new(domain).ticks(None).len(), 9);
+        let axis = Horizontal::new(domain).with_tick_count(2).nice();
+        let scale = axis.x_scale().unwrap();
+        assert_eq!(scale.normalize(50.0), 0.5);
+        let axis = Horizontal::new(domain).with_range(10.0, 90.0).nice();
+        assert_eq!(axis.x_scale(), Some(Numeric::range(10.0, 90.0)));
+    }
+
+    #[test]
+    fn include_zero() {
+        let domain = BBox::new([(40.0, 40.0), (96.0, 96.0)]);
+        assert_eq!(Vertical::new(domain).y_scale(), None);
+        let axis = Vertical::new(domain).include_zero();
+        let scale = axis.y_scale().unwrap();
+        assert_eq!((scale.normalize(0.0), scale.normalize(100.0)), (0.0, 1.0));
+        assert_eq!(axis.ticks(None)[10].text(), "0");
+        let axis = Horizontal::new(domain).include_zero().with_tick_count(4);
+        let scale = axis.nice().x_scale().unwrap();
+        assert_eq!(scale.normalize(100.0), 1.0);
+        let axis = Vertical::new(domain).with_range(50.0, 100.0);
+        let axis = axis.include_zero().nice();
+        assert_eq!(axis.y_scale(), Some(Numeric::range(50.0, 100.0)));
+    }
+
+    #[test]
+    fn reversed() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 400.0)]);
+        let scales = |x: &Horizontal, y: &Vertical| Scales {
+            x: x.x_scale(),
+            y: y.y_scale(),
+            ..Scales::default()
+        };
+        let (x, y) = (Horizontal::new(domain), Vertical::new(domain));
+        let map = Mapping::new(&domain, rect, &scales(&x, &y));
+        assert_eq!((map.x_map(0.0), map.y_map(0.0)), (0, 400));
+        let (x, y) = (x.reversed(), y.reversed());
+        let map = Mapping::new(&domain, rect, &scales(&x, &y));
+        assert_eq!((map.x_map(0.0), map.y_map(0.0)), (400, 0));
+        let mut out = String::new();
+        y.display_grid(&mut out, rect, 0).unwrap();
+        assert!(out.starts_with("<path class='grid-y' d='M0 0h400"));
+    }
+
+    /// Square root scale, for testing custom scales
+    struct Sqrt(f32);
+
+    impl Scale for Sqrt {
+        fn normalize(&self, value: f32) -> f32 {
+            value.sqrt() / self.0.sqrt()
+        }
+
+        fn ticks(&self) -> Vec<f32> {
+            vec![0.0, 25.0, 100.0]
+        }
+    }
+
+    #[test]
+    fn custom_scale() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 400.0)]);
+        let x = Horizontal::new(domain).with_scale(Sqrt(100.0)).with_id("x");
+        let y = Vertical::new(domain).with_scale(Sqrt(100.0)).with_id("y");
+        let ticks = x.ticks(None);
+        let pos: Vec<_> = ticks.iter().map(|t| (t.value(), t.text())).collect();
+        assert_eq!(pos, [(0.0, "0"), (0.5, "25"), (1.0, "100")]);
+        let ticks = y.ticks(None);
+        let pos: Vec<_> = ticks.iter().map(|t| (t.value(), t.text())).collect();
+        assert_eq!(pos, [(0.0, "100"), (0.5, "25"), (1.0, "0")]);
+        // Bound plots are mapped through the scale
+        let scales = Scales {
+            x: x.bound_x_scale(),
+            y: y.bound_y_scale(),
+            ..Scales::default()
+        };
+        let map = Mapping::new(&domain, rect, &scales);
+        assert_eq!((map.x_map(25.0), map.y_map(25.0)), (200, 200));
+        assert_eq!((map.x_map(100.0), map.y_map(100.0)), (400, 0));
+        assert!(!map.x_contains(120.0));
+        let x = x.reversed().with_tick_count(10);
+        let map = Mapping::new(
+            &domain,
+            rect,
+            &Scales {
+                x: x.x_scale(),
+                ..scales
+            },
+        );
+        assert_eq!(map.x_map(25.0), 200);
+        assert_eq!(map.x_map(0.0), 400);
+        assert_eq!(x.ticks(None).len(), 3);
+        let chart = crate::Chart::default()
+            .with_axis(Horizontal::new(domain).with_scale(Sqrt(100.0)));
+        let pt = chart.map_point((25.0, 0.0).into(), &domain);
+        let area = chart.plot_area();
+        assert_eq!(pt.x(), area.x_min() + area.x_span() / 2.0);
+    }
+
+    #[test]
+    fn percent_labels() {
+        let domain = BBox::new([(0.0, 0.0), (1.0, 1.0)]);
+        let axis = Horizontal::new(domain)
+            .with_tick_count(4)
+            .with_percent_labels();
+        let ticks = axis.ticks(None);
+        let text: Vec<_> = ticks.iter().map(|t| t.text()).collect();
+        assert_eq!(text, ["0%", "20%", "40%", "60%", "80%", "100%"]);
+        let axis = Vertical::new(domain).with_percent_decimals(1);
+        assert_eq!(axis.ticks(None)[0].text(), "100.0%");
+        let axis = axis.with_tick_formatter(|v| format!("{v}"));
+        assert_eq!(axis.ticks(None)[0].text(), "1");
+    }
+
+    #[test]
+    fn si_labels() {
+        assert_eq!(si_label(2_500_000.0, 3_000_000.0, ""), "2.5M");
+        assert_eq!(si_label(300_000.0, 3_000_000.0, ""), "0.3M");
+        assert_eq!(si_label(300_000.0, 500_000.0, ""), "300k");
+        assert_eq!(si_label(0.0, 500_000.0, "B"), "0 B");
+        assert_eq!(si_label(0.002, 0.004, "s"), "2 ms");
+        assert_eq!(si_label(0.000_5, 0.000_5, ""), "500µ");
+        let domain = BBox::new([(0.0, 0.0), (2.5e6, 2.5e6)]);
+        let axis = Horizontal::new(domain).with_si_unit("B");
+        let ticks = axis.ticks(None);
+        assert_eq!(ticks[1].text(), "0.5 MB");
+    }
+
+    #[test]
+    fn space() {
+        let domain = BBox::new([(0.0, 0.0), (1.0, 1.0)]);
+        let axis =
+            Vertical::new(domain).with_labeled_ticks([(0.5, "1,250,000")]);
+        let width = Tick::hlen(Tick::LEN) + 9.0 * Tick::CHAR_WIDTH;
+        assert!(f32::from(axis.space()) >= width);
+        // Labels are end-anchored, so they must fit between the edge and plot
+        let mut area = BBox::new([(0.0, 0.0), (1000.0, 1000.0)]);
+        let rect = axis.split(&mut area);
+        assert!(rect.x_max() - width >= 0.0);
+        assert_eq!(area.x_min(), rect.x_max());
+        let axis = Vertical::new(domain).with_name("Y").with_space(300);
+        assert_eq!(axis.space(), 300);
+        assert_eq!(Horizontal::new(domain).with_space(50).space(), 50);
+    }
+
+    #[test]
+    fn hidden_parts() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 400.0)]);
+        let axis = Horizontal::new(domain).with_ticks(&[50.0]).hide_line();
+        let mut out = String::new();
+        axis.display(&mut out, rect, rect, 0).unwrap();
+        assert!(out.contains("<path class='axis-line' d='M200 0v20'/>"));
+        assert!(out.contains(">50</tspan>"));
+        let axis = Vertical::new(domain).hide_line().hide_ticks();
+        let mut out = String::new();
+        axis.display(&mut out, rect, rect, 0).unwrap();
+        assert!(!out.contains("axis-line"));
+        assert!(out.contains("</tspan>"));
+        let axis = Vertical::new(domain).hide_labels();
+        let mut out = String::new();
+        axis.display(&mut out, rect, rect, 0).unwrap();
+        assert!(out.contains("axis-line"));
+        assert!(!out.contains("</tspan>"));
+    }
+
+    #[test]
+    fn grid() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let rect = BBox::new([(0.0, 0.0), (400.0, 400.0)]);
+        let mut out = String::new();
+        let axis = Vertical::new(domain).with_grid(false);
+        axis.display_grid(&mut out, rect, 0).unwrap();
+        let axis = Horizontal::new(domain).with_ticks(&[]);
+        axis.display_grid(&mut out, rect, 0).unwrap();
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn integer_ticks() {
+        let domain = BBox::new([(0.0, 0.0), (3.0, 3.0)]);
+        let axis = Horizontal::new(domain).with_integer_ticks();
+        let ticks = axis.ticks(None);
+        let text: Vec<_> = ticks.iter().map(|t| t.text()).collect();
+        assert_eq!(text, ["0", "1", "2", "3"]);
+        let domain = BBox::new([(1.5, 1.5), (2.25, 2.25)]);
+        let axis = Vertical::new(domain).with_integer_ticks();
+        let ticks = axis.ticks(None);
+        assert_eq!(ticks.len(), 1);
+        assert_eq!(ticks[0].text(), "2");
+    }
+
+    #[test]
+    fn unit() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let axis = Vertical::new(domain).with_unit("ms");
+        assert_eq!(axis.ticks(None)[0].text(), "10\u{2009}ms");
+        assert!(axis.space() > Vertical::new(domain).space());
+        let axis = Horizontal::new(domain).with_unit_on_last_tick("ms");
+        let ticks = axis.ticks(None);
+        assert_eq!(ticks[0].text(), "0");
+        assert_eq!(ticks[ticks.len() - 1].text(), "10\u{2009}ms");
+    }
+
+    #[test]
+    fn tick_precision() {
+        let labels = |max: f32| {
+            let domain = BBox::new([(0.0, 0.0), (max, max)]);
+            let ticks = Horizontal::new(domain).ticks(None);
+            ticks
+                .iter()
+                .map(|t| t.text().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            labels(0.7),
+            ["0.0", "0.1", "0.2", "0.3", "0.4", "0.5", "0.6", "0.7"]
+        );
+        assert_eq!(
+            labels(1.5),
+            ["0.00", "0.25", "0.50", "0.75", "1.00", "1.25", "1.50"]
+        );
+        let labels = labels(5e-6);
+        assert_eq!(labels[3], "3e-6");
+        assert!(labels[1..].iter().all(|l| l.len() == 4));
+    }
 }
@@ -0,0 +1,186 @@
+// band.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Band plots
+
+use std::{fmt, fmt::Write};
+
+use pointy::{BBox, Pt};
+
+use crate::plot::{Mapping, Scales, Series};
+
+/// Band plot, filling the area between two series
+///
+/// The band is drawn as a single closed path, forward along the upper series
+/// and backward along the lower series.
+pub struct Band<'a> {
+    name: &'a str,
+    domain: &'a BBox<f32>,
+    lower: Vec<Pt<f32>>,
+    upper: Vec<Pt<f32>>,
+}
+
+impl<'a> Band<'a> {
+    /// Create a new band plot from `(x, y_low, y_high)` tuples
+    pub fn new<I>(name: &'a str, domain: &'a BBox<f32>, data: I) -> Self
+    where
+        I: IntoIterator<Item = (f32, f32, f32)>,
+    {
+        let (lower, upper) = data
+            .into_iter()
+            .map(|(x, low, high)| (Pt::new(x, low), Pt::new(x, high)))
+            .unzip();
+        Self {
+            name,
+            domain,
+            lower,
+            upper,
+        }
+    }
+
+    /// Create a new band plot from separate lower and upper series
+    ///
+    /// If the series cover different `X` ranges, they are clamped to the
+    /// overlapping range, with points interpolated at its edges.  The series
+    /// should be sorted by `X`.
+    pub fn from_bounds<L, U, P>(
+        name: &'a str,
+        domain: &'a BBox<f32>,
+        lower: L,
+        upper: U,
+    ) -> Self
+    where
+        L: IntoIterator<Item = P>,
+        U: IntoIterator<Item = P>,
+        P: Into<Pt<f32>>,
+    {
+        let mut lower: Vec<Pt<f32>> =
+            lower.into_iter().map(Into::into).collect();
+        let mut upper: Vec<Pt<f32>> =
+            upper.into_iter().map(Into::into).collect();
+        if let (Some(lo), Some(hi)) = (x_range(&lower), x_range(&upper)) {
+            let min = lo.0.max(hi.0);
+            let max = lo.1.min(hi.1);
+            lower = clamp_x(&lower, min, max);
+            upper = clamp_x(&upper, min, max);
+        }
+        Self {
+            name,
+            domain,
+            lower,
+            upper,
+        }
+    }
+}
+
+impl Series for Band<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn domain(&self) -> Option<&BBox<f32>> {
+        Some(self.domain)
+    }
+
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        rect: BBox<f32>,
+        scales: &Scales,
+        _stack: &mut Vec<f32>,
+    ) -> fmt::Result {
+        let map = Mapping::new(self.domain, rect, scales);
+        write!(f, "<path class='plot-{num} plot-area plot-band'")?;
+        display_band(f, &map, &self.lower, &self.upper)?;
+        writeln!(f, "/>")
+    }
+}
+
+/// Display the `d` attribute of a band path
+pub(crate) fn display_band(
+    f: &mut dyn Write,
+    map: &Mapping,
+    lower: &[Pt<f32>],
+    upper: &[Pt<f32>],
+) -> fmt::Result {
+    write!(f, " d='")?;
+    let pts = upper.iter().chain(lower.iter().rev());
+    for (i, pt) in pts.enumerate() {
+        let x = map.x_map(pt.x());
+        let y = map.y_map(pt.y());
+
+        if i == 0 {
+            write!(f, "M{x} {y}")?;
+        } else {
+            write!(f, " {x} {y}")?;
+        }
+    }
+    if !upper.is_empty() || !lower.is_empty() {
+        write!(f, "z")?;
+    }
+    write!(f, "'")
+}
+
+/// Clamp a series to a range of `X` values, interpolating at the edges
+fn clamp_x(pts: &[Pt<f32>], min: f32, max: f32) -> Vec<Pt<f32>> {
+    if min > max {
+        return vec![];
+    }
+    let mut clamped = vec![];
+    clamped.extend(interpolate(pts, min));
+    clamped.extend(pts.iter().filter(|pt| pt.x() > min && pt.x() < max));
+    if max > min {
+        clamped.extend(interpolate(pts, max));
+    }
+    clamped
+}
+
+/// Interpolate a series at an `X` value within its range
+fn interpolate(pts: &[Pt<f32>], x: f32) -> Option<Pt<f32>> {
+    if let Some(pt) = pts.iter().find(|pt| pt.x() == x) {
+        return Some(*pt);
+    }
+    pts.windows(2).find_map(|w| {
+        let (a, b) = (w[0], w[1]);
+        let (lo, hi) = (a.x().min(b.x()), a.x().max(b.x()));
+        (lo < x && x < hi).then(|| {
+            let t = (x - a.x()) / (b.x() - a.x());
+            Pt::new(x, a.y() + (b.y() - a.y()) * t)
+        })
+    })
+}
+
+/// Get the range of `X` values in a series
+fn x_range(pts: &[Pt<f32>]) -> Option<(f32, f32)> {
+    let min = pts.iter().map(|pt| pt.x()).min_by(f32::total_cmp)?;
+    let max = pts.iter().map(|pt| pt.x()).max_by(f32::total_cmp)?;
+    Some((min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlap() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let lower = [(0.0, 1.0), (2.0, 2.0), (4.0, 1.0), (6.0, 2.0)];
+        let upper = [(2.0, 5.0), (4.0, 6.0), (6.0, 5.0), (8.0, 6.0)];
+        let band = Band::from_bounds("Band", &domain, lower, upper);
+        assert_eq!(band.lower.len(), 3);
+        assert_eq!(band.upper.len(), 3);
+        assert_eq!(band.lower[0].x(), 2.0);
+        assert_eq!(band.upper[2].x(), 6.0);
+        // Different sampling is interpolated at the overlap edges
+        let lower = [(0.0, 1.0), (4.0, 3.0), (10.0, 0.0)];
+        let upper = [(1.0, 5.0), (5.0, 9.0), (9.0, 5.0)];
+        let band = Band::from_bounds("Band", &domain, lower, upper);
+        let pts = |p: &[Pt<f32>]| -> Vec<(f32, f32)> {
+            p.iter().map(|pt| (pt.x(), pt.y())).collect()
+        };
+        assert_eq!(pts(&band.lower), [(1.0, 1.5), (4.0, 3.0), (9.0, 0.5)]);
+        assert_eq!(pts(&band.upper), [(1.0, 5.0), (5.0, 9.0), (9.0, 5.0)]);
+    }
+}
@@ -0,0 +1,153 @@
+// bar.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Bar groups
+
+use crate::plot::{BarSlot, Plot, PlotKind};
+
+/// Layout of bars from several series at the same `X` value
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BarLayout {
+    /// Bars stacked on top of each other, with cumulative heights
+    Stacked,
+    /// Bars side-by-side, sharing the slot
+    #[default]
+    Grouped,
+}
+
+/// Group of bar plots sharing `X` values
+///
+/// Points are matched by position, so all plots in the group should have
+/// the same `X` values.
+pub struct BarGroup<'a> {
+    layout: BarLayout,
+    plots: Vec<Plot<'a>>,
+    group_gap: f32,
+    bar_gap: f32,
+}
+
+impl<'a> BarGroup<'a> {
+    /// Create a new bar group
+    pub fn new(layout: BarLayout) -> Self {
+        Self {
+            layout,
+            plots: vec![],
+            group_gap: 0.2,
+            bar_gap: 0.1,
+        }
+    }
+
+    /// Add a plot to the group
+    ///
+    /// Grouped bars are placed from left to right, and stacked bars from
+    /// bottom to top, in the order they are added.
+    pub fn with_plot(mut self, plot: Plot<'a>) -> Self {
+        self.plots.push(plot);
+        self
+    }
+
+    /// Set the gap between groups, as a ratio of the spacing (default 0.2)
+    pub fn with_group_gap(mut self, ratio: f32) -> Self {
+        self.group_gap = ratio.clamp(0.0, 0.95);
+        self
+    }
+
+    /// Set the gap between grouped bars, as a ratio of bar width
+    /// (default 0.1)
+    pub fn with_bar_gap(mut self, ratio: f32) -> Self {
+        self.bar_gap = ratio.clamp(0.0, 0.95);
+        self
+    }
+
+    /// Get the plots, with bar slots assigned
+    pub(crate) fn into_plots(self, stack: usize) -> Vec<Plot<'a>> {
+        let count = self.plots.len();
+        let fill = 1.0 - self.group_gap;
+        let bar_fill = 1.0 - self.bar_gap;
+        self.plots
+            .into_iter()
+            .enumerate()
+            .map(|(index, plot)| {
+                let slot = match self.layout {
+                    BarLayout::Stacked => BarSlot::stacked(stack, fill),
+                    BarLayout::Grouped => {
+                        BarSlot::grouped(index, count, fill, bar_fill)
+                    }
+                };
+                plot.with_kind(PlotKind::Bar(slot))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pointy::BBox;
+
+    use super::*;
+    use crate::Chart;
+
+    fn rects(html: &str, class: &str) -> Vec<(i32, i32, i32, i32)> {
+        let group = html.split_once(class).unwrap().1;
+        let group = group.split_once("</g>").unwrap().0;
+        group
+            .lines()
+            .filter(|line| line.starts_with("<rect"))
+            .map(|line| {
+                let attr = |name: &str| -> i32 {
+                    let v = line.split_once(&format!("{name}='")).unwrap().1;
+                    v.split_once('\'').unwrap().0.parse().unwrap()
+                };
+                (attr("x"), attr("y"), attr("width"), attr("height"))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stacked() {
+        let domain = BBox::new([(0.0, 0.0), (2.0, 10.0)]);
+        let group = BarGroup::new(BarLayout::Stacked)
+            .with_plot(Plot::new("A", &domain, [(0.0, 2.0), (1.0, 4.0)]))
+            .with_plot(Plot::new("B", &domain, [(0.0, 3.0), (1.0, 5.0)]));
+        let html = Chart::default().with_bar_group(group).render();
+        let a = rects(&html, "plot-0 plot-bar");
+        let b = rects(&html, "plot-1 plot-bar");
+        // Each bar in B starts at the top of the bar in A
+        for (a, b) in a.iter().zip(&b) {
+            assert_eq!(a.0, b.0);
+            assert_eq!(a.2, b.2);
+            assert_eq!(b.1 + b.3, a.1);
+        }
+        // Cumulative heights: 2 + 3 and 4 + 5
+        assert!(b[0].3 > a[0].3);
+        assert!(a[0].3 + b[0].3 < a[1].3 + b[1].3);
+    }
+
+    #[test]
+    fn grouped() {
+        let domain = BBox::new([(0.0, 0.0), (2.0, 10.0)]);
+        let data = [(0.0, 2.0), (1.0, 4.0)];
+        let group = BarGroup::new(BarLayout::Grouped)
+            .with_group_gap(0.0)
+            .with_bar_gap(0.0)
+            .with_plot(Plot::new("A", &domain, data))
+            .with_plot(Plot::new("B", &domain, data));
+        let html = Chart::default().with_bar_group(group).render();
+        let a = rects(&html, "plot-0 plot-bar");
+        let b = rects(&html, "plot-1 plot-bar");
+        // Bars are side-by-side, filling the slot without gaps
+        assert_eq!(a[0].0 + a[0].2, b[0].0);
+        assert_eq!(a[0].1, b[0].1);
+        assert_eq!(b[0].0 + b[0].2, a[1].0);
+    }
+
+    #[test]
+    fn gaps() {
+        let slot = BarSlot::grouped(1, 2, 0.8, 0.5);
+        // Group is 80 wide, each bar has 40, filled to 20
+        assert_eq!(slot.geometry(100), (20, 20));
+        let slot = BarSlot::stacked(0, 0.5);
+        assert_eq!(slot.geometry(-100), (50, 0));
+    }
+}
@@ -0,0 +1,125 @@
+// bubble.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Bubble plots
+
+use std::{fmt, fmt::Write};
+
+use pointy::BBox;
+
+use crate::{
+    plot::{display_legend_note, Mapping, Scales, Series},
+    text::Escape,
+};
+
+/// Bubble plot
+///
+/// Each datum is an `(x, y, size)` tuple, where the size is mapped to the area
+/// of the bubble.
+pub struct BubblePlot<'a> {
+    name: &'a str,
+    domain: &'a BBox<f32>,
+    data: Vec<(f32, f32, f32)>,
+    radius: (f32, f32),
+    max_size: f32,
+}
+
+impl<'a> BubblePlot<'a> {
+    /// Create a new bubble plot
+    pub fn new<I>(name: &'a str, domain: &'a BBox<f32>, data: I) -> Self
+    where
+        I: IntoIterator<Item = (f32, f32, f32)>,
+    {
+        let data: Vec<(f32, f32, f32)> = data.into_iter().collect();
+        let max_size = data.iter().fold(0.0, |m, d| d.2.max(m));
+        Self {
+            name,
+            domain,
+            data,
+            radius: (10.0, 60.0),
+            max_size,
+        }
+    }
+
+    /// Set the range of bubble radii (in SVG units)
+    ///
+    /// A size of zero is drawn with the minimum radius, and the largest size
+    /// is drawn with the maximum radius.
+    pub fn with_radius_range(mut self, min: f32, max: f32) -> Self {
+        self.radius = (min.min(max), min.max(max));
+        self
+    }
+
+    /// Get the radius of a bubble
+    fn radius(&self, size: f32) -> f32 {
+        let (min, max) = self.radius;
+        let t = if self.max_size > 0.0 {
+            (size / self.max_size).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        // Interpolate area rather than radius
+        (min * min + (max * max - min * min) * t).sqrt()
+    }
+}
+
+impl Series for BubblePlot<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn domain(&self) -> Option<&BBox<f32>> {
+        Some(self.domain)
+    }
+
+    fn display_legend(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        note: Option<&str>,
+    ) -> fmt::Result {
+        let max = self.radius.1;
+        writeln!(f, "<div>")?;
+        writeln!(f, "<svg width='20' height='10' viewBox='0 0 60 30'>")?;
+        write!(f, "<circle class='plot-{num} plot-bubble'")?;
+        writeln!(f, " cx='30' cy='15' r='12'/>")?;
+        writeln!(f, "</svg>")?;
+        writeln!(f, "{}", Escape(self.name))?;
+        display_legend_note(f, note)?;
+        writeln!(f, "</div>")?;
+        for size in [self.max_size / 4.0, self.max_size / 2.0, self.max_size] {
+            let r = self.radius(size) * 15.0 / max;
+            writeln!(f, "<div>")?;
+            writeln!(f, "<svg width='20' height='10' viewBox='0 0 60 30'>")?;
+            write!(f, "<circle class='plot-{num} legend-bubble'")?;
+            writeln!(f, " cx='30' cy='15' r='{r}'/>")?;
+            writeln!(f, "</svg>")?;
+            writeln!(f, "{size}")?;
+            writeln!(f, "</div>")?;
+        }
+        Ok(())
+    }
+
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        rect: BBox<f32>,
+        scales: &Scales,
+        _stack: &mut Vec<f32>,
+    ) -> fmt::Result {
+        let map = Mapping::new(self.domain, rect, scales);
+
+        writeln!(f, "<g class='plot-{num} plot-bubble'>")?;
+
+        for &(x, y, size) in &self.data {
+            let x = map.x_map(x);
+            let y = map.y_map(y);
+            let r = self.radius(size).round();
+            writeln!(f, "<circle cx='{x}' cy='{y}' r='{r}'/>")?;
+        }
+
+        writeln!(f, "</g>")
+    }
+}
@@ -0,0 +1,118 @@
+// candle.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Candlestick plots
+
+use std::{fmt, fmt::Write};
+
+use pointy::BBox;
+
+use crate::plot::{min_spacing, Mapping, Scales, Series, BAR_FILL};
+
+/// Open, high, low and close values at one `X` value
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ohlc {
+    /// `X` value
+    pub x: f32,
+    /// Opening value
+    pub open: f32,
+    /// Highest value
+    pub high: f32,
+    /// Lowest value
+    pub low: f32,
+    /// Closing value
+    pub close: f32,
+}
+
+/// Candlestick plot for financial data
+///
+/// Each record is drawn as a thin high-low line with a body from open to
+/// close.  Bodies are given the `candle-up` class when the close is at or
+/// above the open, and `candle-down` otherwise.
+pub struct Candlestick<'a> {
+    name: &'a str,
+    domain: &'a BBox<f32>,
+    data: Vec<Ohlc>,
+}
+
+impl From<(f32, f32, f32, f32, f32)> for Ohlc {
+    fn from((x, open, high, low, close): (f32, f32, f32, f32, f32)) -> Self {
+        Self {
+            x,
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+}
+
+impl<'a> Candlestick<'a> {
+    /// Create a new candlestick plot
+    pub fn new<I>(name: &'a str, domain: &'a BBox<f32>, data: I) -> Self
+    where
+        I: IntoIterator<Item = Ohlc>,
+    {
+        Self {
+            name,
+            domain,
+            data: data.into_iter().collect(),
+        }
+    }
+}
+
+impl Series for Candlestick<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn domain(&self) -> Option<&BBox<f32>> {
+        Some(self.domain)
+    }
+
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        rect: BBox<f32>,
+        scales: &Scales,
+        _stack: &mut Vec<f32>,
+    ) -> fmt::Result {
+        let map = Mapping::new(self.domain, rect, scales);
+        let data = &self.data;
+        let spacing = min_spacing(data.iter().map(|d| d.x))
+            .unwrap_or_else(|| self.domain.x_span());
+        let x0 = map.x_map(self.domain.x_min());
+        let x1 = map.x_map(self.domain.x_min() + spacing);
+        let width = ((x1 - x0).abs() as f32 * BAR_FILL).round() as i32;
+
+        writeln!(f, "<g class='plot-{num} plot-candlestick'>")?;
+
+        for d in data {
+            let class = if d.close >= d.open {
+                "candle-up"
+            } else {
+                "candle-down"
+            };
+            let x = map.x_map(d.x);
+            let high = map.y_map(d.high);
+            let low = map.y_map(d.low);
+            let open = map.y_map(d.open);
+            let close = map.y_map(d.close);
+            let (y, height) = (open.min(close), (open - close).abs());
+            writeln!(f, "<g class='{class}'>")?;
+            writeln!(
+                f,
+                "<path d='M{x} {}V{}'/>",
+                high.min(low),
+                high.max(low)
+            )?;
+            write!(f, "<rect x='{}' y='{y}'", x - width / 2)?;
+            writeln!(f, " width='{width}' height='{height}'/>")?;
+            writeln!(f, "</g>")?;
+        }
+
+        writeln!(f, "</g>")
+    }
+}
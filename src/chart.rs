@@ -3,34 +3,107 @@
 // Copyright (c) 2021  Douglas P Lau
 // Copyright (c) 2022  Jeron A Lau
 //
-use std::{fmt, fmt::Write};
+use std::{
+    borrow::Cow,
+    fmt,
+    fmt::Write,
+    fs,
+    io::{self, Write as _},
+    ops::Range,
+    path::Path,
+};
 
 use pointy::{BBox, Pt};
 
 use crate::{
+    annotation::{Annotation, RefLine, Region},
     axis::Axis,
-    page::{AspectRatio, Edge},
-    plot::{Plot, PlotKind},
-    text::{Anchor, Text},
+    band::Band,
+    bar::BarGroup,
+    bubble::BubblePlot,
+    candle::Candlestick,
+    css::{self, generated_color, Palette},
+    error::Error,
+    heatmap::Heatmap,
+    json,
+    legend::Legend,
+    marker::{default_shape, MARKER_SIZE},
+    page::{AspectRatio, Edge, Orientation},
+    pie::Pie,
+    plot::{BarSlot, Mapping, Plot, PlotKind, Scales, Series, Step},
+    row::Row,
+    scale::Numeric,
+    text::{Anchor, Escape, Link, Text, Tspan},
+    theme::{Theme, PLOT_CLASSES},
+    waterfall::Waterfall,
 };
 
-/// Marker shapes
-const MARKERS: &[&str] = &[
-    "<circle r='1'/>",
-    "<rect x='-1' y='-1' width='2' height='2'/>",
-    "<path d='M0 -1 1 1 -1 1z'/>",
-    "<path d='M1 0 -1 1 -1 -1z'/>",
-    "<path d='M0 1 -1 -1 1 -1z'/>",
-    "<path d='M-1 0 1 -1 1 1z'/>",
-    "<path d='M0 -1 1 0 0 1 -1 0z'/>",
-    "<path d='M-1 -1 0 -0.5 1 -1 0.5 0 1 1 0 0.5 -1 1 -0.5 0z'/>",
-];
+/// Adapter to write formatted text to an `io::Write`
+struct IoWriter<W: io::Write> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoWriter<W> {
+    /// Render to the inner stream, keeping any `io::Error`
+    fn render<R>(w: W, render: R) -> io::Result<W>
+    where
+        R: FnOnce(&mut Self) -> fmt::Result,
+    {
+        let mut writer = IoWriter {
+            inner: w,
+            error: None,
+        };
+        match render(&mut writer) {
+            Ok(()) => Ok(writer.inner),
+            Err(_) => Err(writer
+                .error
+                .take()
+                .unwrap_or_else(|| io::Error::other("formatter error"))),
+        }
+    }
+}
+
+impl<W: io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+/// Stylesheet for a chart
+pub(crate) enum Stylesheet {
+    /// Embedded default CSS, generated for the number of series
+    Generated(Palette),
+    /// Embedded sparkline CSS, generated for the number of series
+    Sparkline(Palette),
+    /// Embedded CSS
+    Embedded(String),
+    /// Link to external CSS
+    Href(String),
+}
 
 /// Chart title
 pub struct Title {
     text: String,
     anchor: Anchor,
     edge: Edge,
+    height: f32,
+    line_height: f32,
+    wrap: Option<usize>,
+    class: &'static str,
+    inherit_anchor: bool,
+    inherit_edge: bool,
+    link: Option<Link>,
+}
+
+/// Chart caption, such as a source attribution
+///
+/// Captions are placed below everything else, in a smaller `caption` class.
+pub struct Caption {
+    title: Title,
 }
 
 /// Chart for plotting data
@@ -39,9 +112,56 @@ pub struct Title {
 /// domains and axes.
 pub struct Chart<'a> {
     aspect_ratio: AspectRatio,
+    pixel_size: Option<(u32, u32)>,
+    padding: u16,
+    precision: u8,
+    id: Option<String>,
     titles: Vec<Title>,
+    caption: Option<Caption>,
+    description: Option<String>,
     axes: Vec<Box<dyn Axis + 'a>>,
-    plots: Vec<(PlotKind, Plot<'a>)>,
+    plots: Vec<Box<dyn Series + 'a>>,
+    ref_lines: Vec<RefLine>,
+    regions: Vec<Region>,
+    annotations: Vec<Annotation>,
+    legend: Option<Legend>,
+    legend_axes: bool,
+    grid: bool,
+    plot_border: bool,
+    plot_background: bool,
+    band_fill: Option<Orientation>,
+    stylesheet: Stylesheet,
+    theme: Option<Theme>,
+    stacks: usize,
+    rows: Vec<RowRange>,
+    overlays: Vec<Overlay<'a>>,
+    embedded_data: bool,
+    domain_padding: f32,
+    /// Union of automatic plot extents, before padding
+    auto_extent: Option<BBox<f32>>,
+}
+
+/// Function to map a data point within a domain to the plot area
+pub type PointMap<'m> = &'m dyn Fn(Pt<f32>, &BBox<f32>) -> Pt<f32>;
+
+/// Custom SVG overlay drawn on top of plots
+type Overlay<'a> =
+    Box<dyn Fn(&mut dyn Write, BBox<f32>, PointMap) -> fmt::Result + 'a>;
+
+/// Axes and plots belonging to a row
+struct RowRange {
+    axes: Range<usize>,
+    plots: Range<usize>,
+}
+
+/// Layout of axes and rows within a chart
+struct Layout {
+    /// Plot area, covering all rows
+    area: BBox<f32>,
+    /// Rectangle of each axis
+    axis_rects: Vec<BBox<f32>>,
+    /// Plot area of each row
+    rows: Vec<BBox<f32>>,
 }
 
 impl<T: Into<String>> From<T> for Title {
@@ -57,107 +177,886 @@ impl Title {
             text: text.into(),
             anchor: Anchor::Middle,
             edge: Edge::Top,
+            height: 100.0,
+            line_height: 60.0,
+            wrap: None,
+            class: "title",
+            inherit_anchor: false,
+            inherit_edge: false,
+            link: None,
+        }
+    }
+
+    /// Create a subtitle
+    ///
+    /// A subtitle is smaller than a title, and is laid out next to the
+    /// previous title.  Its edge and anchor match that title, unless
+    /// overridden.
+    pub fn subtitle(text: impl Into<String>) -> Self {
+        Self {
+            height: 60.0,
+            line_height: 38.0,
+            class: "subtitle",
+            inherit_anchor: true,
+            inherit_edge: true,
+            ..Title::new(text)
         }
     }
 
+    /// Anchor title text in the middle (default)
+    pub fn centered(mut self) -> Self {
+        self.anchor = Anchor::Middle;
+        self.inherit_anchor = false;
+        self
+    }
+
     /// Anchor title text at start
     pub fn at_start(mut self) -> Self {
         self.anchor = Anchor::Start;
+        self.inherit_anchor = false;
         self
     }
 
     /// Anchor title text at end
     pub fn at_end(mut self) -> Self {
         self.anchor = Anchor::End;
+        self.inherit_anchor = false;
         self
     }
 
     /// Put title on bottom of chart
     pub fn on_bottom(mut self) -> Self {
         self.edge = Edge::Bottom;
+        self.inherit_edge = false;
         self
     }
 
     /// Put title on left side of chart
+    ///
+    /// The text is rotated to read from bottom to top, and the height is
+    /// reserved as the width of the title.
     pub fn on_left(mut self) -> Self {
         self.edge = Edge::Left;
+        self.inherit_edge = false;
         self
     }
 
     /// Put title on right side of chart
+    ///
+    /// The text is rotated to read from top to bottom, and the height is
+    /// reserved as the width of the title.
     pub fn on_right(mut self) -> Self {
         self.edge = Edge::Right;
+        self.inherit_edge = false;
+        self
+    }
+
+    /// Inherit edge and anchor from the previous title, for subtitles
+    pub(crate) fn follow(mut self, prev: Option<&Title>) -> Self {
+        if let Some(prev) = prev {
+            if self.inherit_anchor {
+                self.anchor = prev.anchor;
+            }
+            if self.inherit_edge {
+                self.edge = prev.edge;
+            }
+        }
+        self
+    }
+
+    /// Set the space for the title (in SVG units)
+    ///
+    /// The default is 100.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height.max(0.0);
+        self
+    }
+
+    /// Soft-wrap lines longer than a number of characters
+    ///
+    /// Lines are wrapped at whitespace, and explicit newlines are always
+    /// kept.  The reserved height grows with each extra line.
+    pub fn wrap_at(mut self, chars: usize) -> Self {
+        self.wrap = Some(chars.max(1));
         self
     }
 
+    /// Link the title to a URL
+    pub fn with_href<L: Into<Link>>(mut self, link: L) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    /// Get the lines of text
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![];
+        for text in self.text.lines() {
+            let wrap = match self.wrap {
+                Some(wrap) => wrap,
+                None => {
+                    lines.push(text.to_string());
+                    continue;
+                }
+            };
+            let mut line = String::new();
+            for word in text.split_whitespace() {
+                let len = line.chars().count() + word.chars().count();
+                if !line.is_empty() && len >= wrap {
+                    lines.push(std::mem::take(&mut line));
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
+            lines.push(line);
+        }
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines
+    }
+
+    /// Split the title from an area
+    fn split(&self, area: &mut BBox<f32>) -> BBox<f32> {
+        let lines = self.lines().len() - 1;
+        let height = self.height + lines as f32 * self.line_height;
+        self.edge.split(area, height)
+    }
+
+    /// Split the title from an area and display it
+    pub(crate) fn display_split(
+        &self,
+        f: &mut dyn Write,
+        area: &mut BBox<f32>,
+    ) -> fmt::Result {
+        let rect = self.split(area);
+        self.display(f, rect)
+    }
+
     fn display(&self, f: &mut dyn Write, rect: BBox<f32>) -> fmt::Result {
         let text = Text::new(self.edge)
             .with_rect(rect)
             .with_anchor(self.anchor)
-            .with_class_name("title");
+            .with_class_name(self.class)
+            .with_link(self.link.as_ref());
         text.display(f)?;
-        writeln!(f, "{}", self.text)?;
+        let lines = self.lines();
+        if let [line] = &lines[..] {
+            writeln!(f, "{}", Escape(line))?;
+        } else {
+            // Lines are stacked, centered on the title rect
+            let first = -0.6 * (lines.len() as f32 - 1.0);
+            for (i, line) in lines.iter().enumerate() {
+                let dy = if i == 0 { first } else { 1.2 };
+                Tspan::new(line).x(0).dy(dy).display(f)?;
+            }
+        }
         text.display_done(f)
     }
 }
 
+impl<T: Into<String>> From<T> for Caption {
+    fn from(text: T) -> Self {
+        Caption::new(text.into())
+    }
+}
+
+impl Caption {
+    /// Create a new caption, anchored at start
+    pub fn new(text: impl Into<String>) -> Self {
+        let title = Title {
+            anchor: Anchor::Start,
+            edge: Edge::Bottom,
+            height: 50.0,
+            line_height: 34.0,
+            class: "caption",
+            ..Title::new(text)
+        };
+        Self { title }
+    }
+
+    /// Anchor caption text in the middle
+    pub fn centered(mut self) -> Self {
+        self.title.anchor = Anchor::Middle;
+        self
+    }
+
+    /// Anchor caption text at end
+    pub fn at_end(mut self) -> Self {
+        self.title.anchor = Anchor::End;
+        self
+    }
+
+    /// Soft-wrap lines longer than a number of characters
+    pub fn wrap_at(mut self, chars: usize) -> Self {
+        self.title = self.title.wrap_at(chars);
+        self
+    }
+
+    /// Set the space for the caption (in SVG units)
+    ///
+    /// The default is 50.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.title = self.title.with_height(height);
+        self
+    }
+}
+
+impl Stylesheet {
+    /// Get embedded CSS, for a number of series
+    pub(crate) fn css(&self, n_series: usize) -> Option<Cow<'_, str>> {
+        match self {
+            Stylesheet::Generated(palette) => {
+                Some(Cow::Owned(css::with_palette(n_series, palette)))
+            }
+            Stylesheet::Sparkline(palette) => {
+                Some(Cow::Owned(css::sparkline(n_series, palette)))
+            }
+            Stylesheet::Embedded(css) => Some(Cow::Borrowed(css)),
+            Stylesheet::Href(_) => None,
+        }
+    }
+
+    /// Get the number of series with colors and markers in the stylesheet
+    pub(crate) fn styled_series(&self, n_series: usize) -> usize {
+        match self {
+            Stylesheet::Generated(_) | Stylesheet::Sparkline(_) => n_series,
+            _ => n_series.min(PLOT_CLASSES),
+        }
+    }
+
+    /// Display in an HTML head
+    pub(crate) fn display(
+        &self,
+        f: &mut dyn Write,
+        n_series: usize,
+    ) -> fmt::Result {
+        match self {
+            Stylesheet::Href(url) => {
+                write!(f, "<link href='{}' rel='stylesheet'/>", Escape(url))
+            }
+            _ => match self.css(n_series) {
+                Some(css) => write!(f, "<style>\n{css}</style>"),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
 impl<'a> Default for Chart<'a> {
     fn default() -> Self {
         Self {
             aspect_ratio: AspectRatio::Landscape,
+            pixel_size: None,
+            padding: 40,
+            precision: 0,
+            id: None,
             titles: vec![],
+            caption: None,
+            description: None,
             axes: vec![],
             plots: vec![],
+            ref_lines: vec![],
+            regions: vec![],
+            annotations: vec![],
+            legend: Some(Legend::default()),
+            legend_axes: false,
+            grid: true,
+            plot_border: false,
+            plot_background: false,
+            band_fill: None,
+            stylesheet: Stylesheet::Generated(Palette::default()),
+            theme: None,
+            stacks: 0,
+            rows: vec![],
+            overlays: vec![],
+            embedded_data: false,
+            domain_padding: 0.0,
+            auto_extent: None,
         }
     }
 }
 
 impl<'a> Chart<'a> {
+    /// Create a sparkline chart
+    ///
+    /// A sparkline is a tiny chart with no titles, axes, legend or grid,
+    /// and a minimal stylesheet.  Plots are scaled to their domains.  The
+    /// default size is 100x25 pixels, which can be changed with
+    /// [with_pixel_size](Self::with_pixel_size).
+    pub fn sparkline() -> Self {
+        Self {
+            aspect_ratio: AspectRatio::Custom {
+                width: 400.0,
+                height: 100.0,
+            },
+            pixel_size: Some((100, 25)),
+            padding: 8,
+            legend: None,
+            grid: false,
+            stylesheet: Stylesheet::Sparkline(Palette::default()),
+            ..Self::default()
+        }
+    }
+
     /// Adjust the aspect ratio
     pub fn with_aspect_ratio(mut self, aspect: AspectRatio) -> Self {
         self.aspect_ratio = aspect;
         self
     }
 
+    /// Set the padding around the chart (in SVG units)
+    ///
+    /// The default is 40.  Padding is limited to half of the page size.
+    pub fn with_padding(mut self, padding: u16) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set the number of decimal places for point coordinates
+    ///
+    /// The default is 0, which rounds coordinates to integers.  Larger values
+    /// give smoother lines on small charts, at the cost of output size.  The
+    /// same precision is used for tick and grid positions.  Values above 4
+    /// are treated as 4.
+    pub fn with_precision(mut self, decimals: u8) -> Self {
+        self.precision = decimals.min(4);
+        self
+    }
+
+    /// Set the rendered size (in pixels)
+    ///
+    /// This adds `width` and `height` attributes to the SVG, for viewers
+    /// which ignore CSS sizing.
+    pub fn with_pixel_size(mut self, width: u32, height: u32) -> Self {
+        self.pixel_size = Some((width, height));
+        self
+    }
+
+    /// Embed the default stylesheet (this is the default)
+    pub fn with_default_css(self) -> Self {
+        self.with_palette(Palette::default())
+    }
+
+    /// Embed the default stylesheet, with a palette of series colors
+    ///
+    /// Sparklines keep their minimal stylesheet, with the palette colors.
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.stylesheet = match self.stylesheet {
+            Stylesheet::Sparkline(_) => Stylesheet::Sparkline(palette),
+            _ => Stylesheet::Generated(palette),
+        };
+        self
+    }
+
+    /// Embed a custom stylesheet, replacing the default
+    pub fn with_css(mut self, css: &str) -> Self {
+        self.stylesheet = Stylesheet::Embedded(css.to_string());
+        self
+    }
+
+    /// Link to an external stylesheet instead of embedding one
+    pub fn with_stylesheet_href<U>(mut self, url: U) -> Self
+    where
+        U: Into<String>,
+    {
+        self.stylesheet = Stylesheet::Href(url.into());
+        self
+    }
+
+    /// Apply a color theme
+    ///
+    /// The theme is rendered as CSS after the stylesheet, overriding its
+    /// colors.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
     /// Add a chart title
     pub fn with_title<T>(mut self, title: T) -> Self
     where
         T: Into<Title>,
     {
-        self.titles.push(title.into());
+        let title = title.into().follow(self.titles.last());
+        self.titles.push(title);
+        self
+    }
+
+    /// Add a caption below the chart
+    ///
+    /// The caption is laid out outermost, below any bottom axes or titles.
+    pub fn with_caption<C>(mut self, caption: C) -> Self
+    where
+        C: Into<Caption>,
+    {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Set the chart id, used as a prefix for element ids
+    ///
+    /// Charts sharing one page must have distinct ids.  Only ASCII letters,
+    /// digits, `-` and `_` are kept.
+    pub fn with_id(mut self, id: &str) -> Self {
+        let id: String = id
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        self.id = if id.is_empty() { None } else { Some(id) };
+        self
+    }
+
+    /// Set an automatic page cell id
+    ///
+    /// These contain a `.`, so they can't collide with ids set by
+    /// [with_id](Self::with_id).
+    pub(crate) fn with_cell_id(mut self, num: usize) -> Self {
+        self.id = Some(format!("cell.{num}"));
+        self
+    }
+
+    /// Get plots with their first `plot-N` class numbers
+    ///
+    /// Series with several colors, such as pies, reserve a number for each.
+    fn numbered_plots(&self) -> impl Iterator<Item = (usize, &dyn Series)> {
+        self.plots.iter().scan(0, |num, plot| {
+            let first = *num;
+            *num += plot.color_count();
+            Some((first, plot.as_ref()))
+        })
+    }
+
+    /// Get the number of series colors
+    ///
+    /// Series with several colors, such as pies, count each one.
+    pub(crate) fn series_count(&self) -> usize {
+        self.plots.iter().map(|plot| plot.color_count()).sum()
+    }
+
+    /// Get the chart id
+    pub(crate) fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Get the prefix of element ids
+    fn prefix(&self) -> String {
+        match &self.id {
+            Some(id) => format!("{id}-"),
+            None => String::new(),
+        }
+    }
+
+    /// Display the chart id as a scoping attribute
+    fn display_scope(&self, f: &mut dyn Write) -> fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, " data-chart='{id}'"),
+            None => Ok(()),
+        }
+    }
+
+    /// Add a description, for screen readers
+    pub fn with_description<D>(mut self, description: D) -> Self
+    where
+        D: Into<String>,
+    {
+        self.description = Some(description.into());
         self
     }
 
     /// Add an `Axis`
     pub fn with_axis<A: Axis + 'a>(mut self, axis: A) -> Self {
-        self.axes.push(Box::new(axis));
+        let mut axis = Box::new(axis);
+        if let Some(domain) = self.auto_domain() {
+            axis.set_auto_domain(domain);
+        }
+        self.axes.push(axis);
+        self
+    }
+
+    /// Add a reference line
+    ///
+    /// Reference lines are mapped through the scales of the first horizontal
+    /// and vertical axes.
+    pub fn with_ref_line(mut self, line: RefLine) -> Self {
+        self.ref_lines.push(line);
+        self
+    }
+
+    /// Add a horizontal reference line at a `Y` value, with a label
+    pub fn with_h_line(self, y: f32, label: &str) -> Self {
+        self.with_ref_line(RefLine::horizontal(y).with_label(label))
+    }
+
+    /// Add a vertical reference line at an `X` value, with a label
+    pub fn with_v_line(self, x: f32, label: &str) -> Self {
+        self.with_ref_line(RefLine::vertical(x).with_label(label))
+    }
+
+    /// Add a shaded region, drawn behind the plots
+    ///
+    /// Regions are mapped through the scales of the first horizontal and
+    /// vertical axes.
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.regions.push(region);
+        self
+    }
+
+    /// Add a vertical shaded region between two `X` values
+    pub fn with_v_band(self, x0: f32, x1: f32) -> Self {
+        self.with_region(Region::vertical(x0, x1))
+    }
+
+    /// Add a horizontal shaded region between two `Y` values
+    pub fn with_h_band(self, y0: f32, y1: f32) -> Self {
+        self.with_region(Region::horizontal(y0, y1))
+    }
+
+    /// Add a text annotation, drawn on top of the plots
+    ///
+    /// Annotations are mapped through the scales of the first horizontal and
+    /// vertical axes, and are not listed in the legend.
+    pub fn with_annotation(mut self, annotation: Annotation) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// Add a custom SVG overlay, drawn on top of plots
+    ///
+    /// The function is called with the writer, the plot area, and a function
+    /// which maps data points to the plot area.  Output is clipped to the
+    /// plot area.
+    pub fn with_overlay<F>(mut self, overlay: F) -> Self
+    where
+        F: Fn(&mut dyn Write, BBox<f32>, PointMap) -> fmt::Result + 'a,
+    {
+        self.overlays.push(Box::new(overlay));
+        self
+    }
+
+    /// Embed the data points of each plot as JSON
+    ///
+    /// The data is an object of `[x, y]` arrays keyed by plot name, with
+    /// `null` for values which are not finite.  HTML output puts it in a
+    /// `script` element, and SVG output in a `metadata` element, both with a
+    /// `splotch-data` class.
+    pub fn with_embedded_data(mut self) -> Self {
+        self.embedded_data = true;
+        self
+    }
+
+    /// Set padding for automatic domains
+    ///
+    /// Each side of the domain is extended by a fraction of its span, such as
+    /// `0.05` for 5%.
+    pub fn with_domain_padding(mut self, fraction: f32) -> Self {
+        self.domain_padding = fraction.max(0.0);
+        self.apply_auto();
+        self
+    }
+
+    /// Disable grid lines for all axes
+    pub fn without_grid(mut self) -> Self {
+        self.grid = false;
+        self
+    }
+
+    /// Draw a border around the plot area, above the plots
+    pub fn with_plot_border(mut self) -> Self {
+        self.plot_border = true;
+        self
+    }
+
+    /// Fill the plot area background, beneath the grid
+    pub fn with_plot_background(mut self) -> Self {
+        self.plot_background = true;
+        self
+    }
+
+    /// Fill alternating bands between ticks, beneath the grid
+    ///
+    /// Horizontal bands use ticks from the first vertical axis, and vertical
+    /// bands use ticks from the first horizontal axis.
+    pub fn with_band_fill(mut self, orientation: Orientation) -> Self {
+        self.band_fill = Some(orientation);
+        self
+    }
+
+    /// Configure the legend
+    pub fn with_legend(mut self, legend: Legend) -> Self {
+        self.legend = Some(legend);
+        self
+    }
+
+    /// Disable the legend
+    pub fn without_legend(mut self) -> Self {
+        self.legend = None;
+        self
+    }
+
+    /// Annotate legend entries with the axes their plots are bound to
+    ///
+    /// Entries are annotated with the axis name, or its id if unnamed.
+    pub fn with_legend_axes(mut self) -> Self {
+        self.legend_axes = true;
         self
     }
 
     /// Add an area `Plot`
     pub fn with_area_plot(mut self, plot: Plot<'a>) -> Self {
-        self.plots.push((PlotKind::Area, plot));
+        self.push_plot(Box::new(plot.with_kind(PlotKind::Area)));
+        self
+    }
+
+    /// Add a group of stacked area `Plot`s
+    ///
+    /// Each area is drawn on top of the cumulative sum of the previous plots
+    /// in the group, which are listed in stack order (bottom first).  Points
+    /// are matched by position, so all plots in the group must share the same
+    /// `X` values.
+    pub fn with_stacked_area_plots(mut self, plots: Vec<Plot<'a>>) -> Self {
+        let stack = self.stacks;
+        self.stacks += 1;
+        for plot in plots {
+            self.push_plot(Box::new(
+                plot.with_kind(PlotKind::StackedArea(stack)),
+            ));
+        }
+        self
+    }
+
+    /// Add a bar `Plot`
+    ///
+    /// Bars extend from zero to each point's `Y` value, and their width is
+    /// derived from the spacing between consecutive `X` values.
+    pub fn with_bar_plot(mut self, plot: Plot<'a>) -> Self {
+        self.push_plot(Box::new(
+            plot.with_kind(PlotKind::Bar(BarSlot::default())),
+        ));
+        self
+    }
+
+    /// Add a group of bar `Plot`s, stacked or side-by-side
+    pub fn with_bar_group(mut self, group: BarGroup<'a>) -> Self {
+        let stack = self.stacks;
+        self.stacks += 1;
+        for plot in group.into_plots(stack) {
+            self.push_plot(Box::new(plot));
+        }
+        self
+    }
+
+    /// Add a horizontal bar `Plot`
+    ///
+    /// Bars extend from zero to each point's `X` value, and their height is
+    /// derived from the spacing between consecutive `Y` values.
+    pub fn with_hbar_plot(mut self, plot: Plot<'a>) -> Self {
+        self.push_plot(Box::new(plot.with_kind(PlotKind::HBar)));
+        self
+    }
+
+    /// Add a `Band` plot
+    pub fn with_band_plot(mut self, plot: Band<'a>) -> Self {
+        self.push_plot(Box::new(plot));
+        self
+    }
+
+    /// Add a `BubblePlot`
+    pub fn with_bubble_plot(mut self, plot: BubblePlot<'a>) -> Self {
+        self.push_plot(Box::new(plot));
+        self
+    }
+
+    /// Add a `Candlestick` plot
+    pub fn with_candlestick_plot(mut self, plot: Candlestick<'a>) -> Self {
+        self.push_plot(Box::new(plot));
+        self
+    }
+
+    /// Add a `Heatmap` plot
+    pub fn with_heatmap(mut self, plot: Heatmap<'a>) -> Self {
+        self.push_plot(Box::new(plot));
         self
     }
 
     /// Add a line `Plot`
     pub fn with_line_plot(mut self, plot: Plot<'a>) -> Self {
-        self.plots.push((PlotKind::Line, plot));
+        self.push_plot(Box::new(plot.with_kind(PlotKind::Line)));
+        self
+    }
+
+    /// Add a `Pie` chart
+    ///
+    /// A pie is drawn centered in the chart area, and does not need any axes.
+    pub fn with_pie(mut self, pie: Pie<'a>) -> Self {
+        self.push_plot(Box::new(pie));
         self
     }
 
     /// Add a scatter `Plot`
     pub fn with_scatter_plot(mut self, plot: Plot<'a>) -> Self {
-        self.plots.push((PlotKind::Scatter, plot));
+        self.push_plot(Box::new(plot.with_kind(PlotKind::Scatter)));
+        self
+    }
+
+    /// Add a smooth line `Plot`
+    ///
+    /// The line is drawn as a Catmull-Rom curve passing through every point.
+    pub fn with_smooth_plot(self, plot: Plot<'a>) -> Self {
+        self.with_smooth_plot_tension(plot, 0.0)
+    }
+
+    /// Add a smooth line `Plot` with a tension
+    ///
+    /// A tension of `0.0` is a Catmull-Rom curve, while `1.0` hugs the data so
+    /// tightly that it's drawn with straight segments.
+    pub fn with_smooth_plot_tension(
+        mut self,
+        plot: Plot<'a>,
+        tension: f32,
+    ) -> Self {
+        self.push_plot(Box::new(plot.with_kind(PlotKind::Smooth(tension))));
+        self
+    }
+
+    /// Add a step `Plot`
+    ///
+    /// Each step moves along `X` to the next point, then steps to its `Y`
+    /// value.
+    pub fn with_step_plot(self, plot: Plot<'a>) -> Self {
+        self.with_step_plot_at(plot, Step::Post)
+    }
+
+    /// Add a step `Plot`, choosing where the step happens
+    pub fn with_step_plot_at(mut self, plot: Plot<'a>, step: Step) -> Self {
+        self.push_plot(Box::new(plot.with_kind(PlotKind::Step(step))));
+        self
+    }
+
+    /// Add a row, for a faceted chart
+    ///
+    /// Rows are stacked vertically from the top, each with its own vertical
+    /// axes and plots.  Horizontal axes added to the chart are shared by all
+    /// rows, and their grid lines run through every row.
+    pub fn with_row(mut self, row: Row<'a>) -> Self {
+        let Row { axes, plots } = row;
+        let (a, p) = (self.axes.len(), self.plots.len());
+        self.axes.extend(axes);
+        self.plots.extend(plots);
+        self.rows.push(RowRange {
+            axes: a..self.axes.len(),
+            plots: p..self.plots.len(),
+        });
+        self.extend_auto(p);
+        self
+    }
+
+    /// Add a plot, updating automatic domains
+    fn push_plot(&mut self, plot: Box<dyn Series + 'a>) {
+        self.plots.push(plot);
+        self.extend_auto(self.plots.len() - 1);
+    }
+
+    /// Extend the automatic extent with plots starting at an index
+    ///
+    /// Each plot's extent is only computed once, when it is added.
+    fn extend_auto(&mut self, first: usize) {
+        let extents =
+            self.plots[first..].iter().filter_map(|p| p.auto_extent());
+        for extent in extents {
+            match &mut self.auto_extent {
+                Some(domain) => domain.extend([
+                    (extent.x_min(), extent.y_min()),
+                    (extent.x_max(), extent.y_max()),
+                ]),
+                None => self.auto_extent = Some(extent),
+            }
+        }
+        self.apply_auto();
+    }
+
+    /// Get the automatic domain, with padding
+    fn auto_domain(&self) -> Option<BBox<f32>> {
+        let domain = self.auto_extent?;
+        let pad_x = domain.x_span() * self.domain_padding;
+        let pad_y = domain.y_span() * self.domain_padding;
+        Some(BBox::new([
+            (domain.x_min() - pad_x, domain.y_min() - pad_y),
+            (domain.x_max() + pad_x, domain.y_max() + pad_y),
+        ]))
+    }
+
+    /// Set the automatic domain on every automatic plot and axis
+    fn apply_auto(&mut self) {
+        let Some(domain) = self.auto_domain() else {
+            return;
+        };
+        for plot in &mut self.plots {
+            plot.set_auto_domain(domain);
+        }
+        for axis in &mut self.axes {
+            axis.set_auto_domain(domain);
+        }
+    }
+
+    /// Get the row of an axis
+    fn axis_row(&self, i: usize) -> Option<usize> {
+        self.rows.iter().position(|row| row.axes.contains(&i))
+    }
+
+    /// Get the row of a plot
+    fn plot_row(&self, i: usize) -> Option<usize> {
+        self.rows.iter().position(|row| row.plots.contains(&i))
+    }
+
+    /// Get the axes of a row, or shared axes for `None`
+    fn row_axes(
+        &self,
+        row: Option<usize>,
+    ) -> impl Iterator<Item = &(dyn Axis + 'a)> {
+        self.axes
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| self.axis_row(*i) == row)
+            .map(|(_, axis)| axis.as_ref())
+    }
+
+    /// Add a `Waterfall` plot
+    pub fn with_waterfall_plot(mut self, plot: Waterfall<'a>) -> Self {
+        self.push_plot(Box::new(plot));
         self
     }
 
-    fn svg(&self, f: &mut dyn Write, stand_alone: bool) -> fmt::Result {
+    /// Display the opening svg element
+    ///
+    /// Charts within a page cell are nested, and scaled to fit the cell.
+    fn svg(&self, f: &mut dyn Write, cell: Option<BBox<f32>>) -> fmt::Result {
         let rect = self.aspect_ratio.rect();
         write!(f, "<svg")?;
-        if stand_alone {
+        if cell.is_none() {
             write!(f, " xmlns='http://www.w3.org/2000/svg'")?;
         }
+        self.display_scope(f)?;
+        if let Some(cell) = cell {
+            write!(f, " x='{}' y='{}'", cell.x_min(), cell.y_min())?;
+            write!(f, " width='{}' height='{}'", cell.x_span(), cell.y_span())?;
+        } else if let Some((width, height)) = self.pixel_size {
+            write!(f, " width='{width}' height='{height}'")?;
+        }
+        write!(f, " role='img'")?;
+        let prefix = self.prefix();
+        let labels: Vec<String> = [
+            (!self.titles.is_empty()).then_some("chart-title"),
+            self.description.as_ref().map(|_| "chart-desc"),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|id| format!("{prefix}{id}"))
+        .collect();
+        if !labels.is_empty() {
+            write!(f, " aria-labelledby='{}'", labels.join(" "))?;
+        }
         write!(f, " viewBox='")?;
         writeln!(
             f,
@@ -166,113 +1065,1450 @@ impl<'a> Chart<'a> {
             rect.y_min(),
             rect.x_span(),
             rect.y_span()
-        )
+        )?;
+        if !self.titles.is_empty() {
+            let titles: Vec<&str> =
+                self.titles.iter().map(|t| &t.text[..]).collect();
+            let title = titles.join(" ");
+            write!(f, "<title id='{prefix}chart-title'>")?;
+            writeln!(f, "{}</title>", Escape(&title))?;
+        }
+        if let Some(desc) = &self.description {
+            write!(f, "<desc id='{prefix}chart-desc'>")?;
+            writeln!(f, "{}</desc>", Escape(desc))?;
+        }
+        Ok(())
     }
 
     fn defs(&self, f: &mut dyn Write) -> fmt::Result {
+        let prefix = self.prefix();
         writeln!(f, "<defs>")?;
-        for i in 0..self.plots.len() {
-            write!(f, "<marker id='marker-{}'", i)?;
+        for (i, plot) in self.numbered_plots() {
+            write!(f, "<marker id='{prefix}marker-{i}'")?;
             write!(f, " class='plot-{}'", i)?;
+            if let Some(color) = plot.color() {
+                write!(f, " style='--color: {}'", Escape(color))?;
+            }
             write!(f, " viewBox='-1 -1 2 2'")?;
-            writeln!(f, " markerWidth='5' markerHeight='5'>")?;
-            writeln!(f, "{}", MARKERS[i % MARKERS.len()])?;
+            let size = plot.marker_size().unwrap_or(MARKER_SIZE);
+            writeln!(f, " markerWidth='{size}' markerHeight='{size}'>")?;
+            let shape = match plot.marker() {
+                Some(marker) => marker.shape().to_string(),
+                None => default_shape(i),
+            };
+            match plot.color() {
+                // Shapes are filled by the marker rule of the stylesheet
+                Some(color) => writeln!(
+                    f,
+                    "<g style='fill: {}'>{shape}</g>",
+                    Escape(color)
+                )?,
+                None => writeln!(f, "{shape}")?,
+            }
             writeln!(f, "</marker>")?;
         }
-        let area = self.area();
-        writeln!(f, "<clipPath id='clip-chart'>")?;
-        write!(f, "<rect x='{}' y='{}'", area.x_min(), area.y_min())?;
-        writeln!(f, " width='{}' height='{}'/>", area.x_span(), area.y_span())?;
-        writeln!(f, "</clipPath>")?;
+        let layout = self.plot_layout();
+        display_clip(f, &format!("{prefix}clip-chart"), layout.area)?;
+        for (r, row) in layout.rows.iter().enumerate() {
+            display_clip(f, &format!("{prefix}clip-row-{r}"), *row)?;
+        }
         writeln!(f, "</defs>")
     }
 
-    fn body(&mut self, f: &mut dyn Write) -> fmt::Result {
-        let mut area = inset(self.aspect_ratio.rect(), 40);
+    fn body(&self, f: &mut dyn Write) -> fmt::Result {
+        let mut area = inset(self.aspect_ratio.rect(), self.padding);
+        if let Some(caption) = &self.caption {
+            caption.title.display_split(f, &mut area)?;
+        }
         for title in &self.titles {
-            let rect = title.edge.split(&mut area, 100.0);
-            title.display(f, rect)?;
+            title.display_split(f, &mut area)?;
+        }
+        let layout = self.layout(area);
+        let area = layout.area;
+        let axis_area =
+            |i: usize| self.axis_row(i).map_or(area, |r| layout.rows[r]);
+        if self.plot_background {
+            display_rect(f, "plot-background", area)?;
         }
-        let mut axis_rects = vec![];
-        for axis in &self.axes {
-            axis_rects.push(axis.split(&mut area));
+        if let Some(orientation) = self.band_fill {
+            let axis =
+                self.axes.iter().enumerate().find(
+                    |(_, axis)| match orientation {
+                        Orientation::Horizontal => {
+                            axis.bound_y_scale().is_some()
+                        }
+                        Orientation::Vertical => axis.bound_x_scale().is_some(),
+                    },
+                );
+            if let Some((i, axis)) = axis {
+                axis.display_bands(f, axis_area(i), self.precision)?;
+            }
         }
-        for axis in self.axes.iter() {
-            axis.display_grid(f, area)?;
+        if self.grid {
+            for (i, axis) in self.axes.iter().enumerate() {
+                axis.display_grid(f, axis_area(i), self.precision)?;
+            }
         }
-        for (axis, rect) in self.axes.iter().zip(axis_rects) {
-            axis.display(f, rect, area)?;
+        for (i, (axis, rect)) in
+            self.axes.iter().zip(layout.axis_rects).enumerate()
+        {
+            axis.display(f, rect, axis_area(i), self.precision)?;
         }
-        writeln!(f, "<g clip-path='url(#clip-chart)'>")?;
-        for ((kind, plot), num) in self.plots.iter_mut().zip((0..10).cycle()) {
-            (*plot).display(f, num, area, *kind)?;
+        let prefix = self.prefix();
+        writeln!(f, "<g clip-path='url(#{prefix}clip-chart)'>")?;
+        let map = self.annotation_map(area);
+        if let Some(map) = &map {
+            for (num, region) in self.regions.iter().enumerate() {
+                region.display(f, num, map)?;
+            }
+        }
+        let mut stack = (None, vec![]);
+        let mut unclipped = vec![];
+        let mut outside = String::new();
+        let mut order: Vec<_> = self.numbered_plots().enumerate().collect();
+        order.sort_by_key(|(_, (_, plot))| plot.z());
+        for (i, (num, plot)) in order {
+            let row = self.plot_row(i);
+            let plot_area = row.map_or(area, |r| layout.rows[r]);
+            let (x_id, y_id) = plot.axes();
+            let scales = Scales {
+                x: match x_id {
+                    Some(id) => {
+                        bound_axis(&self.axes, id, |a| a.bound_x_scale())
+                    }
+                    None => self
+                        .row_axes(None)
+                        .chain(
+                            row.into_iter()
+                                .flat_map(|r| self.row_axes(Some(r))),
+                        )
+                        .find_map(|axis| axis.x_scale()),
+                },
+                y: match y_id {
+                    Some(id) => {
+                        bound_axis(&self.axes, id, |a| a.bound_y_scale())
+                    }
+                    None => self.row_axes(row).find_map(|axis| axis.y_scale()),
+                },
+                prefix: prefix.clone(),
+                precision: self.precision,
+            };
+            if let Some(id) = plot.stack() {
+                if stack.0 != Some(id) {
+                    stack = (Some(id), vec![]);
+                }
+            }
+            let out: &mut dyn Write = if plot.is_clipped() {
+                &mut *f
+            } else {
+                &mut outside
+            };
+            write!(out, "<g")?;
+            match row {
+                Some(r) if plot.is_clipped() => {
+                    write!(out, " clip-path='url(#{prefix}clip-row-{r})'")?;
+                }
+                _ => (),
+            }
+            if !plot.name().is_empty() {
+                write!(out, " aria-label='{}'", Escape(plot.name()))?;
+            }
+            writeln!(out, ">")?;
+            plot.display(out, num, plot_area, &scales, &mut stack.1)?;
+            plot.display_labels(out, num, plot_area, &scales, true)?;
+            writeln!(out, "</g>")?;
+            unclipped.push((plot, num, scales, plot_area));
+        }
+        if let Some(map) = &map {
+            for line in &self.ref_lines {
+                line.display(f, map)?;
+            }
+        }
+        let mapper = |pt: Pt<f32>, domain: &BBox<f32>| {
+            self.map_point_to(pt, domain, area)
+        };
+        for overlay in &self.overlays {
+            overlay(f, area, &mapper)?;
         }
         writeln!(f, "</g>")?;
+        if self.plot_border {
+            display_rect(f, "plot-border", area)?;
+        }
+        f.write_str(&outside)?;
+        for (plot, num, scales, plot_area) in unclipped {
+            plot.display_labels(f, num, plot_area, &scales, false)?;
+        }
+        if let Some(map) = &map {
+            for annotation in &self.annotations {
+                annotation.display(f, map)?;
+            }
+        }
+        if let Some(legend) = &self.legend {
+            let entries = self
+                .numbered_plots()
+                .filter(|(_, plot)| plot.in_legend())
+                .map(|(i, plot)| (i, plot, self.legend_note(plot)));
+            legend.display_inside(f, area, entries)?;
+        }
         writeln!(f, "</svg>")
     }
 
-    fn area(&self) -> BBox<f32> {
-        let mut area = inset(self.aspect_ratio.rect(), 40);
-        for title in &self.titles {
-            title.edge.split(&mut area, 100.0);
+    /// Get the mapping for annotations, from the first axis scales
+    fn annotation_map(&self, area: BBox<f32>) -> Option<Mapping> {
+        let x = self.axes.iter().find_map(|axis| axis.bound_x_scale())?;
+        let y = self.axes.iter().find_map(|axis| axis.bound_y_scale())?;
+        Some(Mapping::from_scales(x, y, area))
+    }
+
+    /// Get the plot area rectangle (in SVG units)
+    ///
+    /// The area depends on titles, captions and axes, so it is only stable
+    /// after they have all been added.
+    pub fn plot_area(&self) -> BBox<f32> {
+        self.plot_layout().area
+    }
+
+    /// Map a data point to the plot area (in SVG units)
+    ///
+    /// The mapping is the same as for a plot with `domain`, which is not
+    /// bound to specific axes.  Like [Chart::plot_area], it is only stable
+    /// after all titles, captions and axes have been added.
+    pub fn map_point(&self, pt: Pt<f32>, domain: &BBox<f32>) -> Pt<f32> {
+        self.map_point_to(pt, domain, self.plot_area())
+    }
+
+    /// Map a data point to an area
+    fn map_point_to(
+        &self,
+        pt: Pt<f32>,
+        domain: &BBox<f32>,
+        area: BBox<f32>,
+    ) -> Pt<f32> {
+        let scales = Scales {
+            x: self.row_axes(None).find_map(|axis| axis.x_scale()),
+            y: self.row_axes(None).find_map(|axis| axis.y_scale()),
+            ..Default::default()
+        };
+        let map = Mapping::new(domain, area, &scales);
+        Pt::new(map.x_pos(pt.x()), map.y_pos(pt.y()))
+    }
+
+    /// Get the layout below the titles
+    fn plot_layout(&self) -> Layout {
+        let mut area = inset(self.aspect_ratio.rect(), self.padding);
+        let captions = self.caption.iter().map(|c| &c.title);
+        for title in captions.chain(&self.titles) {
+            title.split(&mut area);
+        }
+        self.layout(area)
+    }
+
+    /// Lay out axes and rows within an area
+    fn layout(&self, mut area: BBox<f32>) -> Layout {
+        let mut axis_rects = vec![area; self.axes.len()];
+        for (i, axis) in self.axes.iter().enumerate() {
+            if self.axis_row(i).is_none() {
+                axis_rects[i] = axis.split(&mut area);
+            }
+        }
+        if self.rows.is_empty() {
+            return Layout {
+                area,
+                axis_rects,
+                rows: vec![],
+            };
+        }
+        let height = area.y_span() / self.rows.len() as f32;
+        let mut rows = vec![];
+        for (r, range) in self.rows.iter().enumerate() {
+            let y = area.y_min() + height * r as f32;
+            let mut row =
+                BBox::new([(area.x_min(), y), (area.x_max(), y + height)]);
+            for i in range.axes.clone() {
+                axis_rects[i] = self.axes[i].split(&mut row);
+            }
+            rows.push(row);
+        }
+        // Rows are aligned to the narrowest one, so points line up
+        let x_min = rows.iter().map(|r| r.x_min()).fold(area.x_min(), f32::max);
+        let x_max = rows.iter().map(|r| r.x_max()).fold(area.x_max(), f32::min);
+        for (range, row) in self.rows.iter().zip(&mut rows) {
+            for rect in &mut axis_rects[range.axes.clone()] {
+                let dx = if rect.x_max() <= row.x_min() {
+                    x_min - row.x_min()
+                } else if rect.x_min() >= row.x_max() {
+                    x_max - row.x_max()
+                } else {
+                    0.0
+                };
+                *rect = BBox::new([
+                    (rect.x_min() + dx, rect.y_min()),
+                    (rect.x_max() + dx, rect.y_max()),
+                ]);
+            }
+            *row = BBox::new([(x_min, row.y_min()), (x_max, row.y_max())]);
         }
-        for axis in &self.axes {
-            axis.split(&mut area);
+        Layout {
+            area: BBox::new([(x_min, area.y_min()), (x_max, area.y_max())]),
+            axis_rects,
+            rows,
         }
-        area
+    }
+
+    /// Get the legend note for a plot
+    fn legend_note<'s>(&'s self, plot: &'s dyn Series) -> Option<&'s str> {
+        let id = match (self.legend_axes, plot.axes()) {
+            (true, (_, Some(id))) | (true, (Some(id), None)) => id,
+            _ => return None,
+        };
+        let axis = self.axes.iter().find(|axis| axis.id() == Some(id));
+        Some(axis.and_then(|axis| axis.name()).unwrap_or(id))
     }
 
     /// Render the legend as an HTML fragment
     pub(crate) fn legend(&self, f: &mut dyn Write) -> fmt::Result {
-        writeln!(f, "<div class='legend'>")?;
-        for (i, plot) in self.plots.iter().enumerate() {
-            writeln!(f, "<div>")?;
-            writeln!(f, "<svg width='20' height='10' viewBox='0 0 60 30'>")?;
-            write!(f, "<path class='plot-{} legend-line'", i)?;
-            writeln!(f, " d='M0 15h30h30'/>")?;
-            writeln!(f, "</svg>")?;
-            writeln!(f, "{}", plot.1.name())?;
-            writeln!(f, "</div>")?;
+        match &self.legend {
+            Some(legend) if !legend.is_inside() => (),
+            _ => return Ok(()),
+        }
+        write!(f, "<div class='legend'")?;
+        self.display_scope(f)?;
+        writeln!(f, ">")?;
+        for (num, plot) in self.numbered_plots() {
+            if plot.in_legend() {
+                plot.display_legend(f, num, self.legend_note(plot))?;
+            }
         }
         writeln!(f, "</div>")
     }
 
+    /// Display theme style
+    fn theme(&self, f: &mut dyn Write) -> fmt::Result {
+        if let Some(theme) = &self.theme {
+            writeln!(f, "<style>")?;
+            theme.display(f)?;
+            writeln!(f, "</style>")?;
+        }
+        Ok(())
+    }
+
+    /// Display style for marker references with prefixed ids
+    ///
+    /// Plots beyond those styled by the stylesheet are also given colors and
+    /// markers.
+    pub(crate) fn marker_style(
+        &self,
+        f: &mut dyn Write,
+        stylesheet: &Stylesheet,
+    ) -> fmt::Result {
+        let count = self.series_count();
+        let styled = stylesheet.styled_series(count);
+        let extra = styled..count;
+        if self.id.is_none() && extra.is_empty() {
+            return Ok(());
+        }
+        let scope = match &self.id {
+            Some(id) => format!("[data-chart='{id}'] "),
+            None => String::new(),
+        };
+        let prefix = self.prefix();
+        writeln!(f, "<style>")?;
+        if self.id.is_some() {
+            for i in 0..styled {
+                write!(f, "{scope}.plot-{i} {{")?;
+                writeln!(f, " --marker: url(#{prefix}marker-{i}); }}")?;
+            }
+        }
+        for i in extra {
+            write!(f, "{scope}.plot-{i} {{ --color: {};", generated_color(i))?;
+            writeln!(f, " --marker: url(#{prefix}marker-{i}); }}")?;
+        }
+        writeln!(f, "</style>")
+    }
+
+    /// Estimate the size of rendered output
+    fn size_estimate(&self) -> usize {
+        let css = match &self.stylesheet {
+            Stylesheet::Generated(_) => 8192 + self.series_count() * 64,
+            Stylesheet::Sparkline(_) => 512 + self.series_count() * 32,
+            Stylesheet::Embedded(css) => css.len(),
+            Stylesheet::Href(_) => 0,
+        };
+        let points: usize = self.plots.iter().map(|p| p.point_count()).sum();
+        css + 4096 + points * 5
+    }
+
     /// Render chart as HTML
-    pub fn render(mut self) -> String {
-        let mut html = String::new();
+    ///
+    /// The chart is not validated, so mistakes such as plots with empty
+    /// domains or bound to unknown axes are rendered on a best-effort basis,
+    /// without an error.  Use [try_render](Self::try_render) to check them.
+    pub fn render(&self) -> String {
+        let mut html = String::with_capacity(self.size_estimate());
+        self.render_to(&mut html).unwrap();
+        html
+    }
+
+    /// Render chart as HTML, checking for errors
+    ///
+    /// Unlike [render](Self::render), errors found by
+    /// [validate](Self::validate) are reported.
+    pub fn try_render(&self) -> Result<String, Error> {
+        self.validate()?;
+        let mut html = String::with_capacity(self.size_estimate());
+        self.render_to(&mut html)?;
+        Ok(html)
+    }
+
+    /// Check the chart for errors
+    ///
+    /// Plots with an empty domain or bound to an unknown axis are reported,
+    /// as well as axes and titles which need more space than the chart has.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.validate_layout()?;
+        for plot in &self.plots {
+            if let Some(domain) = plot.domain() {
+                // Comparisons are false for NaN bounds
+                let valid = domain.x_min() <= domain.x_max()
+                    && domain.y_min() <= domain.y_max();
+                if !valid {
+                    return Err(Error::EmptyDomain(plot.name().to_string()));
+                }
+            }
+            let (x_id, y_id) = plot.axes();
+            for id in x_id.into_iter().chain(y_id) {
+                if !self.axes.iter().any(|axis| axis.id() == Some(id)) {
+                    return Err(Error::UnknownAxis(id.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that axes and titles fit within the chart
+    fn validate_layout(&self) -> Result<(), Error> {
+        let rect = inset(self.aspect_ratio.rect(), self.padding);
+        let captions = self.caption.iter().map(|c| &c.title);
+        let titles = captions
+            .chain(&self.titles)
+            .map(|title| demand(|area| title.split(area)));
+        let axes = self.axes.iter().enumerate().filter_map(|(i, axis)| {
+            let shared = self.axis_row(i).is_none();
+            shared.then(|| demand(|area| axis.split(area)))
+        });
+        let (mut width, height) = titles
+            .chain(axes)
+            .fold((0.0, 0.0), |(w, h), (dw, dh)| (w + dw, h + dh));
+        // Row axes are beside each other, so the widest row is needed
+        let row_width = self.rows.iter().map(|row| {
+            self.axes[row.axes.clone()]
+                .iter()
+                .map(|axis| demand(|area| axis.split(area)).0)
+                .sum::<f32>()
+        });
+        width += row_width.fold(0.0, f32::max);
+        for (needed, available) in
+            [(width, rect.x_span()), (height, rect.y_span())]
+        {
+            if needed > available {
+                return Err(Error::LayoutOverflow {
+                    needed: needed.ceil() as u32,
+                    available: available as u32,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check plots for likely mistakes, which don't prevent rendering
+    ///
+    /// Line and area plots with unsorted `X` values are reported, since they
+    /// zig-zag back and forth.  Use [Plot::sorted_by_x] to sort them.
+    pub fn warnings(&self) -> Vec<Error> {
+        self.plots
+            .iter()
+            .filter(|plot| plot.is_unsorted())
+            .map(|plot| Error::Unsorted(plot.name().to_string()))
+            .collect()
+    }
 
-        html.push_str("<html>");
-        html.push_str("<head>");
-        html.push_str("<meta charset='UTF-8'>");
-        html.push_str("<link href='./css/splotch.css' rel='stylesheet'/>");
-        html.push_str("</head>");
-        html.push_str("<body>");
-        html.push_str("<div class='page'>");
+    /// Render chart as HTML to a formatted writer
+    pub fn render_to<W: Write>(&self, mut f: W) -> fmt::Result {
+        write!(f, "<html>")?;
+        write!(f, "<head>")?;
+        write!(f, "<meta charset='UTF-8'>")?;
+        self.stylesheet.display(&mut f, self.series_count())?;
+        self.theme(&mut f)?;
+        self.marker_style(&mut f, &self.stylesheet)?;
+        write!(f, "</head>")?;
+        write!(f, "<body>")?;
+        write!(f, "<div class='page'>")?;
 
         // Display chart
-        html.push_str("<div class='chart'>");
-        self.svg(&mut html, true).unwrap();
-        self.defs(&mut html).unwrap();
-        self.body(&mut html).unwrap();
-        self.legend(&mut html).unwrap();
-        html.push_str("</div>");
+        let class = self.legend.map_or("chart", |l| l.chart_class());
+        write!(f, "<div class='{class}'>")?;
+        self.svg(&mut f, None)?;
+        self.defs(&mut f)?;
+        self.body(&mut f)?;
+        self.legend(&mut f)?;
+        write!(f, "</div>")?;
+        if self.embedded_data {
+            write!(f, "<script type='application/json' class='splotch-data'>")?;
+            self.display_data(&mut f)?;
+            write!(f, "</script>")?;
+        }
 
-        html.push_str("</div>");
-        html.push_str("</body>");
+        write!(f, "</div>")?;
+        write!(f, "</body>")
+    }
 
-        html
+    /// Write chart as HTML to a byte stream, such as a file
+    ///
+    /// The output is not buffered, so wrapping the stream in a
+    /// `BufWriter` is recommended.
+    pub fn write_to<W: io::Write>(&self, w: W) -> io::Result<()> {
+        IoWriter::render(w, |f| self.render_to(f))?;
+        Ok(())
+    }
+
+    /// Save chart as an HTML file
+    ///
+    /// The parent directory must already exist; see
+    /// [save_html_all](Self::save_html_all) to create it.
+    pub fn save_html<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = create_file(path.as_ref(), false)?;
+        IoWriter::render(file, |f| self.render_to(f))?.flush()
+    }
+
+    /// Save chart as an HTML file, creating missing parent directories
+    pub fn save_html_all<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = create_file(path.as_ref(), true)?;
+        IoWriter::render(file, |f| self.render_to(f))?.flush()
+    }
+
+    /// Save chart as a standalone SVG file
+    ///
+    /// The parent directory must already exist; see
+    /// [save_svg_all](Self::save_svg_all) to create it.
+    pub fn save_svg<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = create_file(path.as_ref(), false)?;
+        IoWriter::render(file, |f| self.render_svg_to(f))?.flush()
+    }
+
+    /// Save chart as a standalone SVG file, creating missing parent
+    /// directories
+    pub fn save_svg_all<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = create_file(path.as_ref(), true)?;
+        IoWriter::render(file, |f| self.render_svg_to(f))?.flush()
+    }
+
+    /// Render chart nested within a page cell
+    pub(crate) fn render_cell(
+        &self,
+        f: &mut dyn Write,
+        cell: BBox<f32>,
+    ) -> fmt::Result {
+        self.svg(f, Some(cell))?;
+        self.metadata(f)?;
+        self.defs(f)?;
+        self.body(f)
+    }
+
+    /// Display embedded data as SVG metadata
+    fn metadata(&self, f: &mut dyn Write) -> fmt::Result {
+        if self.embedded_data {
+            write!(f, "<metadata class='splotch-data'>")?;
+            self.display_data(f)?;
+            writeln!(f, "</metadata>")?;
+        }
+        Ok(())
+    }
+
+    /// Display embedded data as JSON
+    fn display_data(&self, f: &mut dyn Write) -> fmt::Result {
+        let series = self
+            .plots
+            .iter()
+            .filter_map(|plot| Some((plot.name(), plot.points()?)));
+        json::write_data(f, series)
+    }
+
+    /// Render chart as a standalone SVG
+    ///
+    /// The legend is only included when it's inside the plot area, since
+    /// other legends are rendered as HTML.
+    pub fn render_svg(&self) -> String {
+        let mut svg = String::with_capacity(self.size_estimate());
+        self.render_svg_to(&mut svg).unwrap();
+        svg
+    }
+
+    /// Render chart as a standalone SVG to a formatted writer
+    pub fn render_svg_to<W: Write>(&self, mut f: W) -> fmt::Result {
+        self.svg(&mut f, None)?;
+        if let Some(css) = self.stylesheet.css(self.series_count()) {
+            writeln!(f, "<style>\n{css}</style>")?;
+        }
+        self.theme(&mut f)?;
+        self.marker_style(&mut f, &self.stylesheet)?;
+        self.metadata(&mut f)?;
+        self.defs(&mut f)?;
+        self.body(&mut f)
     }
 }
 
+/// Create a buffered file, optionally with any missing parent directories
+fn create_file(path: &Path, dirs: bool) -> io::Result<io::BufWriter<fs::File>> {
+    if let Some(parent) = path.parent().filter(|_| dirs) {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(io::BufWriter::new(fs::File::create(path)?))
+}
+
+/// Find the scale of a bound axis by id
+fn bound_axis<'a>(
+    axes: &[Box<dyn Axis + 'a>],
+    id: &str,
+    scale: fn(&dyn Axis) -> Option<Numeric>,
+) -> Option<Numeric> {
+    axes.iter()
+        .filter(|axis| axis.id() == Some(id))
+        .find_map(|axis| scale(axis.as_ref()))
+}
+
+/// Get the width and height split from a large area
+fn demand<F>(split: F) -> (f32, f32)
+where
+    F: FnOnce(&mut BBox<f32>) -> BBox<f32>,
+{
+    let before = BBox::new([(0.0, 0.0), (1e6, 1e6)]);
+    let mut area = before;
+    split(&mut area);
+    (
+        before.x_span() - area.x_span(),
+        before.y_span() - area.y_span(),
+    )
+}
+
 /// Inset bounding box
+///
+/// The inset is limited to half of the box size, so it's never inverted.
 fn inset(bbox: BBox<f32>, value: u16) -> BBox<f32> {
-    // unwrap: Always 2
-    let mut iter = bbox.into_iter();
-    let mut min = iter.next().unwrap();
-    let mut max = iter.next().unwrap();
+    let value = f32::from(value);
+    let x = value.min(bbox.x_span() / 2.0);
+    let y = value.min(bbox.y_span() / 2.0);
+    BBox::new([
+        (bbox.x_min() + x, bbox.y_min() + y),
+        (bbox.x_max() - x, bbox.y_max() - y),
+    ])
+}
+
+/// Display a clip path for a rectangle
+fn display_clip(f: &mut dyn Write, id: &str, rect: BBox<f32>) -> fmt::Result {
+    writeln!(f, "<clipPath id='{id}'>")?;
+    write!(f, "<rect x='{}' y='{}'", rect.x_min(), rect.y_min())?;
+    writeln!(f, " width='{}' height='{}'/>", rect.x_span(), rect.y_span())?;
+    writeln!(f, "</clipPath>")
+}
+
+/// Display a rectangle covering the plot area
+fn display_rect(
+    f: &mut dyn Write,
+    class: &str,
+    area: BBox<f32>,
+) -> fmt::Result {
+    write!(
+        f,
+        "<rect class='{class}' x='{}' y='{}'",
+        area.x_min(),
+        area.y_min()
+    )?;
+    writeln!(f, " width='{}' height='{}'/>", area.x_span(), area.y_span())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        axis::{Horizontal, Vertical},
+        row::Row,
+        text::Coord,
+    };
+
+    #[test]
+    fn render_twice() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0), (9.0, 3.0)];
+        let chart = Chart::default()
+            .with_title("Twice")
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain))
+            .with_line_plot(Plot::new("Series", &domain, data));
+        let html = chart.render();
+        assert_eq!(html, chart.render());
+        let svg = chart.render_svg();
+        assert_eq!(svg, chart.render_svg());
+        assert!(svg.starts_with("<svg xmlns='http://www.w3.org/2000/svg'"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        // The standalone SVG also embeds the stylesheet
+        assert!(svg.contains(".plot-0 {"));
+    }
+
+    #[test]
+    fn stylesheet() {
+        let html = Chart::default().render();
+        assert!(html.contains("<style>"));
+        assert!(html.contains(".plot-0 {"));
+        let html = Chart::default().with_css(".custom {}").render();
+        assert!(html.contains("<style>\n.custom {}</style>"));
+        assert!(!html.contains(".plot-0 {"));
+        let html = Chart::default()
+            .with_stylesheet_href("./css/splotch.css")
+            .render();
+        assert!(html.contains("<link href='./css/splotch.css'"));
+        assert!(!html.contains("<style>"));
+        // The shipped stylesheet colors the first series when linked or
+        // embedded, and its classes are replaced when generated
+        let css = include_str!("../css/splotch.css");
+        assert!(css.contains(".plot-0 {\n  --color: #4E79A7;"));
+        assert!(css.contains("  --marker: url(#marker-9);\n}"));
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let html = Chart::default()
+            .with_css(css)
+            .with_line_plot(Plot::new("A", &domain, [(1.0, 2.0)]))
+            .render();
+        assert!(html.contains("class='plot-0 plot-line'"));
+        assert!(html.contains(".plot-0 {\n  --color: #4E79A7;"));
+        assert_eq!(Chart::default().render().matches(".plot-0 {").count(), 1);
+    }
+
+    #[test]
+    fn legend() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0), (9.0, 3.0)];
+        let chart = || {
+            Chart::default()
+                .with_line_plot(Plot::new("Series", &domain, data))
+                .with_axis(Horizontal::new(domain))
+                .with_axis(Vertical::new(domain))
+        };
+        let html = chart().render();
+        assert!(html.contains("<div class='chart'>"));
+        assert!(html.contains("<div class='legend'>"));
+        let html = chart().with_legend(Legend::new().on_bottom()).render();
+        assert!(html.contains("<div class='chart legend-bottom'>"));
+        let inside = chart().with_legend(Legend::new().inside_top_right());
+        let html = inside.render();
+        assert!(!html.contains("<div class='legend'>"));
+        assert!(html.contains("<g class='legend-inside'>"));
+        assert!(inside.render_svg().contains("class='legend-text'"));
+        assert_eq!(inside.plot_area(), chart().plot_area());
+        let html = chart().without_legend().render();
+        assert!(!html.contains("<div class='legend'>"));
+        let html = chart()
+            .with_line_plot(
+                Plot::new("Trend", &domain, data).hide_from_legend(),
+            )
+            .with_line_plot(Plot::new("Other", &domain, data))
+            .render();
+        let legend = html.split("<div class='legend'>").nth(1).unwrap();
+        assert!(!legend.contains("Trend"));
+        assert!(html.contains("plot-1 plot-line"));
+        assert!(html.contains("<path class='plot-2 legend-line'"));
+    }
+
+    #[test]
+    fn escape_text() {
+        let hostile = "A<B>&C'\"";
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0), (9.0, 3.0)];
+        let chart = || {
+            Chart::default()
+                .with_title(hostile)
+                .with_axis(
+                    Horizontal::new(domain)
+                        .with_name(hostile)
+                        .with_labeled_ticks([(5.0, hostile)]),
+                )
+                .with_axis(Vertical::categories([hostile, "b"]))
+                .with_line_plot(Plot::new(hostile, &domain, data))
+        };
+        let html = chart().render();
+        assert!(!html.contains(hostile));
+        assert!(html.contains("A&lt;B&gt;&amp;C&apos;&quot;"));
+        let svg = chart()
+            .with_legend(Legend::new().inside_top_left())
+            .render_svg();
+        let doc = roxmltree::Document::parse(&svg).unwrap();
+        let texts = doc
+            .descendants()
+            .filter(|node| node.is_text())
+            .filter_map(|node| node.text())
+            .filter(|text| text.trim() == hostile)
+            .count();
+        // Title, title element, axis name, tick, category and legend
+        assert_eq!(texts, 6);
+    }
+
+    #[test]
+    fn accessibility() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0)];
+        let chart = Chart::default()
+            .with_title("Sales")
+            .with_description("Sales & returns by month")
+            .with_line_plot(Plot::new("Returns", &domain, data));
+        for svg in [chart.render(), chart.render_svg()] {
+            assert!(svg.contains(
+                " role='img' aria-labelledby='chart-title chart-desc'"
+            ));
+            assert!(svg.contains("<title id='chart-title'>Sales</title>"));
+            assert!(svg.contains(
+                "<desc id='chart-desc'>Sales &amp; returns by month</desc>"
+            ));
+            assert!(svg.contains("<g aria-label='Returns'>"));
+        }
+        let svg = Chart::default().render_svg();
+        assert!(svg.contains(" role='img' viewBox="));
+        assert!(!svg.contains("<title"));
+    }
+
+    #[test]
+    fn unique_ids() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0)];
+        let chart = |id| {
+            Chart::default()
+                .with_id(id)
+                .with_title("Usage")
+                .with_scatter_plot(Plot::new("Series", &domain, data))
+                .render()
+        };
+        let ids = |html: &str| -> Vec<String> {
+            html.split(" id='")
+                .skip(1)
+                .map(|s| s.split('\'').next().unwrap().to_string())
+                .collect()
+        };
+        let (cpu, mem) = (chart("cpu"), chart("mem"));
+        let (cpu_ids, mem_ids) = (ids(&cpu), ids(&mem));
+        assert_eq!(
+            cpu_ids,
+            ["cpu-chart-title", "cpu-marker-0", "cpu-clip-chart"]
+        );
+        assert!(cpu_ids.iter().all(|id| !mem_ids.contains(id)));
+        assert!(cpu.contains("clip-path='url(#cpu-clip-chart)'"));
+        assert!(cpu.contains("marker-mid='url(#cpu-marker-0)'"));
+        assert!(cpu.contains("--marker: url(#cpu-marker-0);"));
+        // Only the default stylesheet refers to unprefixed ids
+        let body = &cpu[cpu.find("<defs>").unwrap()..];
+        assert!(!body.contains("url(#marker-"));
+        assert!(!body.contains("url(#clip-chart)"));
+    }
+
+    #[test]
+    fn custom_size() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0)];
+        let chart = Chart::default()
+            .with_aspect_ratio(AspectRatio::Custom {
+                width: 120.0,
+                height: 60.0,
+            })
+            .with_pixel_size(240, 120)
+            .with_title("Tiny")
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain))
+            .with_line_plot(Plot::new("Series", &domain, data));
+        let svg = chart.render_svg();
+        assert!(svg.contains(" width='240' height='120' role='img'"));
+        assert!(svg.contains(" viewBox='0 0 120 60'>"));
+        let area = chart.plot_area();
+        assert!(area.x_min() >= 0.0 && area.x_max() <= 120.0);
+        assert!(area.y_min() >= 0.0 && area.y_max() <= 60.0);
+        assert!(!svg.contains("NaN") && !svg.contains("height='-"));
+    }
+
+    #[test]
+    fn padding() {
+        let chart = Chart::default()
+            .with_padding(10)
+            .with_title(Title::new("Short").with_height(50.0));
+        let area = chart.plot_area();
+        assert_eq!((area.x_min(), area.y_min()), (10.0, 60.0));
+        assert_eq!((area.x_max(), area.y_max()), (1990.0, 1490.0));
+        let area = Chart::default().with_padding(5000).plot_area();
+        assert_eq!((area.x_min(), area.x_max()), (1000.0, 1000.0));
+        assert_eq!((area.y_min(), area.y_max()), (750.0, 750.0));
+    }
+
+    #[test]
+    fn map_point() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let chart = Chart::default()
+            .with_padding(0)
+            .with_axis(Horizontal::new(domain).with_range(0.0, 20.0));
+        let area = chart.plot_area();
+        let pt = chart.map_point(Pt::new(5.0, 5.0), &domain);
+        assert_eq!(pt.x(), area.x_min() + area.x_span() * 0.25);
+        assert_eq!(pt.y(), area.y_min() + area.y_span() * 0.5);
+    }
+
+    #[test]
+    fn auto_domain() {
+        let a = [(0.0, 0.0), (10.0, 5.0)];
+        let b = [(5.0, -5.0), (20.0, 10.0)];
+        let auto = Chart::default()
+            .with_axis(Horizontal::auto())
+            .with_axis(Vertical::auto())
+            .with_line_plot(Plot::new_auto("A", a))
+            .with_scatter_plot(Plot::new_auto("B", b))
+            .with_domain_padding(0.1);
+        let domain = BBox::new([(-2.0, -6.5), (22.0, 11.5)]);
+        assert_eq!(auto.plots[0].domain(), Some(&domain));
+        assert_eq!(auto.plots[1].domain(), Some(&domain));
+        let fixed = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain))
+            .with_line_plot(Plot::new("A", &domain, a))
+            .with_scatter_plot(Plot::new("B", &domain, b));
+        assert_eq!(auto.render(), fixed.render());
+        // Axes added after plots still get the automatic domain
+        let late = Chart::default()
+            .with_domain_padding(0.1)
+            .with_line_plot(Plot::new_auto("A", a))
+            .with_scatter_plot(Plot::new_auto("B", b))
+            .with_axis(Horizontal::auto())
+            .with_axis(Vertical::auto());
+        assert_eq!(late.render(), fixed.render());
+        // Plots without finite points are left out of the domain
+        let c = [(10.0, 10.0), (20.0, 20.0)];
+        let nan = Chart::default()
+            .with_line_plot(Plot::new_auto("C", c))
+            .with_line_plot(Plot::new_auto("NaN", [(f32::NAN, 1.0)]))
+            .with_line_plot(Plot::new_auto("Empty", Vec::<(f32, f32)>::new()));
+        let domain = BBox::new(c);
+        assert_eq!(nan.plots[0].domain(), Some(&domain));
+        assert_eq!(nan.plots[1].domain(), Some(&domain));
+        let other = BBox::new([(0.0, 0.0), (1.0, 1.0)]);
+        let chart = Chart::default()
+            .with_line_plot(Plot::new("C", &other, [(0.5, 0.5)]))
+            .with_line_plot(Plot::new_auto("D", b));
+        assert_eq!(chart.plots[0].domain(), Some(&other));
+    }
+
+    #[test]
+    fn many_plots() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let chart = (0..12).fold(Chart::default(), |chart, _| {
+            chart.with_line_plot(Plot::new("Series", &domain, [(1.0, 2.0)]))
+        });
+        let html = chart.render();
+        let svg = html.split_once("<svg").unwrap().1;
+        let legend = html.split_once("<div class='legend'>").unwrap().1;
+        assert!(html.contains(".plot-11 {\n  --color: hsl("));
+        assert!(!html.contains(".plot-11 { --color: hsl("));
+        assert!(svg.contains("<marker id='marker-11' class='plot-11'"));
+        assert!(svg.contains("<path class='plot-11 plot-line'"));
+        assert!(legend.contains("<path class='plot-11 legend-line'"));
+        assert_eq!(svg.matches("class='plot-0 plot-line'").count(), 1);
+        // Custom stylesheets only style the first plots
+        let html = chart.with_css(".custom {}").render();
+        assert!(html.contains(".plot-11 { --color: hsl("));
+        assert!(!html.contains(".plot-9 { --color: hsl("));
+    }
+
+    #[test]
+    fn links() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let plot = Plot::new("Series", &domain, [(1.0, 2.0)])
+            .with_legend_href("/series?id=1&x='2'");
+        let chart = Chart::default()
+            .with_title(
+                Title::new("Sales").with_href(Link::new("/sales").in_new_tab()),
+            )
+            .with_axis(
+                Horizontal::new(domain).with_name("X").with_name_href("/x"),
+            )
+            .with_line_plot(plot);
+        let html = chart.render();
+        assert!(html.contains(
+            "<a href='/sales' target='_blank' rel='noopener'><text \
+            class='title'"
+        ));
+        assert!(html.contains("<a href='/x'><text class='axis'"));
+        assert!(html.contains(
+            "<a href='/series?id=1&amp;x=&apos;2&apos;'>Series</a>\n"
+        ));
+        let svg = chart.with_legend(Legend::new().inside_top_left());
+        let svg = svg.render_svg();
+        let doc = roxmltree::Document::parse(&svg).unwrap();
+        let links: Vec<_> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("a"))
+            .map(|n| n.attribute("href").unwrap())
+            .collect();
+        assert_eq!(links, ["/sales", "/x", "/series?id=1&x='2'"]);
+    }
+
+    #[test]
+    fn embedded_data() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (3.0, f32::NAN)];
+        let chart = Chart::default()
+            .with_line_plot(Plot::new("A</script>&B", &domain, data))
+            .with_embedded_data();
+        let expected = serde_json::json!({"A</script>&B": [[1, 2], [3, null]]});
+        let html = chart.render();
+        let start = "<script type='application/json' class='splotch-data'>";
+        let json = html.split_once(start).unwrap().1;
+        let json = json.split_once("</script>").unwrap().0;
+        let json: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(json, expected);
+        let svg = chart.render_svg();
+        let doc = roxmltree::Document::parse(&svg).unwrap();
+        let metadata = doc
+            .descendants()
+            .find(|n| n.has_tag_name("metadata"))
+            .unwrap();
+        let json = metadata.text().unwrap();
+        let json: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(json, expected);
+        assert!(!Chart::default().render().contains("splotch-data"));
+    }
+
+    #[test]
+    fn z_order() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (3.0, 4.0)];
+        let html = Chart::default()
+            .with_line_plot(Plot::new("Top", &domain, data).with_z(1))
+            .with_area_plot(Plot::new("Fill", &domain, data).with_z(-1))
+            .with_line_plot(Plot::new("Middle", &domain, data))
+            .render();
+        let pos = |s| html.find(s).unwrap();
+        assert!(pos("aria-label='Fill'") < pos("aria-label='Middle'"));
+        assert!(pos("aria-label='Middle'") < pos("aria-label='Top'"));
+        assert!(pos("'plot-1 plot-area'") < pos("'plot-0 plot-line'"));
+        // Legend keeps insertion order
+        assert!(pos("</svg>\nTop\n") < pos("</svg>\nFill\n"));
+    }
+
+    #[test]
+    fn overlay() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let chart = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_line_plot(Plot::new("Series", &domain, [(1.0, 2.0)]))
+            .with_overlay(|f, area, map| {
+                let pt = map(Pt::new(10.0, 10.0), &domain);
+                assert_eq!(pt.x(), area.x_max());
+                write!(f, "<circle class='logo' cx='{}' r='5'/>", pt.x())
+            });
+        let svg = chart.render_svg();
+        let overlay = svg.find("<circle class='logo'").unwrap();
+        assert!(svg.find("plot-line").unwrap() < overlay);
+        assert!(svg[overlay..].starts_with(&format!(
+            "<circle class='logo' cx='{}' r='5'/></g>",
+            chart.plot_area().x_max()
+        )));
+    }
+
+    #[test]
+    fn plot_border() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let chart = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain))
+            .with_line_plot(Plot::new("", &domain, [(1.0, 2.0), (5.0, 8.0)]))
+            .with_plot_border()
+            .with_plot_background();
+        let area = chart.plot_area();
+        let rect = format!(
+            "x='{}' y='{}' width='{}' height='{}'/>",
+            area.x_min(),
+            area.y_min(),
+            area.x_span(),
+            area.y_span()
+        );
+        let svg = chart.render_svg();
+        let background = svg.find("<rect class='plot-background'").unwrap();
+        let border = svg.find("<rect class='plot-border'").unwrap();
+        assert!(svg[background..]
+            .starts_with(&format!("<rect class='plot-background' {rect}")));
+        assert!(svg[border..]
+            .starts_with(&format!("<rect class='plot-border' {rect}")));
+        // Background is beneath the grid, border above the plots
+        assert!(background < svg.find("class='grid-").unwrap());
+        assert!(border > svg.find(" plot-line'").unwrap());
+        assert!(!Chart::default()
+            .render_svg()
+            .contains("<rect class='plot-border"));
+    }
+
+    #[test]
+    fn band_fill() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let svg = |vertical: Vertical| {
+            let chart = Chart::default()
+                .with_axis(Horizontal::new(domain))
+                .with_axis(vertical)
+                .with_band_fill(Orientation::Horizontal);
+            let area = chart.plot_area();
+            let svg = chart.render_svg();
+            let bands = svg.split_once("<g class='band-y'>\n").unwrap().1;
+            let bands = bands.split_once("</g>").unwrap().0.to_string();
+            (area, bands)
+        };
+        let (area, bands) =
+            svg(Vertical::new(domain).with_ticks(&[0.0, 2.0, 4.0, 6.0, 10.0]));
+        let rects: Vec<(f32, f32)> = bands
+            .lines()
+            .map(|r| {
+                assert!(r.contains(&format!(" width='{}'", area.x_span())));
+                let attr = |name: &str| {
+                    let v = r.split_once(&format!("{name}='")).unwrap().1;
+                    v.split_once('\'').unwrap().0.parse::<f32>().unwrap()
+                };
+                (attr(" y"), attr("height"))
+            })
+            .collect();
+        // Bands between 10-6 and 4-2, within the plot area
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects[0].0, area.y_min());
+        assert_eq!(rects[0].1, area.y_span() * 0.4);
+        assert!(rects[1].0 + rects[1].1 < area.y_max());
+        let (_, bands) = svg(Vertical::new(domain).with_tick_count(11));
+        assert_eq!(bands.lines().count(), 5);
+        let svg = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_band_fill(Orientation::Horizontal)
+            .render_svg();
+        assert!(!svg.contains("<g class='band-"));
+    }
+
+    #[test]
+    fn rows() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let wide = BBox::new([(0.0, 0.0), (10.0, 100000.0)]);
+        let data = [(0.0, 0.0), (10.0, 10.0)];
+        let chart = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_row(
+                Row::new()
+                    .with_axis(Vertical::new(domain))
+                    .with_line_plot(Plot::new("A", &domain, data)),
+            )
+            .with_row(
+                Row::new()
+                    .with_axis(Vertical::new(wide))
+                    .with_bar_plot(Plot::new("B", &domain, data)),
+            );
+        let layout = chart.plot_layout();
+        let (top, bottom) = (layout.rows[0], layout.rows[1]);
+        // Rows are aligned to the wider tick labels
+        assert_eq!(top.x_min(), bottom.x_min());
+        assert_eq!(top.x_max(), bottom.x_max());
+        assert_eq!(top.y_max(), bottom.y_min());
+        assert_eq!(top.y_span(), bottom.y_span());
+        assert_eq!(layout.axis_rects[1].x_max(), top.x_min());
+        assert_eq!(layout.axis_rects[2].x_max(), bottom.x_min());
+        let area = layout.area;
+        assert_eq!((area.y_min(), area.y_max()), (top.y_min(), bottom.y_max()));
+        let svg = chart.render_svg();
+        let clip = "<g clip-path='url(#clip-row-1)' aria-label='B'>";
+        assert!(svg.contains(clip));
+        // Line from the top-left to the bottom-right of the top row
+        let line = format!("d='M{} {}l", top.x_min(), top.y_max());
+        assert!(svg.contains(&line));
+        // Vertical grid lines run through all rows
+        let grid = format!("v{}", area.y_span());
+        assert!(svg.contains(&grid));
+    }
 
-    min = min + Pt::from(f32::from(value));
-    max = max - Pt::from(f32::from(value));
+    #[test]
+    fn title_lines() {
+        let title = Title::new("First line\nSecond");
+        assert_eq!(title.lines(), ["First line", "Second"]);
+        let title = Title::new("A long title which wraps\nnicely").wrap_at(12);
+        assert_eq!(title.lines(), ["A long title", "which wraps", "nicely"]);
+        assert_eq!(Title::new("").lines(), [""]);
+        let svg = Chart::default().with_title(title).render_svg();
+        assert!(svg.contains(
+            "<tspan x='0' dy='-1.2em'>A long title</tspan>\n\
+            <tspan x='0' dy='1.2em'>which wraps</tspan>\n"
+        ));
+        // Each extra line reserves more height for the title
+        let chart = Chart::default()
+            .with_title(Title::new("A long title which wraps").wrap_at(12));
+        assert_eq!(chart.plot_area().y_min(), 40.0 + 100.0 + 60.0);
+    }
 
-    BBox::from([min, max])
+    #[test]
+    fn subtitle() {
+        let chart = Chart::default()
+            .with_title(Title::new("Main").at_start())
+            .with_title(Title::subtitle("Sub"));
+        assert_eq!(chart.plot_area().y_min(), 40.0 + 100.0 + 60.0);
+        let svg = chart.render_svg();
+        assert!(svg.contains(
+            "<text class='subtitle' transform='translate(40 170)' \
+            text-anchor='start'>\nSub\n"
+        ));
+        let svg = Chart::default()
+            .with_title(Title::new("Main").on_bottom())
+            .with_title(Title::subtitle("Sub").at_end())
+            .render_svg();
+        assert!(svg.contains(
+            "<text class='subtitle' transform='translate(1960 1330)' \
+            text-anchor='end'>"
+        ));
+    }
+
+    #[test]
+    fn caption() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let svg = Chart::default()
+            .with_caption("Source: tests")
+            .with_title(Title::new("Bottom").on_bottom())
+            .with_axis(Horizontal::new(domain))
+            .render_svg();
+        // Caption is outermost, below the bottom title and axis
+        assert!(svg.contains(
+            "<text class='caption' transform='translate(40 1435)' \
+            text-anchor='start'>\nSource: tests\n"
+        ));
+        assert!(svg.contains("class='title' transform='translate(1000 1360)'"));
+        let svg = Chart::default()
+            .with_caption(Caption::new("Source").at_end().with_height(20.0))
+            .render_svg();
+        assert!(svg.contains(
+            "transform='translate(1960 1450)' \
+            text-anchor='end'"
+        ));
+    }
+
+    #[test]
+    fn sparkline() {
+        let domain = BBox::new([(0.0, 0.0), (4.0, 10.0)]);
+        let data = [(0.0, 2.0), (1.0, 8.0), (2.0, 4.0), (4.0, 10.0)];
+        let svg = Chart::sparkline()
+            .with_pixel_size(120, 30)
+            .with_line_plot(Plot::new("", &domain, data).with_last_marker())
+            .render_svg();
+        assert!(svg.contains("width='120' height='30'"));
+        assert!(svg.contains("viewBox='0 0 400 100'"));
+        assert!(!svg.contains("class='axis"));
+        assert!(!svg.contains("grid-"));
+        assert!(svg.contains("d='M8 75l96 -50 96 33 192 -50'/>"));
+        assert!(svg.contains("marker-end='url(#marker-0)' d='M392 8' />"));
+        assert!(svg.len() < 2000);
+        // Every series is colored
+        let plot = |y| Plot::new("", &domain, [(0.0, y), (4.0, y)]);
+        let svg = (0..12)
+            .fold(Chart::sparkline(), |c, i| c.with_line_plot(plot(i as f32)))
+            .render_svg();
+        assert!(svg.contains(".plot-11 {\n  --color: hsl("));
+        assert!(!svg.contains(".plot-12 "));
+    }
+
+    #[test]
+    fn write_to_sink() {
+        let domain = BBox::new([(0.0, -1.0), (1_000_000.0, 1.0)]);
+        let data = (0..1_000_000).map(|i| {
+            let x = i as f32;
+            (x, (x / 1000.0).sin())
+        });
+        let chart = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain))
+            .with_line_plot(Plot::new("Sine", &domain, data));
+        chart.write_to(std::io::sink()).unwrap();
+        let mut bytes = vec![];
+        Chart::default().write_to(&mut bytes).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            Chart::default().render()
+        );
+    }
+
+    #[test]
+    fn try_render() {
+        let domain = BBox::new([(0.0, 0.0), (1.0, 1.0)]);
+        let chart = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_line_plot(Plot::new("A", &domain, [(0.0, 0.0)]));
+        assert_eq!(chart.try_render().unwrap(), chart.render());
+        let empty = BBox::default();
+        let chart = Chart::default().with_line_plot(Plot::new(
+            "B",
+            &empty,
+            [(0.0, 0.0)],
+        ));
+        assert_eq!(chart.try_render(), Err(Error::EmptyDomain("B".into())));
+        let plot = Plot::new("C", &domain, [(0.0, 0.0)]).with_y_axis("right");
+        let chart = Chart::default().with_line_plot(plot);
+        assert_eq!(chart.try_render(), Err(Error::UnknownAxis("right".into())));
+    }
+
+    #[test]
+    fn bound_axis() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(0.0, 0.0), (10.0, 50.0)];
+        let right = || {
+            Vertical::new(domain)
+                .on_right()
+                .with_range(0.0, 100.0)
+                .with_id("right")
+        };
+        let chart = |right: Vertical| {
+            Chart::default()
+                .with_axis(Horizontal::new(domain))
+                .with_axis(right)
+                .with_line_plot(
+                    Plot::new("A", &domain, data).with_y_axis("right"),
+                )
+                .with_legend(Legend::new().inside_top_right())
+                .with_legend_axes()
+        };
+        let chart_a = chart(right());
+        let out = chart_a.render();
+        // 50 is halfway up the axis range, not beyond the plot domain
+        let area = chart_a.plot_area();
+        let (x0, y0) = (area.x_min(), area.y_max());
+        let dy = ((area.y_min() + area.y_max()) / 2.0 - y0).round();
+        let d = format!("d='M{x0} {y0}l{} {dy}'", area.x_span());
+        assert!(out.contains(&d), "{d}");
+        assert!(out.contains("<tspan class='legend-note'>right</tspan>"));
+        let out = chart(right().with_name("Percent")).render();
+        assert!(out.contains("<tspan class='legend-note'>Percent</tspan>"));
+    }
+
+    #[test]
+    fn side_title() {
+        let domain = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let translate = |out: &str, class: &str| {
+            let start = format!("<text class='{class}' transform='translate(");
+            let (_, t) = out.split_once(&start).unwrap();
+            let (pos, t) = t.split_once(')').unwrap();
+            let (x, y) = pos.split_once(' ').unwrap();
+            let rotate = t.split_once('\'').unwrap().0.trim().to_string();
+            (x.parse::<f32>().unwrap(), y.parse::<f32>().unwrap(), rotate)
+        };
+        let chart = Chart::default()
+            .with_title(Title::new("Side").on_left())
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain).with_name("Y"));
+        let out = chart.render();
+        let (title_x, _, rotate) = translate(&out, "title");
+        assert_eq!(rotate, "rotate(-90)");
+        // Baseline is centered in the band, after 40 units of padding
+        assert_eq!(title_x, 90.0);
+        let (name_x, _, rotate) = translate(&out, "axis");
+        assert_eq!(rotate, "rotate(-90)");
+        // Axis name glyphs (40px) stay outside of the title band
+        assert!(name_x - 40.0 >= title_x + 50.0);
+        assert_eq!(chart.plot_area().x_min(), 140.0 + 160.0);
+        // Subtitles stack inward, beside the title
+        let out = Chart::default()
+            .with_title(Title::new("Side").on_right())
+            .with_title(Title::subtitle("Sub"))
+            .render();
+        let (title_x, _, rotate) = translate(&out, "title");
+        assert_eq!((title_x, &rotate[..]), (1910.0, "rotate(90)"));
+        let (sub_x, _, rotate) = translate(&out, "subtitle");
+        assert_eq!((sub_x, &rotate[..]), (1830.0, "rotate(90)"));
+    }
+
+    #[test]
+    fn layout_overflow() {
+        let domain = BBox::new([(0.0, 0.0), (1.0, 1.0)]);
+        let chart = |space: u16| {
+            Chart::default()
+                .with_aspect_ratio(AspectRatio::Portrait)
+                .with_title("One")
+                .with_title("Two")
+                .with_title("Three")
+                .with_axis(Horizontal::new(domain).with_name("A"))
+                .with_axis(Horizontal::new(domain).with_name("B").on_top())
+                .with_axis(Horizontal::new(domain).with_name("C").on_top())
+                .with_axis(
+                    Vertical::new(domain).with_name("D").with_space(space),
+                )
+        };
+        let fits = chart(300);
+        assert_eq!(fits.validate(), Ok(()));
+        let area = fits.plot_area();
+        assert!(area.x_span() > 0.0 && area.y_span() > 0.0);
+        let overflow = chart(1500);
+        let err = overflow.validate().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "layout overflow: axes+titles need 1500 units, only 1420 available"
+        );
+        let area = overflow.plot_area();
+        assert_eq!(area.x_span(), 0.0);
+        assert!(area.y_span() > 0.0);
+        assert!(overflow.try_render().is_err());
+        assert!(!overflow.render().contains("width='-"));
+    }
+
+    #[test]
+    fn save() {
+        let dir = std::env::temp_dir()
+            .join(format!("splotch-save-{}", std::process::id()));
+        let domain = BBox::new([(0.0, 0.0), (1.0, 1.0)]);
+        let chart = Chart::default()
+            .with_title("Saved")
+            .with_line_plot(Plot::new("A", &domain, [(0.0, 0.0), (1.0, 1.0)]));
+        let html = dir.join("nested/chart.html");
+        let svg = dir.join("nested/chart.svg");
+        assert!(chart.save_html(&html).is_err());
+        assert!(chart.save_svg(&svg).is_err());
+        assert!(!dir.exists());
+        chart.save_html_all(&html).unwrap();
+        chart.save_svg(&svg).unwrap();
+        let html = std::fs::read_to_string(html).unwrap();
+        let svg = std::fs::read_to_string(svg).unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+        assert_eq!(html, chart.render());
+        let doc = roxmltree::Document::parse(&svg).unwrap();
+        assert_eq!(doc.root_element().tag_name().name(), "svg");
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(Coord::new(12.5, 2).to_string(), "12.5");
+        assert_eq!(Coord::new(12.504, 2).to_string(), "12.5");
+        assert_eq!(Coord::new(12.5, 0).to_string(), "13");
+        assert_eq!(Coord::new(-0.001, 1).to_string(), "0");
+        assert_eq!(Coord::new(1960.0, 3).to_string(), "1960");
+        assert_eq!(Coord::new(-12.25, 2).to_string(), "-12.25");
+        assert_eq!(Coord::new(-0.06, 2).to_string(), "-0.06");
+        assert_eq!(Coord::new(f32::NAN, 0).to_string(), "NaN");
+        let domain = BBox::new([(0.0, 0.0), (7.0, 3.0)]);
+        let data = [(0.0, 0.0), (1.0, 2.0), (7.0, 3.0)];
+        let render = |precision| {
+            Chart::default()
+                .with_precision(precision)
+                .with_axis(Horizontal::new(domain))
+                .with_line_plot(Plot::new("A", &domain, data))
+                .render_svg()
+        };
+        let svg = render(0);
+        assert!(svg.contains("d='M40 1380l274 -893 1646 -447'/>"));
+        assert!(svg.contains("M40 40v1340M314 40v1340"));
+        let svg = render(2);
+        assert!(svg.contains("d='M40 1380l274.29 -893.33 1645.71 -446.67'/>"));
+        assert!(svg.contains("M40 40v1340M314.29 40v1340"));
+    }
+
+    #[test]
+    fn theme() {
+        let html = Chart::default().render();
+        assert!(!html.contains("--color: #006BA4;"));
+        let chart = Chart::default().with_theme(Theme::colorblind());
+        assert!(chart.render().contains(".plot-0 {\n  --color: #006BA4;"));
+        assert!(chart.render_svg().contains("--color: #006BA4;"));
+    }
 }
@@ -0,0 +1,129 @@
+// css.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Stylesheet generation
+//!
+//! The default stylesheet has one `plot-N` class for each series, so charts
+//! with many series are still colored.
+
+use std::fmt::Write;
+
+use crate::heatmap::ColorRamp;
+
+/// Stylesheet rules, ending with the default series classes
+const SPLOTCH_CSS: &str = include_str!("../css/splotch.css");
+
+/// Sparkline stylesheet rules, without series classes
+const SPARKLINE_CSS: &str = include_str!("../css/sparkline.css");
+
+/// Tableau 10 palette
+pub(crate) const TABLEAU: [&str; 10] = [
+    "#4E79A7", "#F28E2C", "#E15759", "#76B7B2", "#59A14F", "#EDC949",
+    "#AF7AA1", "#FF9DA7", "#9C755F", "#BAB0AB",
+];
+
+/// Palette of series colors
+///
+/// Series beyond the palette are given generated colors, with hues spaced by
+/// the golden angle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    /// Tableau 10 category colors
+    #[default]
+    Category10,
+    /// Colors spaced evenly along the viridis ramp
+    Viridis(usize),
+    /// Custom CSS color values, such as `#4E79A7` or `gray`
+    Custom(Vec<String>),
+}
+
+impl Palette {
+    /// Get the color of a series
+    pub fn color(&self, num: usize) -> String {
+        match self {
+            Palette::Category10 if num < TABLEAU.len() => {
+                TABLEAU[num].to_string()
+            }
+            Palette::Viridis(count) if num < *count => {
+                let t = match count {
+                    1 => 0.0,
+                    _ => num as f32 / (count - 1) as f32,
+                };
+                let [r, g, b] = ColorRamp::Viridis.color(t);
+                format!("#{r:02x}{g:02x}{b:02x}")
+            }
+            Palette::Custom(colors) if num < colors.len() => {
+                colors[num].clone()
+            }
+            _ => generated_color(num),
+        }
+    }
+}
+
+/// Get a generated color for a series
+///
+/// Hues are spaced by the golden angle, so nearby series are distinct.
+pub(crate) fn generated_color(num: usize) -> String {
+    let hue = (num as f32 * 137.508) % 360.0;
+    format!("hsl({hue:.0}, 55%, 50%)")
+}
+
+/// Generate the default stylesheet, for a number of series
+pub fn default(n_series: usize) -> String {
+    with_palette(n_series, &Palette::default())
+}
+
+/// Generate the default stylesheet, with a palette of series colors
+///
+/// At least 10 series classes are always included.
+pub fn with_palette(n_series: usize, palette: &Palette) -> String {
+    let mut css = base_css().to_string();
+    for num in 0..n_series.max(TABLEAU.len()) {
+        let color = palette.color(num);
+        writeln!(css, ".plot-{num} {{\n  --color: {color};").unwrap();
+        writeln!(css, "  --marker: url(#marker-{num});\n}}").unwrap();
+    }
+    css
+}
+
+/// Get stylesheet rules, without the default series classes
+fn base_css() -> &'static str {
+    SPLOTCH_CSS
+        .split_once(".plot-0 {")
+        .map_or(SPLOTCH_CSS, |(base, _)| base)
+}
+
+/// Generate the sparkline stylesheet, with a palette of series colors
+///
+/// Only the classes of the series are included, to keep sparklines small.
+pub(crate) fn sparkline(n_series: usize, palette: &Palette) -> String {
+    let mut css = SPARKLINE_CSS.to_string();
+    for num in 0..n_series {
+        let color = palette.color(num);
+        writeln!(css, ".plot-{num} {{\n  --color: {color};\n}}").unwrap();
+    }
+    css
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palettes() {
+        let css = default(12);
+        assert!(css.contains(".plot-0 {\n  --color: #4E79A7;\n"));
+        assert!(css.contains(".plot-11 {\n  --color: hsl("));
+        assert!(!css.contains(".plot-12 "));
+        assert_eq!(default(0), default(10));
+        let viridis = Palette::Viridis(3);
+        assert_eq!(viridis.color(0), "#440154");
+        assert_eq!(viridis.color(2), "#fde725");
+        assert_eq!(viridis.color(3), generated_color(3));
+        let custom = Palette::Custom(vec!["red".into()]);
+        let css = with_palette(2, &custom);
+        assert!(css.contains(".plot-0 {\n  --color: red;\n"));
+        assert!(css.contains("  --marker: url(#marker-1);\n}"));
+    }
+}
@@ -0,0 +1,406 @@
+// data.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Data loading helpers
+//!
+//! CSV functions require the `csv` feature.
+
+use pointy::{BBox, Pt};
+
+#[cfg(feature = "csv")]
+pub use self::csv_data::*;
+
+/// Get the domain of points
+///
+/// Points which are not finite are ignored.  If there are none, the domain
+/// is a unit square at the origin.
+pub fn domain(pts: &[Pt<f32>]) -> BBox<f32> {
+    let mut finite = pts
+        .iter()
+        .filter(|pt| pt.x().is_finite() && pt.y().is_finite())
+        .copied();
+    match finite.next() {
+        Some(first) => {
+            let mut domain = BBox::new([first]);
+            domain.extend(finite);
+            domain
+        }
+        None => BBox::new([(0.0, 0.0), (1.0, 1.0)]),
+    }
+}
+
+/// Parse an RFC 3339 timestamp into seconds since the Unix epoch
+///
+/// For example, `2022-03-04T05:06:07.5Z` or `2022-03-04 05:06:07+02:00`.
+pub fn parse_timestamp(text: &str) -> Option<f64> {
+    let b = text.trim().as_bytes();
+    let num = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = b.get(range)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        std::str::from_utf8(digits).ok()?.parse().ok()
+    };
+    if b.len() < 19
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || !matches!(b[10], b'T' | b't' | b' ')
+        || b[13] != b':'
+        || b[16] != b':'
+    {
+        return None;
+    }
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+    let mut pos = 19;
+    let mut fraction = 0.0;
+    if b.get(pos) == Some(&b'.') {
+        let start = pos + 1;
+        pos = start;
+        while b.get(pos).is_some_and(u8::is_ascii_digit) {
+            pos += 1;
+        }
+        if pos == start {
+            return None;
+        }
+        fraction = std::str::from_utf8(&b[start - 1..pos])
+            .ok()?
+            .parse::<f64>()
+            .ok()?;
+    }
+    let offset = match b.get(pos..)? {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let (h, m) = (num(pos + 1..pos + 3)?, num(pos + 4..pos + 6)?);
+            if h > 23 || m > 59 {
+                return None;
+            }
+            let offset = h * 3600 + m * 60;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset;
+    Some(secs as f64 + fraction)
+}
+
+/// Get the number of days since the Unix epoch for a civil date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(feature = "csv")]
+mod csv_data {
+    use std::{fmt, io};
+
+    use pointy::{BBox, Pt};
+
+    use super::{domain, parse_timestamp};
+
+    /// Column of a CSV file
+    ///
+    /// The first row of a file is a header, containing column names.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Column {
+        key: Key,
+        /// Origin of timestamps, in seconds since the epoch
+        timestamp: Option<f64>,
+    }
+
+    /// Column key
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum Key {
+        Name(String),
+        Index(usize),
+    }
+
+    /// Error reading data
+    #[derive(Debug)]
+    #[non_exhaustive]
+    pub enum DataError {
+        /// Error reading or parsing CSV
+        Csv(csv::Error),
+        /// Column name not found in the header
+        UnknownColumn(String),
+        /// Column index beyond the end of the header
+        ColumnIndex(usize),
+    }
+
+    /// Row skipped because a value could not be parsed
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Skipped {
+        /// Line number of the row (starting at 1)
+        pub line: u64,
+        /// Text of the value which could not be parsed
+        pub text: String,
+    }
+
+    impl From<&str> for Column {
+        fn from(name: &str) -> Self {
+            Column::name(name)
+        }
+    }
+
+    impl From<String> for Column {
+        fn from(name: String) -> Self {
+            Column::name(name)
+        }
+    }
+
+    impl From<usize> for Column {
+        fn from(index: usize) -> Self {
+            Column::index(index)
+        }
+    }
+
+    impl Column {
+        /// Select a column by header name
+        pub fn name(name: impl Into<String>) -> Self {
+            Self {
+                key: Key::Name(name.into()),
+                timestamp: None,
+            }
+        }
+
+        /// Select a column by index, starting at 0
+        pub fn index(index: usize) -> Self {
+            Self {
+                key: Key::Index(index),
+                timestamp: None,
+            }
+        }
+
+        /// Parse values as RFC 3339 timestamps, in seconds since the epoch
+        ///
+        /// Values are stored as `f32`, so precision is limited to a few
+        /// minutes for current dates.  Use
+        /// [as_timestamp_since](Self::as_timestamp_since) to keep it.
+        pub fn as_timestamp(self) -> Self {
+            self.as_timestamp_since(0.0)
+        }
+
+        /// Parse values as RFC 3339 timestamps, in seconds since an origin
+        ///
+        /// The origin is in seconds since the epoch, as returned by
+        /// [parse_timestamp](super::parse_timestamp).  It is subtracted
+        /// before values are stored as `f32`, so times near the origin keep
+        /// sub-second precision.
+        pub fn as_timestamp_since(mut self, origin: f64) -> Self {
+            self.timestamp = Some(origin);
+            self
+        }
+
+        /// Find the column index in a header
+        fn find(&self, header: &csv::StringRecord) -> Result<usize, DataError> {
+            match &self.key {
+                Key::Index(index) if *index < header.len() => Ok(*index),
+                Key::Index(index) => Err(DataError::ColumnIndex(*index)),
+                Key::Name(name) => header
+                    .iter()
+                    .position(|h| h.trim() == name)
+                    .ok_or_else(|| DataError::UnknownColumn(name.clone())),
+            }
+        }
+
+        /// Parse a value
+        fn parse(&self, text: &str) -> Option<f32> {
+            match self.timestamp {
+                Some(origin) => {
+                    parse_timestamp(text).map(|secs| (secs - origin) as f32)
+                }
+                None => text.trim().parse().ok(),
+            }
+        }
+    }
+
+    impl fmt::Display for DataError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                DataError::Csv(e) => write!(f, "CSV error: {e}"),
+                DataError::UnknownColumn(name) => {
+                    write!(f, "unknown column \"{name}\"")
+                }
+                DataError::ColumnIndex(index) => {
+                    write!(f, "column index {index} out of range")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for DataError {}
+
+    impl From<csv::Error> for DataError {
+        fn from(e: csv::Error) -> Self {
+            DataError::Csv(e)
+        }
+    }
+
+    /// Read points from two columns of a CSV file
+    ///
+    /// Rows with values which can't be parsed are skipped.
+    pub fn from_csv<R, X, Y>(
+        reader: R,
+        x_col: X,
+        y_col: Y,
+    ) -> Result<Vec<Pt<f32>>, DataError>
+    where
+        R: io::Read,
+        X: Into<Column>,
+        Y: Into<Column>,
+    {
+        from_csv_with_skipped(reader, x_col, y_col).map(|(pts, _)| pts)
+    }
+
+    /// Read points from two columns of a CSV file, with skipped rows
+    pub fn from_csv_with_skipped<R, X, Y>(
+        reader: R,
+        x_col: X,
+        y_col: Y,
+    ) -> Result<(Vec<Pt<f32>>, Vec<Skipped>), DataError>
+    where
+        R: io::Read,
+        X: Into<Column>,
+        Y: Into<Column>,
+    {
+        let (x_col, y_col) = (x_col.into(), y_col.into());
+        let mut rdr =
+            csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+        let header = rdr.headers()?.clone();
+        let (xi, yi) = (x_col.find(&header)?, y_col.find(&header)?);
+        let mut pts = vec![];
+        let mut skipped = vec![];
+        for record in rdr.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |p| p.line());
+            let x = record.get(xi).unwrap_or_default();
+            let y = record.get(yi).unwrap_or_default();
+            match (x_col.parse(x), y_col.parse(y)) {
+                (Some(x), Some(y)) => pts.push(Pt::new(x, y)),
+                (None, _) => skipped.push(Skipped {
+                    line,
+                    text: x.to_string(),
+                }),
+                (_, None) => skipped.push(Skipped {
+                    line,
+                    text: y.to_string(),
+                }),
+            }
+        }
+        Ok((pts, skipped))
+    }
+
+    /// Read points from two columns of a CSV file, along with their domain
+    ///
+    /// ```rust
+    /// use splotch::{data::from_csv_with_domain, Plot};
+    ///
+    /// let csv = "x,y\n1,2\n3,4\n";
+    /// let (data, domain) = from_csv_with_domain(csv.as_bytes(), "x", "y")?;
+    /// let plot = Plot::new("Series", &domain, data);
+    /// # Ok::<(), splotch::data::DataError>(())
+    /// ```
+    pub fn from_csv_with_domain<R, X, Y>(
+        reader: R,
+        x_col: X,
+        y_col: Y,
+    ) -> Result<(Vec<Pt<f32>>, BBox<f32>), DataError>
+    where
+        R: io::Read,
+        X: Into<Column>,
+        Y: Into<Column>,
+    {
+        let pts = from_csv(reader, x_col, y_col)?;
+        let domain = domain(&pts);
+        Ok((pts, domain))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn csv() {
+            let csv = "time, value,other\n\
+                2022-01-01T00:00:00Z,1.5,a\n\
+                2022-01-01T00:01:00Z,oops,b\n\
+                2022-01-01T00:02:00+00:00,2.5,c\n";
+            let col = || Column::name("time").as_timestamp();
+            let (pts, skipped) =
+                from_csv_with_skipped(csv.as_bytes(), col(), 1).unwrap();
+            let xy: Vec<_> = pts.iter().map(|pt| (pt.x(), pt.y())).collect();
+            assert_eq!(xy, [(1_640_995_200.0, 1.5), (1_640_995_320.0, 2.5)]);
+            assert_eq!(
+                skipped,
+                [Skipped {
+                    line: 3,
+                    text: "oops".into()
+                }]
+            );
+            let (_, domain) =
+                from_csv_with_domain(csv.as_bytes(), col(), "value").unwrap();
+            assert_eq!((domain.y_min(), domain.y_max()), (1.5, 2.5));
+            let err = from_csv(csv.as_bytes(), "missing", 1).unwrap_err();
+            assert_eq!(err.to_string(), "unknown column \"missing\"");
+            let origin = parse_timestamp("2022-01-01T00:00:00Z").unwrap();
+            let col = Column::index(0).as_timestamp_since(origin);
+            let csv = "time,value\n\
+                2022-01-01T00:00:00.5Z,1\n\
+                2022-01-01T00:00:01.25Z,2\n";
+            let pts = from_csv(csv.as_bytes(), col, 1).unwrap();
+            let xs: Vec<_> = pts.iter().map(|pt| pt.x()).collect();
+            assert_eq!(xs, [0.5, 1.25]);
+            let err = from_csv(csv.as_bytes(), 0, 3).unwrap_err();
+            assert_eq!(err.to_string(), "column index 3 out of range");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0.0));
+        assert_eq!(
+            parse_timestamp("2000-03-01T12:30:15.25Z"),
+            Some(951_913_815.25)
+        );
+        assert_eq!(
+            parse_timestamp("2000-03-01 14:30:15+02:00"),
+            parse_timestamp("2000-03-01T12:30:15Z")
+        );
+        assert_eq!(parse_timestamp("1969-12-31T23:59:59Z"), Some(-1.0));
+        assert_eq!(parse_timestamp("2000-13-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2000-01-01T00:00:00"), None);
+        assert_eq!(parse_timestamp("2000-01-01T00:00:00.Z"), None);
+    }
+
+    #[test]
+    fn domains() {
+        let pts =
+            [Pt::new(1.0, f32::NAN), Pt::new(2.0, 3.0), Pt::new(4.0, 1.0)];
+        let domain = domain(&pts);
+        assert_eq!((domain.x_min(), domain.x_max()), (2.0, 4.0));
+        assert_eq!((domain.y_min(), domain.y_max()), (1.0, 3.0));
+    }
+}
@@ -0,0 +1,104 @@
+// decimate.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Downsampling of large series
+
+use pointy::Pt;
+
+/// Downsample points with Largest-Triangle-Three-Buckets
+///
+/// The first and last points are always kept.  Points between them are
+/// split into buckets, keeping the one which forms the largest triangle with
+/// the previous kept point and the average of the next bucket.
+pub(crate) fn lttb(pts: &[Pt<f32>], max: usize) -> Vec<Pt<f32>> {
+    let n = pts.len();
+    if n <= max || n < 3 {
+        return pts.to_vec();
+    }
+    if max < 3 {
+        return vec![pts[0], pts[n - 1]];
+    }
+    let every = (n - 2) as f32 / (max - 2) as f32;
+    let bucket = |i: usize| ((i as f32 * every) as usize + 1).min(n - 1);
+    let mut out = Vec::with_capacity(max);
+    let mut a = pts[0];
+    out.push(a);
+    for i in 0..max - 2 {
+        // Average of the next bucket (or the last point)
+        let next = &pts[bucket(i + 1)..bucket(i + 2).max(bucket(i + 1) + 1)];
+        let len = next.len() as f32;
+        let avg = next.iter().fold(Pt::new(0.0, 0.0), |s, p| s + *p) / len;
+        let best = pts[bucket(i)..bucket(i + 1)]
+            .iter()
+            .max_by(|p, q| area(a, **p, avg).total_cmp(&area(a, **q, avg)))
+            .copied();
+        if let Some(pt) = best {
+            out.push(pt);
+            a = pt;
+        }
+    }
+    out.push(pts[n - 1]);
+    out
+}
+
+/// Downsample points by keeping every `n`th point
+///
+/// The first and last points are always kept.
+pub(crate) fn stride(pts: &[Pt<f32>], max: usize) -> Vec<Pt<f32>> {
+    let n = pts.len();
+    if n <= max || n < 3 {
+        return pts.to_vec();
+    }
+    let step = (n - 1).div_ceil(max.max(2) - 1);
+    let mut out: Vec<Pt<f32>> = pts.iter().step_by(step).copied().collect();
+    if !(n - 1).is_multiple_of(step) {
+        out.push(pts[n - 1]);
+    }
+    out
+}
+
+/// Get twice the area of a triangle
+fn area(a: Pt<f32>, b: Pt<f32>, c: Pt<f32>) -> f32 {
+    ((a.x() - c.x()) * (b.y() - a.y()) - (a.x() - b.x()) * (c.y() - a.y()))
+        .abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(n: usize) -> Vec<Pt<f32>> {
+        (0..n)
+            .map(|i| Pt::new(i as f32, (i as f32 / 10.0).sin()))
+            .collect()
+    }
+
+    #[test]
+    fn largest_triangle() {
+        let pts = points(10_000);
+        let out = lttb(&pts, 200);
+        assert_eq!(out.len(), 200);
+        assert_eq!(out[0], pts[0]);
+        assert_eq!(out[199], pts[9_999]);
+        assert!(out.windows(2).all(|w| w[0].x() < w[1].x()));
+        // A spike is always kept
+        let mut pts = points(1000);
+        pts[500] = Pt::new(500.0, 100.0);
+        assert!(lttb(&pts, 50).contains(&pts[500]));
+        assert_eq!(lttb(&pts[..10], 50).len(), 10);
+        assert_eq!(lttb(&pts, 2), vec![pts[0], pts[999]]);
+    }
+
+    #[test]
+    fn striding() {
+        let pts = points(1001);
+        let out = stride(&pts, 11);
+        assert_eq!(out.len(), 11);
+        assert_eq!(out[1], pts[100]);
+        assert_eq!(out[10], pts[1000]);
+        let out = stride(&pts[..1000], 11);
+        assert!(out.len() <= 11);
+        assert_eq!(out.last(), Some(&pts[999]));
+    }
+}
@@ -0,0 +1,56 @@
+// error.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Chart errors
+
+use std::fmt;
+
+/// Error rendering a chart
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Formatting error from the writer
+    Fmt(fmt::Error),
+    /// Domain of the named plot is empty
+    EmptyDomain(String),
+    /// Plot is bound to an axis id which is not on the chart
+    UnknownAxis(String),
+    /// Line or area plot has `X` values which are not in order
+    Unsorted(String),
+    /// Axes and titles need more space than the chart has
+    LayoutOverflow {
+        /// Space needed, in chart units
+        needed: u32,
+        /// Space available, in chart units
+        available: u32,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Fmt(e) => write!(f, "format error: {e}"),
+            Error::EmptyDomain(name) => {
+                write!(f, "empty domain for plot \"{name}\"")
+            }
+            Error::UnknownAxis(id) => write!(f, "unknown axis id \"{id}\""),
+            Error::Unsorted(name) => {
+                write!(f, "unsorted X values for plot \"{name}\"")
+            }
+            Error::LayoutOverflow { needed, available } => write!(
+                f,
+                "layout overflow: axes+titles need {needed} units, only \
+                {available} available"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<fmt::Error> for Error {
+    fn from(e: fmt::Error) -> Self {
+        Error::Fmt(e)
+    }
+}
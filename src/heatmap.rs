@@ -0,0 +1,190 @@
+// heatmap.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Heatmap plots
+
+use std::{fmt, fmt::Write};
+
+use pointy::BBox;
+
+use crate::plot::{Mapping, Scales, Series};
+
+/// Viridis color ramp control points
+const VIRIDIS: &[[u8; 3]] = &[
+    [68, 1, 84],
+    [72, 40, 120],
+    [62, 74, 137],
+    [49, 104, 142],
+    [38, 130, 142],
+    [31, 158, 137],
+    [53, 183, 121],
+    [109, 205, 89],
+    [180, 222, 44],
+    [253, 231, 37],
+];
+
+/// Color ramp for mapping values to colors
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorRamp {
+    /// Perceptually uniform ramp from purple to yellow
+    #[default]
+    Viridis,
+    /// Linear ramp between two RGB colors
+    Linear([u8; 3], [u8; 3]),
+}
+
+/// Heatmap plot over a 2D grid
+///
+/// Cells are spread evenly over the domain, with the first row at the top and
+/// the first column on the left.  Each cell is filled with a color from a
+/// `ColorRamp`.
+pub struct Heatmap<'a> {
+    name: &'a str,
+    domain: &'a BBox<f32>,
+    rows: &'a [Vec<f32>],
+    range: Option<(f32, f32)>,
+    ramp: ColorRamp,
+}
+
+impl ColorRamp {
+    /// Get the color at a normalized value (0 to 1)
+    pub fn color(self, t: f32) -> [u8; 3] {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        match self {
+            ColorRamp::Viridis => {
+                let pos = t * (VIRIDIS.len() - 1) as f32;
+                let i = (pos.floor() as usize).min(VIRIDIS.len() - 2);
+                lerp(VIRIDIS[i], VIRIDIS[i + 1], pos - i as f32)
+            }
+            ColorRamp::Linear(start, end) => lerp(start, end, t),
+        }
+    }
+
+    /// Display a color as a hex string
+    pub(crate) fn display(self, f: &mut dyn Write, t: f32) -> fmt::Result {
+        let [r, g, b] = self.color(t);
+        write!(f, "#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+impl<'a> Heatmap<'a> {
+    /// Create a new heatmap from rows of values
+    pub fn new(
+        name: &'a str,
+        domain: &'a BBox<f32>,
+        rows: &'a [Vec<f32>],
+    ) -> Self {
+        Self {
+            name,
+            domain,
+            rows,
+            range: None,
+            ramp: ColorRamp::default(),
+        }
+    }
+
+    /// Set the range of values mapped to the color ramp
+    ///
+    /// By default, the range of the data is used.
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Set the color ramp
+    pub fn with_ramp(mut self, ramp: ColorRamp) -> Self {
+        self.ramp = ramp;
+        self
+    }
+
+    /// Get the range of values
+    fn range(&self) -> (f32, f32) {
+        self.range.unwrap_or_else(|| {
+            let values = self.rows.iter().flatten().filter(|v| v.is_finite());
+            values.fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| {
+                (min.min(*v), max.max(*v))
+            })
+        })
+    }
+}
+
+impl Series for Heatmap<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn domain(&self) -> Option<&BBox<f32>> {
+        Some(self.domain)
+    }
+
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        rect: BBox<f32>,
+        scales: &Scales,
+        _stack: &mut Vec<f32>,
+    ) -> fmt::Result {
+        let map = Mapping::new(self.domain, rect, scales);
+        let n_rows = self.rows.len();
+        let n_cols = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let (min, max) = self.range();
+        let span = max - min;
+        let width = self.domain.x_span() / n_cols as f32;
+        let height = self.domain.y_span() / n_rows as f32;
+
+        writeln!(f, "<g class='plot-{num} plot-heatmap'>")?;
+
+        for (r, row) in self.rows.iter().enumerate() {
+            let y0 = self.domain.y_max() - r as f32 * height;
+            let y0 = map.y_map(y0);
+            let y1 = self.domain.y_max() - (r + 1) as f32 * height;
+            let y1 = map.y_map(y1);
+            for (c, value) in row.iter().enumerate() {
+                if !value.is_finite() {
+                    continue;
+                }
+                let x0 = self.domain.x_min() + c as f32 * width;
+                let x0 = map.x_map(x0);
+                let x1 = self.domain.x_min() + (c + 1) as f32 * width;
+                let x1 = map.x_map(x1);
+                let t = if span > 0.0 {
+                    (value - min) / span
+                } else {
+                    0.5
+                };
+                write!(f, "<rect x='{}' y='{}'", x0.min(x1), y0.min(y1))?;
+                write!(f, " width='{}'", (x1 - x0).abs())?;
+                write!(f, " height='{}' fill='", (y1 - y0).abs())?;
+                self.ramp.display(f, t)?;
+                writeln!(f, "'/>")?;
+            }
+        }
+
+        writeln!(f, "</g>")
+    }
+}
+
+/// Linearly interpolate between two colors
+fn lerp(a: [u8; 3], b: [u8; 3], t: f32) -> [u8; 3] {
+    let mix = |a: u8, b: u8| {
+        (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8
+    };
+    [mix(a[0], b[0]), mix(a[1], b[1]), mix(a[2], b[2])]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ramp() {
+        let ramp = ColorRamp::Linear([0, 0, 0], [255, 100, 10]);
+        assert_eq!(ramp.color(0.0), [0, 0, 0]);
+        assert_eq!(ramp.color(0.5), [128, 50, 5]);
+        assert_eq!(ramp.color(2.0), [255, 100, 10]);
+        assert_eq!(ColorRamp::Viridis.color(0.0), VIRIDIS[0]);
+        assert_eq!(ColorRamp::Viridis.color(1.0), VIRIDIS[9]);
+    }
+}
@@ -0,0 +1,100 @@
+// json.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Embedded JSON data
+//!
+//! Output is safe in both an HTML `script` element and XML text, since `<`,
+//! `>` and `&` only appear as `\u` escapes within strings.
+
+use std::{fmt, fmt::Write};
+
+use pointy::Pt;
+
+/// Write a JSON string, escaped for embedding in HTML or XML
+fn write_string(f: &mut dyn Write, text: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => {
+                write!(f, "\\u{:04x}", u32::from(c))?
+            }
+            c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Write a JSON number, or `null` if not finite
+fn write_number(f: &mut dyn Write, value: f32) -> fmt::Result {
+    if value.is_finite() {
+        write!(f, "{value}")
+    } else {
+        f.write_str("null")
+    }
+}
+
+/// Write a JSON object of `[x, y]` point arrays, keyed by name
+///
+/// Repeated names are given a ` (n)` suffix, so no data is lost.
+pub(crate) fn write_data<'p>(
+    f: &mut dyn Write,
+    series: impl Iterator<Item = (&'p str, &'p [Pt<f32>])>,
+) -> fmt::Result {
+    let mut names: Vec<&str> = vec![];
+    f.write_char('{')?;
+    for (i, (name, pts)) in series.enumerate() {
+        if i > 0 {
+            f.write_char(',')?;
+        }
+        let count = names.iter().filter(|n| **n == name).count();
+        names.push(name);
+        match count {
+            0 => write_string(f, name)?,
+            _ => write_string(f, &format!("{name} ({})", count + 1))?,
+        }
+        f.write_str(":[")?;
+        for (j, pt) in pts.iter().enumerate() {
+            if j > 0 {
+                f.write_char(',')?;
+            }
+            f.write_char('[')?;
+            write_number(f, pt.x())?;
+            f.write_char(',')?;
+            write_number(f, pt.y())?;
+            f.write_char(']')?;
+        }
+        f.write_char(']')?;
+    }
+    f.write_char('}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data() {
+        let a = [Pt::new(1.0, 2.5), Pt::new(f32::NAN, -3.0)];
+        let b = [Pt::new(1e20, 0.1)];
+        let mut out = String::new();
+        write_data(
+            &mut out,
+            [("</script>", &a[..]), ("a\"b", &b[..]), ("a\"b", &[][..])]
+                .into_iter(),
+        )
+        .unwrap();
+        assert!(!out.contains('<') && !out.contains('&'));
+        let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(json["</script>"][0][1], 2.5);
+        assert!(json["</script>"][1][0].is_null());
+        assert_eq!(json["a\"b"][0][0].as_f64().unwrap() as f32, 1e20);
+        assert_eq!(json["a\"b (2)"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,209 @@
+// legend.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Chart legends
+
+use std::{fmt, fmt::Write};
+
+use pointy::BBox;
+
+use crate::{
+    page::Edge,
+    plot::Series,
+    text::{Escape, Tick},
+};
+
+/// Corner of the plot area
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Legend placement
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Placement {
+    /// HTML legend beside the chart
+    Outside(Edge),
+    /// SVG legend overlaid on the plot area
+    Inside(Corner),
+}
+
+/// Chart legend
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Legend {
+    placement: Placement,
+}
+
+/// Padding around inside legend entries
+const PADDING: i32 = 20;
+
+/// Height of an inside legend row
+const ROW_HEIGHT: i32 = 40;
+
+/// Width of an inside legend swatch
+const SWATCH_WIDTH: i32 = 60;
+
+impl Default for Legend {
+    fn default() -> Self {
+        Self {
+            placement: Placement::Outside(Edge::Right),
+        }
+    }
+}
+
+impl Legend {
+    /// Create a new legend, to the right of the chart
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place the legend above the chart
+    pub fn on_top(mut self) -> Self {
+        self.placement = Placement::Outside(Edge::Top);
+        self
+    }
+
+    /// Place the legend below the chart
+    pub fn on_bottom(mut self) -> Self {
+        self.placement = Placement::Outside(Edge::Bottom);
+        self
+    }
+
+    /// Place the legend to the left of the chart
+    pub fn on_left(mut self) -> Self {
+        self.placement = Placement::Outside(Edge::Left);
+        self
+    }
+
+    /// Place the legend to the right of the chart (this is the default)
+    pub fn on_right(mut self) -> Self {
+        self.placement = Placement::Outside(Edge::Right);
+        self
+    }
+
+    /// Overlay the legend in the top-left corner of the plot area
+    pub fn inside_top_left(mut self) -> Self {
+        self.placement = Placement::Inside(Corner::TopLeft);
+        self
+    }
+
+    /// Overlay the legend in the top-right corner of the plot area
+    pub fn inside_top_right(mut self) -> Self {
+        self.placement = Placement::Inside(Corner::TopRight);
+        self
+    }
+
+    /// Overlay the legend in the bottom-left corner of the plot area
+    pub fn inside_bottom_left(mut self) -> Self {
+        self.placement = Placement::Inside(Corner::BottomLeft);
+        self
+    }
+
+    /// Overlay the legend in the bottom-right corner of the plot area
+    pub fn inside_bottom_right(mut self) -> Self {
+        self.placement = Placement::Inside(Corner::BottomRight);
+        self
+    }
+
+    /// Check if the legend is overlaid on the plot area
+    pub(crate) fn is_inside(&self) -> bool {
+        matches!(self.placement, Placement::Inside(_))
+    }
+
+    /// Get the class of the chart `div` for an HTML legend
+    pub(crate) fn chart_class(&self) -> &'static str {
+        match self.placement {
+            Placement::Outside(Edge::Top) => "chart legend-top",
+            Placement::Outside(Edge::Bottom) => "chart legend-bottom",
+            Placement::Outside(Edge::Left) => "chart legend-left",
+            _ => "chart",
+        }
+    }
+
+    /// Display an inside legend as an SVG group
+    ///
+    /// Each entry is a `(num, series, note)` tuple.
+    pub(crate) fn display_inside<'s>(
+        &self,
+        f: &mut dyn Write,
+        area: BBox<f32>,
+        entries: impl Iterator<Item = (usize, &'s dyn Series, Option<&'s str>)>,
+    ) -> fmt::Result {
+        let corner = match self.placement {
+            Placement::Inside(corner) => corner,
+            Placement::Outside(_) => return Ok(()),
+        };
+        let entries: Vec<_> = entries.collect();
+        let rows: usize = entries
+            .iter()
+            .map(|(_, series, _)| series.legend_labels().len())
+            .sum();
+        if rows == 0 {
+            return Ok(());
+        }
+        let chars = entries
+            .iter()
+            .flat_map(|(_, series, note)| {
+                let note = note.map_or(0, |n| n.chars().count() + 1);
+                series
+                    .legend_labels()
+                    .into_iter()
+                    .map(move |label| label.chars().count() + note)
+            })
+            .max()
+            .unwrap_or(0);
+        let width = PADDING * 3
+            + SWATCH_WIDTH
+            + (chars as f32 * Tick::CHAR_WIDTH).ceil() as i32;
+        let height = PADDING * 2 + ROW_HEIGHT * rows as i32;
+        let x = match corner {
+            Corner::TopLeft | Corner::BottomLeft => {
+                area.x_min() as i32 + PADDING
+            }
+            _ => area.x_max() as i32 - PADDING - width,
+        };
+        let y = match corner {
+            Corner::TopLeft | Corner::TopRight => area.y_min() as i32 + PADDING,
+            _ => area.y_max() as i32 - PADDING - height,
+        };
+
+        writeln!(f, "<g class='legend-inside'>")?;
+        write!(f, "<rect class='legend-box' x='{x}' y='{y}'")?;
+        writeln!(f, " width='{width}' height='{height}'/>")?;
+        let x0 = x + PADDING;
+        let mut y = y + PADDING + ROW_HEIGHT / 2;
+        for (num, series, note) in entries {
+            let empty = series.is_empty();
+            if empty {
+                writeln!(f, "<g class='legend-empty'>")?;
+            }
+            for (i, label) in series.legend_labels().into_iter().enumerate() {
+                series.display_legend_swatch(f, num, i, x0, y)?;
+                let x = x0 + SWATCH_WIDTH + PADDING;
+                let link = series.legend_link();
+                if let Some(link) = link {
+                    link.display(f)?;
+                }
+                write!(f, "<text class='legend-text' x='{x}' y='{y}'")?;
+                write!(f, " dy='0.33em'>{}", Escape(label))?;
+                if let Some(note) = note {
+                    let note = Escape(note);
+                    write!(f, " <tspan class='legend-note'>{note}</tspan>")?;
+                }
+                writeln!(f, "</text>")?;
+                if let Some(link) = link {
+                    link.display_done(f)?;
+                }
+                y += ROW_HEIGHT;
+            }
+            if empty {
+                writeln!(f, "</g>")?;
+            }
+        }
+        writeln!(f, "</g>")
+    }
+}
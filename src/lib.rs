@@ -12,8 +12,7 @@
 //!     domain.extend([(0.0, 200.0)]);
 //!     domain
 //! };
-//! let mut data = data.into_iter().map(Into::into);
-//! let plot = Plot::new("Series", &domain, &mut data);
+//! let plot = Plot::new("Series", &domain, data);
 //! let chart = Chart::default()
 //!     .with_title("Line Plot")
 //!     .with_axis(Horizontal::new(domain).with_name("X Axis Name"))
@@ -25,13 +24,50 @@
 //! ```
 #![forbid(unsafe_code)]
 
+mod annotation;
 pub mod axis;
+mod band;
+mod bar;
+mod bubble;
+mod candle;
 mod chart;
+pub mod css;
+pub mod data;
+mod decimate;
+mod error;
+mod heatmap;
+mod json;
+mod legend;
+mod marker;
+mod number;
 mod page;
+mod pie;
 mod plot;
+mod row;
 mod scale;
 mod text;
+mod theme;
+pub mod transform;
+mod trend;
+mod waterfall;
 
-pub use chart::{Chart, Title};
-pub use page::AspectRatio;
-pub use plot::Plot;
+pub use annotation::{Annotation, RefLine, Region};
+pub use band::Band;
+pub use bar::{BarGroup, BarLayout};
+pub use bubble::BubblePlot;
+pub use candle::{Candlestick, Ohlc};
+pub use chart::{Caption, Chart, PointMap, Title};
+pub use error::Error;
+pub use heatmap::{ColorRamp, Heatmap};
+pub use legend::Legend;
+pub use marker::Marker;
+pub use number::NumberFormat;
+pub use page::{AspectRatio, Orientation, Page};
+pub use pie::Pie;
+pub use plot::{Plot, Step};
+pub use row::Row;
+pub use scale::{Quantile, Scale};
+pub use text::{Label, Link};
+pub use theme::Theme;
+pub use trend::TrendLabel;
+pub use waterfall::Waterfall;
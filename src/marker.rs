@@ -0,0 +1,90 @@
+// marker.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Plot markers
+
+/// Default markers, assigned to plots in order
+pub(crate) const MARKERS: &[&str] = &[
+    "<circle r='1'/>",
+    "<rect x='-1' y='-1' width='2' height='2'/>",
+    "<path d='M0 -1 1 1 -1 1z'/>",
+    "<path d='M1 0 -1 1 -1 -1z'/>",
+    "<path d='M0 1 -1 -1 1 -1z'/>",
+    "<path d='M-1 0 1 -1 1 1z'/>",
+    "<path d='M0 -1 1 0 0 1 -1 0z'/>",
+    "<path d='M-1 -1 0 -0.5 1 -1 0.5 0 1 1 0 0.5 -1 1 -0.5 0z'/>",
+];
+
+/// Default marker width and height
+pub(crate) const MARKER_SIZE: f32 = 5.0;
+
+/// Get the default marker shape for a plot number
+///
+/// After the built-in markers are used, shapes are combined with a smaller
+/// hole of another shape, then rotated, so every plot stays distinct.
+pub(crate) fn default_shape(num: usize) -> String {
+    let shape = MARKERS[num % MARKERS.len()];
+    let round = num / MARKERS.len();
+    if round == 0 {
+        return shape.to_string();
+    }
+    let hole = MARKERS[(round - 1) % MARKERS.len()];
+    let angle = 15 * ((round - 1) / MARKERS.len());
+    format!(
+        "<g transform='rotate({angle})'>{shape}\
+        <g class='marker-hole' transform='scale(0.5)'>{hole}</g></g>"
+    )
+}
+
+/// Marker shape for plot points
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Marker<'a> {
+    /// Circle
+    Circle,
+    /// Square
+    Square,
+    /// Triangle pointing up
+    TriangleUp,
+    /// Diamond
+    Diamond,
+    /// Cross
+    Cross,
+    /// Custom SVG elements, drawn within a `viewBox` of `-1 -1 2 2`
+    ///
+    /// The snippet is inserted verbatim, without escaping.
+    Custom(&'a str),
+}
+
+impl<'a> Marker<'a> {
+    /// Get the SVG elements for the marker
+    pub(crate) fn shape(&self) -> &'a str {
+        match self {
+            Marker::Circle => MARKERS[0],
+            Marker::Square => MARKERS[1],
+            Marker::TriangleUp => MARKERS[2],
+            Marker::Diamond => MARKERS[6],
+            Marker::Cross => {
+                "<path d='M-1 -0.3H-0.3V-1H0.3V-0.3H1V0.3H0.3V1H-0.3V0.3H-1z'/>"
+            }
+            Marker::Custom(shape) => shape,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_shapes() {
+        assert_eq!(default_shape(1), MARKERS[1]);
+        let shapes: Vec<_> = (0..200).map(default_shape).collect();
+        let mut unique = shapes.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), shapes.len());
+        assert!(shapes[8].starts_with("<g transform='rotate(0)'><circle"));
+        assert!(shapes[72].starts_with("<g transform='rotate(15)'>"));
+    }
+}
@@ -0,0 +1,141 @@
+// number.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Number formatting
+
+/// Number format for tick and point labels
+///
+/// Numbers are first formatted with a `.` decimal point and no grouping, then
+/// converted to this format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    decimal: char,
+    grouping: Option<(char, usize)>,
+    minus: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::plain()
+    }
+}
+
+impl NumberFormat {
+    /// Create a plain format, such as `-1234.5` (default)
+    pub fn plain() -> Self {
+        Self {
+            decimal: '.',
+            grouping: None,
+            minus: '-',
+        }
+    }
+
+    /// Create an English format, such as `-1,234.5`
+    pub fn en() -> Self {
+        Self::plain().with_grouping(',', 3)
+    }
+
+    /// Create a German format, such as `-1.234,5`
+    pub fn de() -> Self {
+        Self::plain().with_decimal(',').with_grouping('.', 3)
+    }
+
+    /// Set the decimal separator
+    pub fn with_decimal(mut self, decimal: char) -> Self {
+        self.decimal = decimal;
+        self
+    }
+
+    /// Group integer digits, such as by thousands with a thin space
+    ///
+    /// A size of zero disables grouping.
+    pub fn with_grouping(mut self, separator: char, size: usize) -> Self {
+        self.grouping = (size > 0).then_some((separator, size));
+        self
+    }
+
+    /// Set the minus sign, such as `'\u{2212}'`
+    pub fn with_minus(mut self, minus: char) -> Self {
+        self.minus = minus;
+        self
+    }
+
+    /// Format a value with a number of decimal places
+    pub fn format(&self, value: f32, places: usize) -> String {
+        self.localize(&format!("{value:.places$}"))
+    }
+
+    /// Convert a plain number at the start of some text
+    ///
+    /// Text following the number, such as a unit, is unchanged.
+    pub(crate) fn localize(&self, text: &str) -> String {
+        if *self == Self::plain() {
+            return text.to_string();
+        }
+        let (negative, rest) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let digits = rest.find(|c: char| !c.is_ascii_digit());
+        let (int, mut rest) = rest.split_at(digits.unwrap_or(rest.len()));
+        let mut out = String::with_capacity(text.len() + int.len() / 3);
+        if negative {
+            out.push(self.minus);
+        }
+        for (i, c) in int.chars().enumerate() {
+            if let Some((separator, size)) = self.grouping {
+                if i > 0 && (int.len() - i) % size == 0 {
+                    out.push(separator);
+                }
+            }
+            out.push(c);
+        }
+        if let Some(fraction) = rest.strip_prefix('.') {
+            out.push(self.decimal);
+            rest = fraction;
+        }
+        let digits = rest.find(|c: char| !c.is_ascii_digit());
+        let (fraction, mut rest) = rest.split_at(digits.unwrap_or(rest.len()));
+        out.push_str(fraction);
+        if let Some(exp) = rest.strip_prefix("e-") {
+            out.push('e');
+            out.push(self.minus);
+            rest = exp;
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Label;
+
+    #[test]
+    fn formats() {
+        assert_eq!(NumberFormat::plain().format(-1234.5, 1), "-1234.5");
+        assert_eq!(NumberFormat::en().format(-1234.5, 1), "-1,234.5");
+        assert_eq!(NumberFormat::de().format(1234567.0, 2), "1.234.567,00");
+        assert_eq!(NumberFormat::en().format(999.0, 0), "999");
+        let thin = NumberFormat::plain()
+            .with_grouping('\u{2009}', 3)
+            .with_minus('\u{2212}');
+        assert_eq!(thin.format(-12345.0, 0), "\u{2212}12\u{2009}345");
+        assert_eq!(thin.localize("-1.5e-7"), "\u{2212}1.5e\u{2212}7");
+        assert_eq!(NumberFormat::de().localize("2500.5 km"), "2.500,5 km");
+    }
+
+    #[test]
+    fn rounding() {
+        // Rounding can carry into a new group
+        assert_eq!(NumberFormat::en().format(9999.96, 1), "10,000.0");
+        assert_eq!(NumberFormat::de().format(999.5, 0), "1.000");
+        assert_eq!(NumberFormat::en().format(-99999.99, 0), "-100,000");
+        assert_eq!(NumberFormat::de().format(0.004, 2), "0,00");
+        let label = Label::new().with_number_format(NumberFormat::de());
+        assert_eq!(label.clone().rounded_to(1).rounded(1234.56), "1.234,6");
+        assert_eq!(label.rounded(-0.25), "-0,25");
+    }
+}
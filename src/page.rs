@@ -3,8 +3,38 @@
 // Copyright (c) 2021  Douglas P Lau
 // Copyright (c) 2022  Jeron A Lau
 //
+use std::{fmt, fmt::Write};
+
 use pointy::BBox;
 
+use crate::{
+    chart::{Chart, Stylesheet, Title},
+    css::Palette,
+};
+
+/// Page of charts, laid out in a grid of cells
+///
+/// All charts share one stylesheet.  Charts without an id are given one, so
+/// element ids are unique within the page.
+pub struct Page<'a> {
+    aspect_ratio: AspectRatio,
+    pixel_size: Option<(u32, u32)>,
+    rows: usize,
+    cols: usize,
+    titles: Vec<Title>,
+    cells: Vec<Cell<'a>>,
+    stylesheet: Stylesheet,
+}
+
+/// Chart placed in a page
+struct Cell<'a> {
+    chart: Chart<'a>,
+    row: usize,
+    col: usize,
+    rows: usize,
+    cols: usize,
+}
+
 /// Page aspect ratio
 #[derive(Clone, Copy)]
 pub enum AspectRatio {
@@ -14,6 +44,24 @@ pub enum AspectRatio {
     Square,
     /// Tall rectangular aspect
     Portrait,
+    /// Custom size (in SVG units)
+    ///
+    /// Sizes smaller than 1 are treated as 1.
+    Custom {
+        /// Width of the page
+        width: f32,
+        /// Height of the page
+        height: f32,
+    },
+}
+
+/// Orientation of chart elements
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// Parallel to the `X` axis
+    Horizontal,
+    /// Parallel to the `Y` axis
+    Vertical,
 }
 
 /// Edge of rendered item
@@ -26,7 +74,14 @@ pub enum Edge {
 }
 
 impl Edge {
+    /// Split a rectangle at an edge, returning the split portion
+    ///
+    /// The value is limited to the size of the rectangle.
     pub fn split(self, rect: &mut BBox<f32>, value: f32) -> BBox<f32> {
+        let value = match self {
+            Edge::Top | Edge::Bottom => value.clamp(0.0, rect.y_span()),
+            Edge::Left | Edge::Right => value.clamp(0.0, rect.x_span()),
+        };
         match self {
             Edge::Top => {
                 let y = rect.y_min();
@@ -86,6 +141,279 @@ impl AspectRatio {
             AspectRatio::Landscape => BBox::new([(0.0, 0.0), (2000.0, 1500.0)]),
             AspectRatio::Square => BBox::new([(0.0, 0.0), (2000.0, 2000.0)]),
             AspectRatio::Portrait => BBox::new([(0.0, 0.0), (1500.0, 2000.0)]),
+            AspectRatio::Custom { width, height } => {
+                let width = if width >= 1.0 { width } else { 1.0 };
+                let height = if height >= 1.0 { height } else { 1.0 };
+                BBox::new([(0.0, 0.0), (width, height)])
+            }
+        }
+    }
+}
+
+impl<'a> Page<'a> {
+    /// Create a new page with a grid of cells
+    ///
+    /// Rows are added as needed to fit all charts.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            aspect_ratio: AspectRatio::Landscape,
+            pixel_size: None,
+            rows: rows.max(1),
+            cols: cols.max(1),
+            titles: vec![],
+            cells: vec![],
+            stylesheet: Stylesheet::Generated(Palette::default()),
+        }
+    }
+
+    /// Adjust the aspect ratio
+    pub fn with_aspect_ratio(mut self, aspect: AspectRatio) -> Self {
+        self.aspect_ratio = aspect;
+        self
+    }
+
+    /// Set the rendered size (in pixels)
+    pub fn with_pixel_size(mut self, width: u32, height: u32) -> Self {
+        self.pixel_size = Some((width, height));
+        self
+    }
+
+    /// Embed the default stylesheet, with a palette of series colors
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.stylesheet = Stylesheet::Generated(palette);
+        self
+    }
+
+    /// Embed a custom stylesheet, replacing the default
+    pub fn with_css(mut self, css: &str) -> Self {
+        self.stylesheet = Stylesheet::Embedded(css.to_string());
+        self
+    }
+
+    /// Add a page title
+    pub fn with_title<T>(mut self, title: T) -> Self
+    where
+        T: Into<Title>,
+    {
+        let title = title.into().follow(self.titles.last());
+        self.titles.push(title);
+        self
+    }
+
+    /// Add a chart in the next free cell
+    pub fn with_chart(self, chart: Chart<'a>) -> Self {
+        self.with_chart_span(chart, 1, 1)
+    }
+
+    /// Add a chart spanning multiple cells
+    ///
+    /// The chart is placed at the first free position, in row order.
+    pub fn with_chart_span(
+        mut self,
+        chart: Chart<'a>,
+        rows: usize,
+        cols: usize,
+    ) -> Self {
+        let chart = match chart.id() {
+            Some(_) => chart,
+            None => chart.with_cell_id(self.cells.len()),
+        };
+        let rows = rows.max(1);
+        let cols = cols.clamp(1, self.cols);
+        let (row, col) = self.free_position(rows, cols);
+        self.cells.push(Cell {
+            chart,
+            row,
+            col,
+            rows,
+            cols,
+        });
+        self
+    }
+
+    /// Find the first free position for a span of cells
+    fn free_position(&self, rows: usize, cols: usize) -> (usize, usize) {
+        let overlaps = |row: usize, col: usize| {
+            self.cells.iter().any(|c| {
+                row < c.row + c.rows
+                    && c.row < row + rows
+                    && col < c.col + c.cols
+                    && c.col < col + cols
+            })
+        };
+        (0..)
+            .flat_map(|row| (0..=self.cols - cols).map(move |col| (row, col)))
+            .find(|(row, col)| !overlaps(*row, *col))
+            .unwrap()
+    }
+
+    /// Get the rectangle of each cell
+    fn cell_rects(&self, area: BBox<f32>) -> Vec<BBox<f32>> {
+        let rows = self
+            .cells
+            .iter()
+            .map(|c| c.row + c.rows)
+            .fold(self.rows, usize::max);
+        let width = area.x_span() / self.cols as f32;
+        let height = area.y_span() / rows as f32;
+        self.cells
+            .iter()
+            .map(|c| {
+                let x = area.x_min() + width * c.col as f32;
+                let y = area.y_min() + height * c.row as f32;
+                BBox::new([
+                    (x, y),
+                    (x + width * c.cols as f32, y + height * c.rows as f32),
+                ])
+            })
+            .collect()
+    }
+
+    /// Display the page svg, with nested charts
+    ///
+    /// Stand-alone SVG includes the styles within the svg element.
+    fn svg(&self, f: &mut dyn Write, stand_alone: bool) -> fmt::Result {
+        let mut area = self.aspect_ratio.rect();
+        write!(f, "<svg xmlns='http://www.w3.org/2000/svg'")?;
+        if let Some((width, height)) = self.pixel_size {
+            write!(f, " width='{width}' height='{height}'")?;
+        }
+        writeln!(
+            f,
+            " viewBox='{} {} {} {}'>",
+            area.x_min(),
+            area.y_min(),
+            area.x_span(),
+            area.y_span()
+        )?;
+        if stand_alone {
+            if let Some(css) = self.stylesheet.css(self.series_count()) {
+                writeln!(f, "<style>\n{css}</style>")?;
+            }
+            self.marker_style(f)?;
+        }
+        for title in &self.titles {
+            title.display_split(f, &mut area)?;
+        }
+        for (cell, rect) in self.cells.iter().zip(self.cell_rects(area)) {
+            cell.chart.render_cell(f, rect)?;
+        }
+        writeln!(f, "</svg>")
+    }
+
+    /// Get the largest number of series in a chart
+    fn series_count(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|c| c.chart.series_count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Display style for markers of all charts
+    fn marker_style(&self, f: &mut dyn Write) -> fmt::Result {
+        for cell in &self.cells {
+            cell.chart.marker_style(f, &self.stylesheet)?;
         }
+        Ok(())
+    }
+
+    /// Render page as HTML
+    ///
+    /// Chart legends are only included when inside the plot area.
+    pub fn render(&self) -> String {
+        let mut html = String::new();
+        self.render_to(&mut html).unwrap();
+        html
+    }
+
+    /// Render page as HTML to a formatted writer
+    pub fn render_to<W: Write>(&self, mut f: W) -> fmt::Result {
+        write!(f, "<html>")?;
+        write!(f, "<head>")?;
+        write!(f, "<meta charset='UTF-8'>")?;
+        self.stylesheet.display(&mut f, self.series_count())?;
+        self.marker_style(&mut f)?;
+        write!(f, "</head>")?;
+        write!(f, "<body>")?;
+        write!(f, "<div class='page'>")?;
+        write!(f, "<div class='chart'>")?;
+        self.svg(&mut f, false)?;
+        write!(f, "</div>")?;
+        write!(f, "</div>")?;
+        write!(f, "</body>")
+    }
+
+    /// Render page as a standalone SVG
+    ///
+    /// Chart legends are only included when inside the plot area.
+    pub fn render_svg(&self) -> String {
+        let mut svg = String::new();
+        self.render_svg_to(&mut svg).unwrap();
+        svg
+    }
+
+    /// Render page as a standalone SVG to a formatted writer
+    pub fn render_svg_to<W: Write>(&self, mut f: W) -> fmt::Result {
+        self.svg(&mut f, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        axis::{Horizontal, Vertical},
+        Plot,
+    };
+
+    #[test]
+    fn page_cells() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let chart = || {
+            Chart::default()
+                .with_axis(Horizontal::new(domain))
+                .with_axis(Vertical::new(domain))
+                .with_line_plot(Plot::new("", &domain, [(1.0, 2.0)]))
+        };
+        let page = Page::new(2, 2)
+            .with_title("Dashboard")
+            .with_chart_span(chart(), 1, 2)
+            .with_chart(chart())
+            .with_chart(chart().with_id("cell-1"))
+            .render_svg();
+        let doc = roxmltree::Document::parse(&page).unwrap();
+        let cells: Vec<_> = doc
+            .root_element()
+            .children()
+            .filter(|n| n.has_tag_name("svg"))
+            .map(|n| {
+                let attr = |name| n.attribute(name).unwrap();
+                (attr("data-chart"), attr("x"), attr("y"), attr("width"))
+            })
+            .collect();
+        assert_eq!(
+            cells,
+            [
+                ("cell.0", "0", "100", "2000"),
+                ("cell.1", "0", "800", "1000"),
+                ("cell-1", "1000", "800", "1000"),
+            ]
+        );
+        let mut ids: Vec<_> = doc
+            .descendants()
+            .filter_map(|n| n.attribute("id"))
+            .collect();
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count);
+        // Rows are added when charts don't fit
+        let page = Page::new(1, 2)
+            .with_chart(chart())
+            .with_chart(chart())
+            .with_chart(chart());
+        let rects = page.cell_rects(BBox::new([(0.0, 0.0), (100.0, 100.0)]));
+        assert_eq!((rects[2].y_min(), rects[2].y_max()), (50.0, 100.0));
     }
 }
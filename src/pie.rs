@@ -0,0 +1,257 @@
+// pie.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Pie charts
+
+use std::{
+    f32::consts::{FRAC_PI_2, TAU},
+    fmt,
+    fmt::Write,
+};
+
+use pointy::{BBox, Pt};
+
+use crate::{
+    page::Edge,
+    plot::{Scales, Series},
+    text::{Anchor, Escape, Text, Tspan},
+};
+
+/// Pie (or donut) chart
+///
+/// Each labeled value is drawn as a wedge, starting at the top and proceeding
+/// clockwise.  Wedges are given consecutive `plot-N` classes, which are not
+/// shared by other plots, and are listed separately in the legend.  A pie
+/// does not use any axes.
+pub struct Pie<'a> {
+    slices: &'a [(&'a str, f32)],
+    inner: f32,
+}
+
+impl<'a> Pie<'a> {
+    /// Create a new pie chart from labeled values
+    ///
+    /// Negative values are treated as zero.
+    pub fn new(slices: &'a [(&'a str, f32)]) -> Self {
+        Self { slices, inner: 0.0 }
+    }
+
+    /// Set the inner radius to make a donut chart
+    ///
+    /// The radius is a fraction (0 to 1) of the outer radius.
+    pub fn with_inner_radius(mut self, inner: f32) -> Self {
+        self.inner = inner.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Get the total of all slice values
+    fn total(&self) -> f32 {
+        self.slices.iter().map(|(_, v)| v.max(0.0)).sum()
+    }
+
+    /// Display one wedge
+    fn display_wedge(
+        &self,
+        f: &mut dyn Write,
+        center: Pt<f32>,
+        radius: f32,
+        (a0, a1): (f32, f32),
+    ) -> fmt::Result {
+        let inner = radius * self.inner;
+        let mid = (a0 + a1) / 2.0;
+        // Split the arc in two so that neither half exceeds 180 degrees
+        let outer = [a0, mid, a1].map(|a| polar(center, radius, a));
+        let r = radius.round();
+        write!(f, " d='M{} {}", outer[0].x(), outer[0].y())?;
+        write!(f, "A{r} {r} 0 0 1 {} {}", outer[1].x(), outer[1].y())?;
+        write!(f, "A{r} {r} 0 0 1 {} {}", outer[2].x(), outer[2].y())?;
+        if inner > 0.0 {
+            let pts = [a1, mid, a0].map(|a| polar(center, inner, a));
+            let r = inner.round();
+            write!(f, "L{} {}", pts[0].x(), pts[0].y())?;
+            write!(f, "A{r} {r} 0 0 0 {} {}", pts[1].x(), pts[1].y())?;
+            write!(f, "A{r} {r} 0 0 0 {} {}", pts[2].x(), pts[2].y())?;
+        } else {
+            write!(f, "L{} {}", center.x().round(), center.y().round())?;
+        }
+        writeln!(f, "z'/>")
+    }
+
+    /// Display the label of one wedge at its centroid
+    fn display_label(
+        &self,
+        f: &mut dyn Write,
+        label: &str,
+        center: Pt<f32>,
+        radius: f32,
+        (a0, a1): (f32, f32),
+    ) -> fmt::Result {
+        let inner = radius * self.inner;
+        let half = (a1 - a0) / 2.0;
+        let chord = if half > f32::EPSILON {
+            half.sin() / half
+        } else {
+            1.0
+        };
+        let r = 2.0 / 3.0 * (radius.powi(3) - inner.powi(3))
+            / (radius.powi(2) - inner.powi(2))
+            * chord;
+        let pt = polar(center, r, a0 + half);
+        let anchor = if pt.x() >= center.x() {
+            Anchor::Start
+        } else {
+            Anchor::End
+        };
+        let text = Text::new(Edge::Top)
+            .with_anchor(anchor)
+            .with_class_name("pie-label");
+        text.display(f)?;
+        Tspan::new(label)
+            .x(pt.x() as i32)
+            .y(pt.y() as i32)
+            .dy(0.33)
+            .display(f)?;
+        text.display_done(f)
+    }
+
+    /// Get the angles of each wedge
+    fn angles(&self) -> Vec<(f32, f32)> {
+        let total = self.total();
+        let mut angle = -FRAC_PI_2;
+        self.slices
+            .iter()
+            .map(|(_, value)| {
+                let a0 = angle;
+                angle += TAU * value.max(0.0) / total;
+                (a0, angle)
+            })
+            .collect()
+    }
+}
+
+impl Series for Pie<'_> {
+    fn name(&self) -> &str {
+        ""
+    }
+
+    fn color_count(&self) -> usize {
+        self.slices.len()
+    }
+
+    fn legend_labels(&self) -> Vec<&str> {
+        self.slices.iter().map(|(label, _)| *label).collect()
+    }
+
+    fn display_legend_swatch(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        entry: usize,
+        x: i32,
+        y: i32,
+    ) -> fmt::Result {
+        let (x, y) = (x + 15, y - 15);
+        write!(f, "<rect class='plot-{} plot-pie'", num + entry)?;
+        writeln!(f, " x='{x}' y='{y}' width='30' height='30'/>")
+    }
+
+    fn display_legend(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        _note: Option<&str>,
+    ) -> fmt::Result {
+        for (i, (label, _)) in self.slices.iter().enumerate() {
+            writeln!(f, "<div>")?;
+            writeln!(f, "<svg width='20' height='10' viewBox='0 0 60 30'>")?;
+            write!(f, "<rect class='plot-{} plot-pie'", num + i)?;
+            writeln!(f, " x='15' y='0' width='30' height='30'/>")?;
+            writeln!(f, "</svg>")?;
+            writeln!(f, "{}", Escape(label))?;
+            writeln!(f, "</div>")?;
+        }
+        Ok(())
+    }
+
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        rect: BBox<f32>,
+        _scales: &Scales,
+        _stack: &mut Vec<f32>,
+    ) -> fmt::Result {
+        if self.total() <= 0.0 {
+            return Ok(());
+        }
+        let center = Pt::new(rect.x_mid(), rect.y_mid());
+        let radius = rect.x_span().min(rect.y_span()) * 0.45;
+        let angles = self.angles();
+
+        writeln!(f, "<g class='plot-pie'>")?;
+        for (i, angles) in angles.iter().enumerate() {
+            if angles.1 - angles.0 > 0.0 {
+                write!(f, "<path class='plot-{} plot-pie'", num + i)?;
+                self.display_wedge(f, center, radius, *angles)?;
+            }
+        }
+        for ((label, _), angles) in self.slices.iter().zip(angles) {
+            if angles.1 - angles.0 > 0.0 {
+                self.display_label(f, label, center, radius, angles)?;
+            }
+        }
+        writeln!(f, "</g>")
+    }
+}
+
+/// Get a point from polar coordinates, rounded to whole units
+fn polar(center: Pt<f32>, radius: f32, angle: f32) -> Pt<f32> {
+    let x = center.x() + radius * angle.cos();
+    let y = center.y() + radius * angle.sin();
+    Pt::new(x.round(), y.round())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn angles() {
+        let slices = [("A", 1.0), ("B", 3.0), ("C", -2.0)];
+        let angles = Pie::new(&slices).angles();
+        assert_eq!(angles[0].0, -FRAC_PI_2);
+        assert!((angles[0].1 - angles[0].0 - PI / 2.0).abs() < 1e-5);
+        assert!((angles[1].1 - angles[1].0 - PI * 1.5).abs() < 1e-5);
+        assert_eq!(angles[2].0, angles[2].1);
+    }
+
+    #[test]
+    fn slice_classes() {
+        let slices = [("A", 1.0), ("B", 3.0)];
+        let domain = BBox::new([(0.0, 0.0), (1.0, 1.0)]);
+        let plot = crate::Plot::new("Line", &domain, [(0.0, 0.0), (1.0, 1.0)]);
+        let out = crate::Chart::default()
+            .with_pie(Pie::new(&slices))
+            .with_line_plot(plot)
+            .with_legend(crate::Legend::new().inside_top_right())
+            .render();
+        assert!(out.contains("class='plot-0 plot-pie'"));
+        assert!(out.contains("class='plot-1 plot-pie'"));
+        assert!(out.contains("class='plot-2 plot-line'"));
+        assert!(out.contains("class='plot-2 legend-line'"));
+        assert!(!out.contains("plot-1 plot-line"));
+        assert!(out.contains("<marker id='marker-2' class='plot-2'"));
+        // Every slice gets a stylesheet color
+        let slices: Vec<_> = (0..12).map(|i| ("", i as f32 + 1.0)).collect();
+        let out = crate::Chart::default().with_pie(Pie::new(&slices)).render();
+        assert!(out.contains(".plot-11 {"));
+        assert!(out.contains("class='plot-11 plot-pie'"));
+        let svg = crate::Chart::default()
+            .with_pie(Pie::new(&slices))
+            .render_svg();
+        assert!(svg.contains(".plot-11 {"));
+    }
+}
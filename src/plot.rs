@@ -9,13 +9,405 @@ use std::{fmt, fmt::Write};
 
 use pointy::{BBox, Pt};
 
-use crate::scale::Numeric;
+use crate::{
+    band::display_band,
+    data, decimate,
+    marker::Marker,
+    page::Edge,
+    scale::Numeric,
+    text::{Coord, Escape, Label, LabelPoint, Link, Text, Tspan},
+    trend::{Fit, TrendLabel},
+};
 
+/// Kind of plot to render
 #[derive(Copy, Clone, Debug)]
 pub(crate) enum PlotKind {
     Area,
+    /// Bar in a slot, which may be grouped or stacked
+    Bar(BarSlot),
+    HBar,
     Line,
     Scatter,
+    /// Smooth curve with tension
+    Smooth(f32),
+    Step(Step),
+    /// Area stacked on the previous plots with the same stack number
+    StackedArea(usize),
+}
+
+/// Position of a bar within the spacing between `X` values
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct BarSlot {
+    /// Stack number, for stacked bars
+    stack: Option<usize>,
+    /// Index of bar within a group
+    index: usize,
+    /// Number of bars in a group
+    count: usize,
+    /// Fraction of the spacing filled by a group
+    fill: f32,
+    /// Fraction of each bar's share of a group filled by the bar
+    bar_fill: f32,
+}
+
+impl Default for BarSlot {
+    fn default() -> Self {
+        Self {
+            stack: None,
+            index: 0,
+            count: 1,
+            fill: BAR_FILL,
+            bar_fill: 1.0,
+        }
+    }
+}
+
+impl BarSlot {
+    /// Create a slot for a stacked bar
+    pub(crate) fn stacked(stack: usize, fill: f32) -> Self {
+        Self {
+            stack: Some(stack),
+            fill,
+            ..Self::default()
+        }
+    }
+
+    /// Create a slot for one bar in a group
+    pub(crate) fn grouped(
+        index: usize,
+        count: usize,
+        fill: f32,
+        bar_fill: f32,
+    ) -> Self {
+        Self {
+            index,
+            count: count.max(1),
+            fill,
+            bar_fill,
+            ..Self::default()
+        }
+    }
+
+    /// Get the bar width and offset from the `X` value (in SVG units)
+    pub(crate) fn geometry(&self, spacing: i32) -> (i32, i32) {
+        let group = spacing.abs() as f32 * self.fill;
+        let share = group / self.count as f32;
+        let width = (share * self.bar_fill).round() as i32;
+        let offset = share * (self.index as f32 + 0.5) - group / 2.0;
+        (width, offset.round() as i32)
+    }
+}
+
+/// Half width of error bar caps
+const ERROR_CAP: i32 = 10;
+
+/// Radius of the hover area around points with tooltips
+const TOOLTIP_RADIUS: i32 = 15;
+
+/// Function to format the tooltip of a data point
+type Tooltip<'a> = Box<dyn Fn(usize, Pt<f32>) -> String + 'a>;
+
+/// Step position for step plots
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Step {
+    /// Step to the next `Y` value before moving along `X`
+    Pre,
+    /// Step to the next `Y` value after moving along `X`
+    #[default]
+    Post,
+}
+
+/// Handling of non-finite data points
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum NonFinite {
+    /// Drop points, connecting their neighbors
+    #[default]
+    Skip,
+    /// Break the path, leaving a gap
+    Gap,
+}
+
+/// Get the style property colored by a series, for an element kind
+fn color_property(kind: &str) -> Option<&'static str> {
+    match kind {
+        "plot-line" | "legend-line" | "plot-error" | "trend" => Some("stroke"),
+        "plot-area" | "plot-bar" | "plot-ribbon" | "legend-ribbon" => {
+            Some("fill")
+        }
+        _ => None,
+    }
+}
+
+/// Fraction of the spacing between points filled by a bar
+pub(crate) const BAR_FILL: f32 = 0.8;
+
+/// Series of data which can be rendered in a `Chart`
+pub(crate) trait Series {
+    /// Get the series name
+    fn name(&self) -> &str;
+
+    /// Get the stack number, for stacked series
+    fn stack(&self) -> Option<usize> {
+        None
+    }
+
+    /// Get the drawing order
+    fn z(&self) -> i32 {
+        0
+    }
+
+    /// Get the legend entry link
+    fn legend_link(&self) -> Option<&Link> {
+        None
+    }
+
+    /// Get the data points, for embedding
+    fn points(&self) -> Option<&[Pt<f32>]> {
+        None
+    }
+
+    /// Check if the series is clipped to the plot area
+    fn is_clipped(&self) -> bool {
+        true
+    }
+
+    /// Check if the series has no data to display
+    fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Get the color override for the series
+    fn color(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the marker override for the series
+    fn marker(&self) -> Option<Marker<'_>> {
+        None
+    }
+
+    /// Get the marker size override for the series
+    fn marker_size(&self) -> Option<f32> {
+        None
+    }
+
+    /// Check if the series draws markers at its data points
+    fn has_markers(&self) -> bool {
+        false
+    }
+
+    /// Get the user class name for the series
+    fn class(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the stroke width override for the series
+    fn stroke_width(&self) -> Option<f32> {
+        None
+    }
+
+    /// Get the stroke dash pattern for the series
+    fn dash(&self) -> Option<&[f32]> {
+        None
+    }
+
+    /// Display `class` and `style` attributes for an element of the series
+    ///
+    /// A color override sets the `--color` property used by the bundled
+    /// stylesheet, as well as the `stroke` or `fill` of the element, so that
+    /// it works with any stylesheet.  Stroke overrides are also added as
+    /// inline style properties.  Legend lines of series with markers have a
+    /// marker in the middle.
+    fn display_class(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        kind: &str,
+    ) -> fmt::Result {
+        write!(f, " class='plot-{num} {kind}")?;
+        if let Some(class) = self.class() {
+            write!(f, " {}", Escape(class))?;
+        }
+        write!(f, "'")?;
+        let mut style = vec![];
+        if let Some(color) = self.color() {
+            style.push(format!("--color: {}", Escape(color)));
+            if let Some(property) = color_property(kind) {
+                style.push(format!("{property}: {}", Escape(color)));
+            }
+        }
+        if kind == "legend-line" && self.has_markers() {
+            style.push("marker-mid: var(--marker)".to_string());
+        }
+        if let Some(width) = self.stroke_width() {
+            style.push(format!("stroke-width: {width}px"));
+        }
+        if let Some(dash) = self.dash() {
+            let dash: Vec<String> =
+                dash.iter().map(|d| d.to_string()).collect();
+            style.push(format!("stroke-dasharray: {}", dash.join(" ")));
+        }
+        if !style.is_empty() {
+            write!(f, " style='{}'", style.join("; "))?;
+        }
+        Ok(())
+    }
+
+    /// Get the number of `plot-N` color classes used by the series
+    fn color_count(&self) -> usize {
+        1
+    }
+
+    /// Get the number of data points, for estimating output size
+    fn point_count(&self) -> usize {
+        0
+    }
+
+    /// Get the domain of the series, if it has one
+    fn domain(&self) -> Option<&BBox<f32>> {
+        None
+    }
+
+    /// Get the extent of the data, if the domain is automatic
+    fn auto_extent(&self) -> Option<BBox<f32>> {
+        None
+    }
+
+    /// Set the automatic domain, computed by a chart
+    fn set_auto_domain(&mut self, _domain: BBox<f32>) {}
+
+    /// Get the ids of the `X` and `Y` axes the series is bound to
+    fn axes(&self) -> (Option<&str>, Option<&str>) {
+        (None, None)
+    }
+
+    /// Display the legend entry for the series
+    ///
+    /// The `note` annotates the entry, such as with the bound axis.
+    fn display_legend(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        note: Option<&str>,
+    ) -> fmt::Result {
+        if self.is_empty() {
+            writeln!(f, "<div class='legend-empty'>")?;
+        } else {
+            writeln!(f, "<div>")?;
+        }
+        writeln!(f, "<svg width='20' height='10' viewBox='0 0 60 30'>")?;
+        self.display_legend_fill(f, num, 0, 15)?;
+        write!(f, "<path")?;
+        self.display_class(f, num, "legend-line")?;
+        writeln!(f, " d='M0 15h30h30'/>")?;
+        writeln!(f, "</svg>")?;
+        match self.legend_link() {
+            Some(link) => {
+                link.display(f)?;
+                write!(f, "{}", Escape(self.name()))?;
+                link.display_done(f)?;
+            }
+            None => writeln!(f, "{}", Escape(self.name()))?,
+        }
+        display_legend_note(f, note)?;
+        display_legend_note(f, self.legend_detail().as_deref())?;
+        writeln!(f, "</div>")
+    }
+
+    /// Check if the series is drawn in order, but has unsorted `X` values
+    fn is_unsorted(&self) -> bool {
+        false
+    }
+
+    /// Get extra detail for the legend entry
+    fn legend_detail(&self) -> Option<String> {
+        None
+    }
+
+    /// Check if the series is listed in the legend
+    fn in_legend(&self) -> bool {
+        true
+    }
+
+    /// Get the labels of legend entries for the series
+    fn legend_labels(&self) -> Vec<&str> {
+        vec![self.name()]
+    }
+
+    /// Display the SVG swatch of a legend entry, centered vertically on `y`
+    fn display_legend_swatch(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        _entry: usize,
+        x: i32,
+        y: i32,
+    ) -> fmt::Result {
+        self.display_legend_fill(f, num, x, y)?;
+        write!(f, "<path")?;
+        self.display_class(f, num, "legend-line")?;
+        writeln!(f, " d='M{x} {y}h30h30'/>")
+    }
+
+    /// Display a fill behind a legend line swatch, centered vertically on `y`
+    fn display_legend_fill(
+        &self,
+        _f: &mut dyn Write,
+        _num: usize,
+        _x: i32,
+        _y: i32,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    /// Display value labels for the series
+    ///
+    /// Only labels which match `clipped` are displayed, so that labels can be
+    /// drawn inside or outside of the chart clip area.
+    fn display_labels(
+        &self,
+        _f: &mut dyn Write,
+        _num: usize,
+        _rect: BBox<f32>,
+        _scales: &Scales,
+        _clipped: bool,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    /// Display the series
+    ///
+    /// The `scales` override the domain of the series, and the `stack`
+    /// contains cumulative `Y` values from previous series in the same stack.
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        rect: BBox<f32>,
+        scales: &Scales,
+        stack: &mut Vec<f32>,
+    ) -> fmt::Result;
+}
+
+/// Display a note for a legend entry
+pub(crate) fn display_legend_note(
+    f: &mut dyn Write,
+    note: Option<&str>,
+) -> fmt::Result {
+    match note {
+        Some(note) => {
+            writeln!(f, "<span class='legend-note'>{}</span>", Escape(note))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Domain of a plot
+enum Domain<'a> {
+    /// Domain supplied with the plot
+    Fixed(&'a BBox<f32>),
+    /// Domain computed by a chart, from all automatic plots
+    Auto(BBox<f32>),
 }
 
 /// Generic plot
@@ -23,61 +415,536 @@ pub(crate) enum PlotKind {
 /// The type of plot that's rendered is determined at a later step.
 pub struct Plot<'a> {
     name: &'a str,
-    domain: &'a BBox<f32>,
-    data: &'a mut dyn Iterator<Item = Pt<f32>>,
+    domain: Domain<'a>,
+    data: Vec<Pt<f32>>,
+    kind: PlotKind,
+    y_error: Option<Vec<(f32, f32)>>,
+    x_axis: Option<&'a str>,
+    y_axis: Option<&'a str>,
+    non_finite: NonFinite,
+    color: Option<&'a str>,
+    class: Option<&'a str>,
+    marker: Option<Marker<'a>>,
+    marker_size: Option<f32>,
+    markers: Option<usize>,
+    last_marker: bool,
+    trend: Option<TrendLabel>,
+    ribbon: Vec<(Pt<f32>, Pt<f32>)>,
+    max_points: Option<usize>,
+    baseline: f32,
+    stroke_width: Option<f32>,
+    dash: Option<Vec<f32>>,
+    in_legend: bool,
+    legend_link: Option<Link>,
+    tooltips: Option<Tooltip<'a>>,
+    z: i32,
+    clipped: bool,
+    labels: Option<Label>,
+    label_texts: Option<Vec<String>>,
 }
 
 impl<'a> Plot<'a> {
-    pub fn new(
+    /// Create a new plot
+    ///
+    /// The data can be any iterable of points, such as `(x, y)` tuples.
+    pub fn new<I, P>(name: &'a str, domain: &'a BBox<f32>, data: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Pt<f32>>,
+    {
+        let data = data.into_iter().map(Into::into).collect();
+        Self::with_domain(name, Domain::Fixed(domain), data)
+    }
+
+    /// Create a new plot with an automatic domain
+    ///
+    /// When added to a `Chart`, the domain is the union of all automatic
+    /// plots, shared with `Horizontal::auto` and `Vertical::auto` axes.
+    pub fn new_auto<I, P>(name: &'a str, data: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Pt<f32>>,
+    {
+        let data: Vec<_> = data.into_iter().map(Into::into).collect();
+        let domain = Domain::Auto(data::domain(&data));
+        Self::with_domain(name, domain, data)
+    }
+
+    /// Create a new plot with a domain
+    fn with_domain(
         name: &'a str,
-        domain: &'a BBox<f32>,
-        data: &'a mut dyn Iterator<Item = Pt<f32>>,
+        domain: Domain<'a>,
+        data: Vec<Pt<f32>>,
     ) -> Self {
-        Self { name, domain, data }
+        Self {
+            name,
+            domain,
+            data,
+            kind: PlotKind::Line,
+            y_error: None,
+            x_axis: None,
+            y_axis: None,
+            non_finite: NonFinite::Skip,
+            color: None,
+            class: None,
+            marker: None,
+            marker_size: None,
+            markers: None,
+            last_marker: false,
+            trend: None,
+            ribbon: vec![],
+            max_points: None,
+            baseline: 0.0,
+            stroke_width: None,
+            dash: None,
+            in_legend: true,
+            legend_link: None,
+            tooltips: None,
+            z: 0,
+            clipped: true,
+            labels: None,
+            label_texts: None,
+        }
     }
 
-    fn display_area(
-        &mut self,
+    /// Get the domain bounding box
+    fn bbox(&self) -> &BBox<f32> {
+        match &self.domain {
+            Domain::Fixed(domain) => domain,
+            Domain::Auto(domain) => domain,
+        }
+    }
+
+    /// Bind the plot to a horizontal axis by id
+    ///
+    /// `X` values are mapped through the scale of that axis instead of the
+    /// plot's domain.
+    pub fn with_x_axis(mut self, id: &'a str) -> Self {
+        self.x_axis = Some(id);
+        self
+    }
+
+    /// Bind the plot to a vertical axis by id
+    ///
+    /// `Y` values are mapped through the scale of that axis instead of the
+    /// plot's domain.
+    pub fn with_y_axis(mut self, id: &'a str) -> Self {
+        self.y_axis = Some(id);
+        self
+    }
+
+    /// Set the plot color, overriding the stylesheet
+    ///
+    /// Any CSS color value can be used, such as `#d62728`.
+    pub fn with_color(mut self, color: &'a str) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Add a class name, for styling the plot independent of its order
+    ///
+    /// The class is added alongside the `plot-N` class.
+    pub fn with_class(mut self, class: &'a str) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    /// Set the marker shape, instead of one assigned by plot order
+    pub fn with_marker(mut self, marker: Marker<'a>) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// Set the marker width and height
+    ///
+    /// Sizes are scaled by the stroke width; the default is 5.  Sizes which
+    /// are not positive are ignored.
+    pub fn with_marker_size(mut self, size: f32) -> Self {
+        if size > 0.0 && size.is_finite() {
+            self.marker_size = Some(size);
+        }
+        self
+    }
+
+    /// Set the stroke width of lines
+    ///
+    /// Widths which are negative or not finite are ignored.
+    pub fn with_stroke_width(mut self, width: f32) -> Self {
+        if width >= 0.0 && width.is_finite() {
+            self.stroke_width = Some(width);
+        }
+        self
+    }
+
+    /// Set the dash pattern of lines
+    ///
+    /// The pattern alternates lengths of dashes and gaps, as with the SVG
+    /// `stroke-dasharray` property.  An empty pattern draws a solid line.
+    pub fn with_dash(mut self, dash: &[f32]) -> Self {
+        self.dash = if dash.is_empty() {
+            None
+        } else {
+            Some(dash.to_vec())
+        };
+        self
+    }
+
+    /// Set the drawing order
+    ///
+    /// Plots with a higher `z` are drawn on top, and plots with equal `z` are
+    /// drawn in the order they were added.  Colors and legend order are not
+    /// affected.  Stacked plots should have equal `z`.
+    pub fn with_z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+
+    /// Draw the plot outside of the chart clip area
+    ///
+    /// This keeps markers of points on the domain edges from being cut off.
+    pub fn without_clip(mut self) -> Self {
+        self.clipped = false;
+        self
+    }
+
+    /// Label each data point with its value
+    ///
+    /// For bar plots, the label point selects a position along the bar.
+    pub fn with_point_labels(mut self, label: Label) -> Self {
+        self.labels = Some(label);
+        self
+    }
+
+    /// Label data points with custom text instead of values
+    ///
+    /// Each text is matched by position with a data point.  If point labels
+    /// were not set, the default `Label` is used.
+    pub fn with_point_texts<I, T>(mut self, texts: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.label_texts = Some(texts.into_iter().map(Into::into).collect());
+        self.labels = self.labels.or_else(|| Some(Label::new()));
+        self
+    }
+
+    /// Exclude the plot from the legend
+    ///
+    /// The plot still uses its `plot-N` class, so colors of other plots are
+    /// not shifted.
+    pub fn hide_from_legend(mut self) -> Self {
+        self.in_legend = false;
+        self
+    }
+
+    /// Link the legend entry to a URL
+    pub fn with_legend_href<L: Into<Link>>(mut self, link: L) -> Self {
+        self.legend_link = Some(link.into());
+        self
+    }
+
+    /// Add a hover tooltip to each marker
+    ///
+    /// The function is called with the index and value of each data point.
+    /// Markers are drawn as separate elements, each with a `title`, so this
+    /// should be avoided for large data sets.
+    pub fn with_tooltips<T>(mut self, tooltip: T) -> Self
+    where
+        T: Fn(usize, Pt<f32>) -> String + 'a,
+    {
+        self.tooltips = Some(Box::new(tooltip));
+        self
+    }
+
+    /// Draw markers at data points of line plots
+    pub fn with_markers(mut self) -> Self {
+        self.markers = Some(self.markers.unwrap_or(1));
+        self
+    }
+
+    /// Draw markers at every `n`th data point of line plots
+    ///
+    /// This is useful for dense series, where markers would overlap.
+    pub fn with_marker_every(mut self, n: usize) -> Self {
+        self.markers = Some(n.max(1));
+        self
+    }
+
+    /// Draw a marker at the last data point of line and area plots
+    ///
+    /// This is useful for sparklines, to highlight the current value.
+    pub fn with_last_marker(mut self) -> Self {
+        self.last_marker = true;
+        self
+    }
+
+    /// Sort data by `X` value
+    ///
+    /// The sort is stable, so points with equal `X` values keep their order.
+    /// Error bars, point texts and ribbons are reordered to match.
+    pub fn sorted_by_x(mut self) -> Self {
+        let mut order: Vec<usize> = (0..self.data.len()).collect();
+        order.sort_by(|a, b| self.data[*a].x().total_cmp(&self.data[*b].x()));
+        permute(&mut self.data, &order);
+        if let Some(err) = &mut self.y_error {
+            permute(err, &order);
+        }
+        if let Some(texts) = &mut self.label_texts {
+            permute(texts, &order);
+        }
+        permute(&mut self.ribbon, &order);
+        self
+    }
+
+    /// Draw a ribbon between lower and upper bounds, behind the plot
+    ///
+    /// Bounds are matched by position.  Points with a non-finite bound leave
+    /// a gap in the ribbon.
+    pub fn with_ribbon<L, U, P>(mut self, lower: L, upper: U) -> Self
+    where
+        L: IntoIterator<Item = P>,
+        U: IntoIterator<Item = P>,
+        P: Into<Pt<f32>>,
+    {
+        let lower = lower.into_iter().map(Into::into);
+        let upper = upper.into_iter().map(Into::into);
+        self.ribbon = lower.zip(upper).collect();
+        self
+    }
+
+    /// Draw a ribbon around the data, with a half width for each point
+    pub fn with_ribbon_widths<I>(mut self, half_widths: I) -> Self
+    where
+        I: IntoIterator<Item = f32>,
+    {
+        self.ribbon = self
+            .data
+            .iter()
+            .zip(half_widths)
+            .map(|(pt, w)| {
+                (Pt::new(pt.x(), pt.y() - w), Pt::new(pt.x(), pt.y() + w))
+            })
+            .collect();
+        self
+    }
+
+    /// Display the ribbon, split into runs of finite bounds
+    fn display_ribbon(
+        &self,
         f: &mut dyn Write,
         num: usize,
-        rect: BBox<f32>,
+        map: &Mapping,
     ) -> fmt::Result {
-        let mut iter = self.data.peekable();
+        let finite = |pt: &Pt<f32>| pt.x().is_finite() && pt.y().is_finite();
+        let runs = self.ribbon.split(|(lo, hi)| !finite(lo) || !finite(hi));
+        for run in runs.filter(|run| !run.is_empty()) {
+            let (lo, hi): (Vec<_>, Vec<_>) = run.iter().copied().unzip();
+            write!(f, "<path")?;
+            self.display_class(f, num, "plot-ribbon")?;
+            display_band(f, map, &lo, &hi)?;
+            writeln!(f, "/>")?;
+        }
+        Ok(())
+    }
 
-        write!(f, "<path class='plot-{num} plot-area' d='")?;
+    /// Draw a linear trend line, fit to the data
+    ///
+    /// The line extends across the `X` domain, and has a `trend` class.  The
+    /// fit can be shown in the legend, after the plot name.
+    pub fn with_trendline(mut self, label: TrendLabel) -> Self {
+        self.trend = Some(label);
+        self
+    }
 
-        if let Some(pt) = iter.peek() {
-            let x = x_map(self.domain, pt.x(), rect);
-            let y = y_map(self.domain, 0.0, rect);
-            write!(f, "M{x} {y}")?;
+    /// Display the trend line
+    fn display_trend(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+    ) -> fmt::Result {
+        let fit = match (self.trend, Fit::linear(self.data.iter())) {
+            (Some(_), Some(fit)) => fit,
+            _ => return Ok(()),
+        };
+        let d = self.bbox();
+        let [p0, p1] = fit.ends((d.x_min(), d.x_max()), (d.y_min(), d.y_max()));
+        write!(f, "<path")?;
+        self.display_class(f, num, "trend")?;
+        let (x0, y0) = map.pt(&p0.into());
+        let (x1, y1) = map.pt(&p1.into());
+        write!(f, " d='M")?;
+        write_pair(f, x0, y0)?;
+        write!(f, "l")?;
+        write_pair(f, x1.delta(x0), y1.delta(y0))?;
+        writeln!(f, "'/>")
+    }
+
+    /// Set the `Y` value that area plots are filled to (default 0.0)
+    ///
+    /// A baseline outside of the visible range is clamped to its edge.
+    pub fn with_baseline(mut self, y: f32) -> Self {
+        self.baseline = y;
+        self
+    }
+
+    /// Downsample to a maximum number of points before rendering
+    ///
+    /// Line and area plots keep the points which best preserve the shape
+    /// (Largest-Triangle-Three-Buckets), while scatter plots keep every
+    /// `n`th point.  The first and last points are always kept.  Bar and
+    /// stacked area plots are not downsampled.
+    pub fn with_max_points(mut self, max: usize) -> Self {
+        self.max_points = Some(max);
+        self
+    }
+
+    /// Downsample segments to the maximum number of points
+    ///
+    /// Each segment gets a share of the maximum, by its number of points.
+    fn decimate(&self, segs: Vec<Vec<Pt<f32>>>) -> Vec<Vec<Pt<f32>>> {
+        use PlotKind::*;
+
+        let max = match (self.kind, self.max_points) {
+            (Area | Line | Scatter | Smooth(_) | Step(_), Some(max)) => max,
+            _ => return segs,
+        };
+        let total: usize = segs.iter().map(|seg| seg.len()).sum();
+        if total <= max {
+            return segs;
         }
+        segs.into_iter()
+            .map(|seg| {
+                let share = (max * seg.len() / total).max(2);
+                match self.kind {
+                    Scatter => decimate::stride(&seg, share),
+                    _ => decimate::lttb(&seg, share),
+                }
+            })
+            .collect()
+    }
 
-        while let Some(pt) = iter.next() {
-            let x = x_map(self.domain, pt.x(), rect);
-            let y = y_map(self.domain, pt.y(), rect);
-            write!(f, " {x} {y}")?;
+    /// Skip non-finite (`NaN` or infinite) points, connecting neighbors
+    ///
+    /// This is the default.
+    pub fn skip_non_finite(mut self) -> Self {
+        self.non_finite = NonFinite::Skip;
+        self
+    }
 
-            if iter.peek().is_none() {
-                let x = x_map(self.domain, pt.x(), rect);
-                let y = y_map(self.domain, 0.0, rect);
-                write!(f, " {x} {y}")?;
+    /// Leave a gap in lines and areas at non-finite points
+    pub fn gap_on_non_finite(mut self) -> Self {
+        self.non_finite = NonFinite::Gap;
+        self
+    }
+
+    /// Split points into segments of finite points
+    fn segments(&self, pts: &[Pt<f32>]) -> Vec<Vec<Pt<f32>>> {
+        let finite = |pt: &Pt<f32>| pt.x().is_finite() && pt.y().is_finite();
+        match self.non_finite {
+            NonFinite::Skip => {
+                vec![pts.iter().copied().filter(finite).collect()]
             }
+            NonFinite::Gap => pts
+                .split(|pt| !finite(pt))
+                .filter(|seg| !seg.is_empty())
+                .map(|seg| seg.to_vec())
+                .collect(),
+        }
+    }
+
+    /// Add symmetric `Y` error bars
+    ///
+    /// Each error value is matched by position with a data point, and a
+    /// whisker is drawn from `y - err` to `y + err`.
+    pub fn with_y_error<I>(self, err: I) -> Self
+    where
+        I: IntoIterator<Item = f32>,
+    {
+        self.with_y_error_bounds(err.into_iter().map(|e| (e, e)))
+    }
+
+    /// Add asymmetric `Y` error bars
+    ///
+    /// Each `(low, high)` pair is matched by position with a data point, and
+    /// a whisker is drawn from `y - low` to `y + high`.
+    pub fn with_y_error_bounds<I>(mut self, err: I) -> Self
+    where
+        I: IntoIterator<Item = (f32, f32)>,
+    {
+        self.y_error = Some(err.into_iter().collect());
+        self
+    }
+
+    /// Set the kind of plot to render
+    pub(crate) fn with_kind(mut self, kind: PlotKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    fn display_area(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+        segs: &[Vec<Pt<f32>>],
+    ) -> fmt::Result {
+        write!(f, "<path")?;
+        self.display_class(f, num, "plot-area")?;
+        write!(f, " d='")?;
+
+        // Each segment is closed down to the baseline
+        let rect = map.rect();
+        let base = map.y_pos(self.baseline).clamp(rect.y_min(), rect.y_max());
+        let base = map.coord(base);
+        for (j, seg) in segs.iter().enumerate() {
+            let (first, last) = match (seg.first(), seg.last()) {
+                (Some(first), Some(last)) => (first, last),
+                _ => continue,
+            };
+            let x = map.coord(map.x_pos(first.x()));
+            if j > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "M{x} {base}l")?;
+            let mut prev = (x, base);
+            for pt in seg {
+                let (x, y) = map.pt(pt);
+                write_pair(f, x.delta(prev.0), y.delta(prev.1))?;
+                f.write_char(' ')?;
+                prev = (x, y);
+            }
+            let x = map.coord(map.x_pos(last.x()));
+            write!(f, "{} {}", x.delta(prev.0), base.delta(prev.1))?;
         }
 
         writeln!(f, "' />")
     }
 
-    fn display_line(
-        &mut self,
+    fn display_stacked_area(
+        &self,
         f: &mut dyn Write,
         num: usize,
-        rect: BBox<f32>,
+        map: &Mapping,
+        pts: &[Pt<f32>],
+        base: &mut Vec<f32>,
     ) -> fmt::Result {
-        write!(f, "<path class='plot-{num} plot-line' d='")?;
+        base.resize(pts.len(), 0.0);
+        let tops: Vec<f32> = pts
+            .iter()
+            .zip(base.iter())
+            .map(|(pt, b)| b + pt.y())
+            .collect();
+        let (y_min, y_max) = (map.rect().y_min(), map.rect().y_max());
 
-        for (i, pt) in self.data.enumerate() {
-            let x = x_map(self.domain, pt.x(), rect);
-            let y = y_map(self.domain, pt.y(), rect);
+        write!(f, "<path")?;
+        self.display_class(f, num, "plot-area")?;
+        write!(f, " d='")?;
+
+        for (i, (pt, top)) in pts.iter().zip(tops.iter()).enumerate() {
+            let x = map.coord(map.x_pos(pt.x()));
+            let y = map.coord(map.y_pos(*top).clamp(y_min, y_max));
 
             if i == 0 {
                 write!(f, "M{x} {y}")?;
@@ -85,75 +952,1299 @@ impl<'a> Plot<'a> {
                 write!(f, " {x} {y}")?;
             }
         }
+        for (pt, b) in pts.iter().zip(base.iter()).rev() {
+            let x = map.coord(map.x_pos(pt.x()));
+            let y = map.coord(map.y_pos(*b).clamp(y_min, y_max));
+            write!(f, " {x} {y}")?;
+        }
+
+        *base = tops;
+
+        writeln!(f, "' />")
+    }
+
+    /// Get the bar width and offset from the `X` value (in SVG units)
+    fn bar_geometry(
+        &self,
+        map: &Mapping,
+        pts: &[Pt<f32>],
+        slot: BarSlot,
+    ) -> (i32, i32) {
+        let spacing = min_spacing(pts.iter().map(|pt| pt.x()))
+            .unwrap_or_else(|| self.bbox().x_span());
+        let x0 = map.x_map(self.bbox().x_min());
+        let x1 = map.x_map(self.bbox().x_min() + spacing);
+        slot.geometry(x1 - x0)
+    }
+
+    fn display_bar(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+        pts: &[Pt<f32>],
+        slot: BarSlot,
+        stack: &mut Vec<f32>,
+    ) -> fmt::Result {
+        let (width, offset) = self.bar_geometry(map, pts, slot);
+        if slot.stack.is_some() {
+            stack.resize(pts.len(), 0.0);
+        }
+
+        write!(f, "<g")?;
+        self.display_class(f, num, "plot-bar")?;
+        writeln!(f, ">")?;
+
+        for (i, pt) in pts.iter().enumerate() {
+            let x = map.x_map(pt.x()) + offset - width / 2;
+            // Stacked bars start from the top of the previous bar
+            let (base, top) = match slot.stack {
+                Some(_) => (stack[i], stack[i] + pt.y()),
+                None => (0.0, pt.y()),
+            };
+            if slot.stack.is_some() {
+                stack[i] = top;
+            }
+            let (y, base) = (map.y_map(top), map.y_map(base));
+            let (y, height) = (y.min(base), (y - base).abs());
+            write!(f, "<rect x='{x}' y='{y}'")?;
+            writeln!(f, " width='{width}' height='{height}'/>")?;
+        }
+
+        writeln!(f, "</g>")
+    }
+
+    fn display_hbar(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+        pts: &[Pt<f32>],
+    ) -> fmt::Result {
+        let spacing = min_spacing(pts.iter().map(|pt| pt.y()))
+            .unwrap_or_else(|| self.bbox().y_span());
+        let y0 = map.y_map(self.bbox().y_min());
+        let y1 = map.y_map(self.bbox().y_min() + spacing);
+        let height = ((y1 - y0).abs() as f32 * BAR_FILL).round() as i32;
+        let (x_min, x_max) =
+            (map.rect().x_min() as i32, map.rect().x_max() as i32);
+        let base = map.x_map(0.0).clamp(x_min, x_max);
+
+        write!(f, "<g")?;
+        self.display_class(f, num, "plot-bar")?;
+        writeln!(f, ">")?;
+
+        for pt in pts {
+            let x = map.x_map(pt.x()).clamp(x_min, x_max);
+            let y = map.y_map(pt.y()) - height / 2;
+            let (x, width) = (x.min(base), (x - base).abs());
+            write!(f, "<rect x='{x}' y='{y}'")?;
+            writeln!(f, " width='{width}' height='{height}'/>")?;
+        }
+
+        writeln!(f, "</g>")
+    }
+
+    fn display_line(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+        segs: &[Vec<Pt<f32>>],
+    ) -> fmt::Result {
+        write!(f, "<path")?;
+        self.display_class(f, num, "plot-line")?;
+        write!(f, " d='")?;
+
+        // Each segment starts with an absolute move, then relative lines
+        for (j, seg) in segs.iter().enumerate() {
+            let mut prev = None;
+            for (i, pt) in seg.iter().enumerate() {
+                let (x, y) = map.pt(pt);
+
+                match prev {
+                    None => {
+                        if j > 0 {
+                            write!(f, " ")?;
+                        }
+                        write!(f, "M{x} {y}")?;
+                    }
+                    Some((px, py)) => {
+                        let sep = if i == 1 { "l" } else { " " };
+                        f.write_str(sep)?;
+                        write_pair(f, x.delta(px), y.delta(py))?;
+                    }
+                }
+                prev = Some((x, y));
+            }
+        }
         writeln!(f, "'/>")
     }
 
-    fn display_scatter(
-        &mut self,
+    fn display_smooth(
+        &self,
         f: &mut dyn Write,
         num: usize,
-        rect: BBox<f32>,
+        map: &Mapping,
+        segs: &[Vec<Pt<f32>>],
+        tension: f32,
     ) -> fmt::Result {
-        write!(f, "<path class='plot-{num} plot-scatter' d='")?;
+        write!(f, "<path")?;
+        self.display_class(f, num, "plot-line")?;
+        write!(f, " d='")?;
+        for (j, seg) in segs.iter().enumerate() {
+            if j > 0 {
+                write!(f, " ")?;
+            }
+            self.display_smooth_segment(f, map, seg, tension)?;
+        }
+        writeln!(f, "'/>")
+    }
 
-        for (i, pt) in self.data.enumerate() {
-            let x = x_map(self.domain, pt.x(), rect);
-            let y = y_map(self.domain, pt.y(), rect);
+    /// Display one segment of a smooth line as a subpath
+    fn display_smooth_segment(
+        &self,
+        f: &mut dyn Write,
+        map: &Mapping,
+        pts: &[Pt<f32>],
+        tension: f32,
+    ) -> fmt::Result {
+        let pts: Vec<Pt<f32>> = pts
+            .iter()
+            .map(|pt| {
+                let (x, y) = map.pt(pt);
+                Pt::new(x.value(), y.value())
+            })
+            .collect();
+        let scale = (1.0 - tension.clamp(0.0, 1.0)) / 6.0;
+        let rect = map.rect();
+        // Clamp control points so the curve stays within the rect
+        let clamp = |pt: Pt<f32>| {
+            let x = map.coord(pt.x().clamp(rect.x_min(), rect.x_max()));
+            let y = map.coord(pt.y().clamp(rect.y_min(), rect.y_max()));
+            (x, y)
+        };
 
-            if i == 0 {
-                write!(f, "M{x} {y}")?;
-            } else {
+        let coord = |pt: Pt<f32>| (map.coord(pt.x()), map.coord(pt.y()));
+
+        if let Some(pt) = pts.first() {
+            let (x, y) = coord(*pt);
+            write!(f, "M{x} {y}")?;
+        }
+        if pts.len() < 3 {
+            for pt in pts.iter().skip(1) {
+                let (x, y) = coord(*pt);
                 write!(f, " {x} {y}")?;
             }
+        } else {
+            for i in 1..pts.len() {
+                let p0 = pts[i.saturating_sub(2)];
+                let p1 = pts[i - 1];
+                let p2 = pts[i];
+                let p3 = pts[(i + 1).min(pts.len() - 1)];
+                let (x1, y1) = clamp(p1 + (p2 - p0) * scale);
+                let (x2, y2) = clamp(p2 - (p3 - p1) * scale);
+                let (x, y) = coord(p2);
+                write!(f, "C{x1} {y1} {x2} {y2} {x} {y}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn display_step(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+        segs: &[Vec<Pt<f32>>],
+        step: Step,
+    ) -> fmt::Result {
+        write!(f, "<path")?;
+        self.display_class(f, num, "plot-line")?;
+        write!(f, " d='")?;
+
+        for (j, seg) in segs.iter().enumerate() {
+            for (i, pt) in seg.iter().enumerate() {
+                let (x, y) = map.pt(pt);
+
+                if i == 0 {
+                    if j > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "M{x} {y}")?;
+                } else {
+                    match step {
+                        Step::Pre => write!(f, "V{y}H{x}")?,
+                        Step::Post => write!(f, "H{x}V{y}")?,
+                    }
+                }
+            }
+        }
+        writeln!(f, "'/>")
+    }
+
+    fn display_y_error(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+        pts: &[Pt<f32>],
+        err: &[(f32, f32)],
+    ) -> fmt::Result {
+        write!(f, "<path")?;
+        self.display_class(f, num, "plot-error")?;
+        write!(f, " d='")?;
+
+        let finite = pts.iter().zip(err).filter(|(pt, (low, high))| {
+            (pt.y() - low).is_finite() && (pt.y() + high).is_finite()
+        });
+        for (i, (pt, (low, high))) in finite.enumerate() {
+            let x = map.x_map(pt.x());
+            let y0 = map.y_map(pt.y() - low);
+            let y1 = map.y_map(pt.y() + high);
+            let xc = x - ERROR_CAP;
+            let cap = ERROR_CAP * 2;
+
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "M{x} {y0}V{y1}M{xc} {y0}h{cap}M{xc} {y1}h{cap}")?;
+        }
+        writeln!(f, "'/>")
+    }
+
+    /// Get finite data points, with their indices
+    fn finite_points(&self) -> impl Iterator<Item = (usize, &Pt<f32>)> {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, pt)| pt.x().is_finite() && pt.y().is_finite())
+    }
+
+    /// Get displayed (possibly decimated) points with their data indices
+    ///
+    /// Displayed points are copies of finite data points, in order, so each
+    /// is matched with the next equal one.
+    fn indexed<'p>(
+        &'p self,
+        pts: &'p [Pt<f32>],
+    ) -> impl Iterator<Item = (usize, &'p Pt<f32>)> {
+        let mut finite = self.finite_points();
+        pts.iter().filter_map(move |pt| {
+            finite.find(|(_, p)| *p == pt).map(|(i, _)| (i, pt))
+        })
+    }
+
+    /// Display markers at points, with tooltips if set
+    fn display_point_markers<'p>(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+        prefix: &str,
+        pts: impl Iterator<Item = (usize, &'p Pt<f32>)>,
+    ) -> fmt::Result {
+        let tooltip = match &self.tooltips {
+            Some(tooltip) => tooltip,
+            None => {
+                let pts = pts.map(|(_, pt)| pt);
+                return self.display_markers(f, num, map, prefix, pts);
+            }
+        };
+        let marker = format!("url(#{prefix}marker-{num})");
+        for (i, pt) in pts {
+            let (x, y) = map.pt(pt);
+            writeln!(f, "<g class='plot-tooltip'>")?;
+            writeln!(f, "<title>{}</title>", Escape(&tooltip(i, *pt)))?;
+            write!(f, "<path")?;
+            self.display_class(f, num, "plot-scatter")?;
+            writeln!(f, " marker-start='{marker}' d='M{x} {y}'/>")?;
+            write!(f, "<circle class='plot-hover' cx='{x}' cy='{y}'")?;
+            writeln!(f, " r='{TOOLTIP_RADIUS}'/>")?;
+            writeln!(f, "</g>")?;
+        }
+        Ok(())
+    }
+
+    /// Display markers at points
+    fn display_markers<'p>(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        map: &Mapping,
+        prefix: &str,
+        pts: impl Iterator<Item = &'p Pt<f32>>,
+    ) -> fmt::Result {
+        let marker = format!("url(#{prefix}marker-{num})");
+        write!(f, "<path")?;
+        self.display_class(f, num, "plot-scatter")?;
+        write!(f, " marker-start='{marker}'")?;
+        write!(f, " marker-mid='{marker}'")?;
+        write!(f, " marker-end='{marker}' d='")?;
+
+        // Each point is its own subpath, so no segments connect them
+        let mut prev = None;
+        for pt in pts {
+            let (x, y) = map.pt(pt);
+
+            match prev {
+                None => write!(f, "M{x} {y}")?,
+                Some((px, py)) => {
+                    f.write_str(" m")?;
+                    write_pair(f, x.delta(px), y.delta(py))?;
+                }
+            }
+            prev = Some((x, y));
         }
         writeln!(f, "' />")
     }
+}
 
-    pub(crate) fn name(&self) -> &'a str {
+impl Series for Plot<'_> {
+    fn name(&self) -> &str {
         self.name
     }
 
-    pub(crate) fn display(
-        &mut self,
+    fn is_empty(&self) -> bool {
+        !self
+            .data
+            .iter()
+            .any(|pt| pt.x().is_finite() && pt.y().is_finite())
+    }
+
+    fn point_count(&self) -> usize {
+        self.data.len()
+    }
+
+    fn domain(&self) -> Option<&BBox<f32>> {
+        Some(self.bbox())
+    }
+
+    fn auto_extent(&self) -> Option<BBox<f32>> {
+        match self.domain {
+            // Plots without finite points don't extend the domain
+            Domain::Auto(_) if self.finite_points().next().is_some() => {
+                Some(data::domain(&self.data))
+            }
+            _ => None,
+        }
+    }
+
+    fn set_auto_domain(&mut self, domain: BBox<f32>) {
+        if let Domain::Auto(_) = self.domain {
+            self.domain = Domain::Auto(domain);
+        }
+    }
+
+    fn axes(&self) -> (Option<&str>, Option<&str>) {
+        (self.x_axis, self.y_axis)
+    }
+
+    fn color(&self) -> Option<&str> {
+        self.color
+    }
+
+    fn class(&self) -> Option<&str> {
+        self.class
+    }
+
+    fn marker(&self) -> Option<Marker<'_>> {
+        self.marker
+    }
+
+    fn marker_size(&self) -> Option<f32> {
+        self.marker_size
+    }
+
+    fn stroke_width(&self) -> Option<f32> {
+        self.stroke_width
+    }
+
+    fn in_legend(&self) -> bool {
+        self.in_legend
+    }
+
+    fn has_markers(&self) -> bool {
+        use PlotKind::*;
+        match self.kind {
+            Scatter => true,
+            Line | Smooth(_) | Step(_) => self.markers.is_some(),
+            _ => false,
+        }
+    }
+
+    fn display_legend_fill(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        x: i32,
+        y: i32,
+    ) -> fmt::Result {
+        if !self.ribbon.is_empty() {
+            write!(f, "<rect")?;
+            self.display_class(f, num, "legend-ribbon")?;
+            let y = y - 10;
+            writeln!(f, " x='{x}' y='{y}' width='60' height='20'/>")?;
+        }
+        Ok(())
+    }
+
+    fn is_unsorted(&self) -> bool {
+        use PlotKind::*;
+        match self.kind {
+            Area | Line | Smooth(_) | StackedArea(_) | Step(_) => {
+                !is_monotonic(self.data.iter().map(|pt| pt.x()))
+            }
+            Bar(_) | HBar | Scatter => false,
+        }
+    }
+
+    fn legend_detail(&self) -> Option<String> {
+        let label = self.trend?;
+        Fit::linear(self.data.iter())?.label(label)
+    }
+
+    fn display_labels(
+        &self,
         f: &mut dyn Write,
         num: usize,
         rect: BBox<f32>,
-        kind: PlotKind,
+        scales: &Scales,
+        clipped: bool,
+    ) -> fmt::Result {
+        let label = match &self.labels {
+            Some(label) if label.is_clipped() == clipped => label,
+            _ => return Ok(()),
+        };
+        let map = Mapping::new(self.bbox(), rect, scales);
+        // Position along bars is selected by the label point
+        let along = |value: f32| match label.label_point() {
+            LabelPoint::Minimum => 0.0,
+            LabelPoint::Center => value / 2.0,
+            LabelPoint::Maximum => value,
+        };
+        let class = format!("plot-{num} plot-label");
+        let text = Text::new(Edge::Top)
+            .with_anchor(label.anchor())
+            .with_class_name(&class);
+        text.display(f)?;
+        // Grouped bars are shifted from their `X` value
+        let offset = match self.kind {
+            PlotKind::Bar(slot) => self.bar_geometry(&map, &self.data, slot).1,
+            _ => 0,
+        };
+        for (i, pt) in self.data.iter().enumerate() {
+            if !pt.x().is_finite() || !pt.y().is_finite() {
+                continue;
+            }
+            let (x, y) = match self.kind {
+                PlotKind::Bar(_) => (pt.x(), along(pt.y())),
+                PlotKind::HBar => (along(pt.x()), pt.y()),
+                _ => (pt.x(), pt.y()),
+            };
+            let value = match self.kind {
+                PlotKind::HBar => pt.x(),
+                _ => pt.y(),
+            };
+            let value = match &self.label_texts {
+                Some(texts) => match texts.get(i) {
+                    Some(text) => text.clone(),
+                    None => continue,
+                },
+                None => label.rounded(value),
+            };
+            Tspan::new(&value)
+                .x(map.x_map(x) + offset)
+                .y(map.y_map(y))
+                .dy(label.dy())
+                .display(f)?;
+        }
+        text.display_done(f)
+    }
+
+    fn dash(&self) -> Option<&[f32]> {
+        self.dash.as_deref()
+    }
+
+    fn z(&self) -> i32 {
+        self.z
+    }
+
+    fn legend_link(&self) -> Option<&Link> {
+        self.legend_link.as_ref()
+    }
+
+    fn points(&self) -> Option<&[Pt<f32>]> {
+        Some(&self.data)
+    }
+
+    fn is_clipped(&self) -> bool {
+        self.clipped
+    }
+
+    fn stack(&self) -> Option<usize> {
+        match self.kind {
+            PlotKind::StackedArea(stack) => Some(stack),
+            PlotKind::Bar(slot) => slot.stack,
+            _ => None,
+        }
+    }
+
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        rect: BBox<f32>,
+        scales: &Scales,
+        stack: &mut Vec<f32>,
     ) -> fmt::Result {
         use PlotKind::*;
 
-        match kind {
-            Area => self.display_area(f, num, rect),
-            Line => self.display_line(f, num, rect),
-            Scatter => self.display_scatter(f, num, rect),
+        let map = Mapping::new(self.bbox(), rect, scales);
+        let pts = &self.data;
+        let segs = self.decimate(self.segments(pts));
+        if segs.iter().all(|seg| seg.is_empty()) {
+            return Ok(());
+        }
+
+        self.display_ribbon(f, num, &map)?;
+        if let Some(err) = &self.y_error {
+            self.display_y_error(f, num, &map, pts, err)?;
         }
+        let pts = segs.concat();
+
+        match self.kind {
+            Area => self.display_area(f, num, &map, &segs),
+            Bar(slot) => self.display_bar(f, num, &map, &pts, slot, stack),
+            HBar => self.display_hbar(f, num, &map, &pts),
+            Line => self.display_line(f, num, &map, &segs),
+            Scatter if self.tooltips.is_some() => self.display_point_markers(
+                f,
+                num,
+                &map,
+                &scales.prefix,
+                self.indexed(&pts),
+            ),
+            Scatter => {
+                self.display_markers(f, num, &map, &scales.prefix, pts.iter())
+            }
+            Smooth(tension) => {
+                self.display_smooth(f, num, &map, &segs, tension)
+            }
+            Step(step) => self.display_step(f, num, &map, &segs, step),
+            StackedArea(_) => {
+                self.display_stacked_area(f, num, &map, &pts, stack)
+            }
+        }?;
+
+        match (self.kind, self.markers) {
+            (Line | Smooth(_) | Step(_), Some(n))
+                if self.tooltips.is_some() =>
+            {
+                self.display_point_markers(
+                    f,
+                    num,
+                    &map,
+                    &scales.prefix,
+                    self.indexed(&pts).step_by(n),
+                )
+            }
+            (Line | Smooth(_) | Step(_), Some(n)) => self.display_markers(
+                f,
+                num,
+                &map,
+                &scales.prefix,
+                pts.iter().step_by(n),
+            ),
+            _ => Ok(()),
+        }?;
+
+        match (self.kind, self.finite_points().last()) {
+            (Area | Line | Smooth(_) | Step(_), Some(last))
+                if self.last_marker =>
+            {
+                self.display_point_markers(
+                    f,
+                    num,
+                    &map,
+                    &scales.prefix,
+                    [last].into_iter(),
+                )
+            }
+            _ => Ok(()),
+        }?;
+
+        self.display_trend(f, num, &map)
     }
 }
 
-/// Normalize an `X` value
-fn x_norm(domain: BBox<f32>, x: f32) -> f32 {
-    let x_scale = Numeric::from_data(domain, |pt| pt.x());
-    x_scale.normalize(x)
+/// Write a pair of coordinates, separated by a space
+fn write_pair(f: &mut dyn Write, x: Coord, y: Coord) -> fmt::Result {
+    let mut xb = [0; Coord::MAX_LEN];
+    let mut yb = [0; Coord::MAX_LEN];
+    let (xl, yl) = (x.encode(&mut xb), y.encode(&mut yb));
+    if xl == 0 || yl == 0 {
+        return write!(f, "{x} {y}");
+    }
+    // Join into one buffer, for a single write
+    let mut buf = [b' '; Coord::MAX_LEN * 2 + 1];
+    buf[..xl].copy_from_slice(&xb[Coord::MAX_LEN - xl..]);
+    buf[xl + 1..xl + 1 + yl].copy_from_slice(&yb[Coord::MAX_LEN - yl..]);
+    f.write_str(std::str::from_utf8(&buf[..xl + 1 + yl]).unwrap_or_default())
 }
 
-/// Normalize a `Y` value
-fn y_norm(domain: BBox<f32>, y: f32) -> f32 {
-    let y_scale = Numeric::from_data(domain, |pt| pt.y());
-    y_scale.inverted().normalize(y)
+/// Reorder values, if there is one for each index
+fn permute<T: Clone>(values: &mut Vec<T>, order: &[usize]) {
+    if values.len() == order.len() {
+        *values = order.iter().map(|i| values[*i].clone()).collect();
+    }
 }
 
-/// Map an `X` value to a rectangle
-pub(crate) fn x_map(domain: &BBox<f32>, x: f32, rect: BBox<f32>) -> i32 {
-    let rx = rect.x_min();
-    let rw = rect.x_span();
-    let mx = rx + rw * x_norm(*domain, x);
-    mx.round() as i32
+/// Check if finite values are in increasing or decreasing order
+fn is_monotonic(values: impl Iterator<Item = f32>) -> bool {
+    let values: Vec<f32> = values.filter(|v| v.is_finite()).collect();
+    values.windows(2).all(|w| w[0] <= w[1])
+        || values.windows(2).all(|w| w[0] >= w[1])
 }
 
-/// Map a `Y` value to a rectangle
-pub(crate) fn y_map(domain: &BBox<f32>, y: f32, rect: BBox<f32>) -> i32 {
-    let ry = rect.y_min();
-    let rh = rect.y_span();
-    let my = ry + rh * y_norm(*domain, y);
-    my.round() as i32
+/// Find the smallest non-zero spacing between values
+pub(crate) fn min_spacing(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let mut values: Vec<f32> = values.collect();
+    values.sort_by(f32::total_cmp);
+    values
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|d| *d > 0.0)
+        .min_by(f32::total_cmp)
+}
+
+/// Scales which override the domain of a series
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Scales {
+    /// Scale for `X` values
+    pub(crate) x: Option<Numeric>,
+    /// Scale for `Y` values
+    pub(crate) y: Option<Numeric>,
+    /// Prefix of chart element ids
+    pub(crate) prefix: String,
+    /// Decimal places of point coordinates
+    pub(crate) precision: u8,
+}
+
+/// Mapping of data values to a rectangle
+///
+/// A mapping is created once per series display, so the scales are only
+/// built once, rather than for every point.
+pub(crate) struct Mapping {
+    x_scale: Numeric,
+    y_scale: Numeric,
+    rect: BBox<f32>,
+    precision: u8,
+}
+
+impl Mapping {
+    /// Create a new mapping from a domain to a rectangle
+    pub(crate) fn new(
+        domain: &BBox<f32>,
+        rect: BBox<f32>,
+        scales: &Scales,
+    ) -> Self {
+        let x_scale = scales
+            .x
+            .clone()
+            .unwrap_or_else(|| Numeric::from_data(*domain, |pt| pt.x()));
+        let y_scale = scales
+            .y
+            .clone()
+            .unwrap_or_else(|| Numeric::from_data(*domain, |pt| pt.y()))
+            .inverted();
+        Self {
+            x_scale,
+            y_scale,
+            rect,
+            precision: scales.precision,
+        }
+    }
+
+    /// Create a new mapping from scales to a rectangle
+    pub(crate) fn from_scales(x: Numeric, y: Numeric, rect: BBox<f32>) -> Self {
+        Self {
+            x_scale: x,
+            y_scale: y.inverted(),
+            rect,
+            precision: 0,
+        }
+    }
+
+    /// Check if an `X` value is within the scale
+    pub(crate) fn x_contains(&self, x: f32) -> bool {
+        self.x_scale.contains(x)
+    }
+
+    /// Check if a `Y` value is within the scale
+    pub(crate) fn y_contains(&self, y: f32) -> bool {
+        self.y_scale.contains(y)
+    }
+
+    /// Get the rectangle
+    pub(crate) fn rect(&self) -> BBox<f32> {
+        self.rect
+    }
+
+    /// Map an `X` value to the rectangle
+    pub(crate) fn x_map(&self, x: f32) -> i32 {
+        self.x_pos(x).round() as i32
+    }
+
+    /// Map a `Y` value to the rectangle
+    pub(crate) fn y_map(&self, y: f32) -> i32 {
+        self.y_pos(y).round() as i32
+    }
+
+    /// Map an `X` value to the rectangle, without rounding
+    pub(crate) fn x_pos(&self, x: f32) -> f32 {
+        let rx = self.rect.x_min();
+        let rw = self.rect.x_span();
+        rx + rw * self.x_scale.normalize(x)
+    }
+
+    /// Map a `Y` value to the rectangle, without rounding
+    pub(crate) fn y_pos(&self, y: f32) -> f32 {
+        let ry = self.rect.y_min();
+        let rh = self.rect.y_span();
+        ry + rh * self.y_scale.normalize(y)
+    }
+
+    /// Round a position to the mapping precision
+    pub(crate) fn coord(&self, pos: f32) -> Coord {
+        Coord::new(pos, self.precision)
+    }
+
+    /// Map a point to coordinates, at the mapping precision
+    pub(crate) fn pt(&self, pt: &Pt<f32>) -> (Coord, Coord) {
+        (
+            self.coord(self.x_pos(pt.x())),
+            self.coord(self.y_pos(pt.y())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scatter_has_no_segments() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0), (9.0, 3.0)];
+        let plot =
+            Plot::new("Scatter", &domain, data).with_kind(PlotKind::Scatter);
+        let rect = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let mut out = String::new();
+        plot.display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+            .unwrap();
+
+        let d = out
+            .split(" d='")
+            .nth(1)
+            .unwrap()
+            .split('\'')
+            .next()
+            .unwrap();
+        let cmds: Vec<&str> = d.split_whitespace().collect();
+        assert_eq!(cmds.len(), 6);
+        for pair in cmds.chunks(2) {
+            assert!(pair[0].starts_with(['M', 'm']));
+            assert!(pair[1].parse::<i32>().is_ok());
+        }
+        assert!(out.contains("marker-mid='url(#marker-0)'"));
+    }
+
+    #[test]
+    fn zero_span_is_finite() {
+        use crate::{
+            axis::{Horizontal, Vertical},
+            Chart,
+        };
+        let data = vec![(5.0, 3.0), (5.0, 8.0)];
+        let domain = BBox::new(data.iter().cloned());
+        let chart = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain))
+            .with_line_plot(Plot::new("Line", &domain, data.clone()))
+            .with_bar_plot(Plot::new("Bar", &domain, data.clone()))
+            .with_area_plot(Plot::new("Area", &domain, data))
+            .render();
+        assert!(!chart.contains("NaN"));
+        assert!(!chart.contains("inf"));
+    }
+
+    #[test]
+    fn empty_plot() {
+        use crate::{
+            axis::{Horizontal, Vertical},
+            Chart,
+        };
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let empty: [(f32, f32); 0] = [];
+        let data = [(1.0, 2.0), (5.0, 8.0)];
+        let chart = Chart::default()
+            .with_axis(Horizontal::new(domain))
+            .with_axis(Vertical::new(domain))
+            .with_line_plot(Plot::new("Empty", &domain, empty))
+            .with_line_plot(Plot::new("Data", &domain, data))
+            .render();
+        assert!(!chart.contains("plot-0 plot-line"));
+        assert!(chart.contains("plot-1 plot-line"));
+        assert!(chart.contains("<div class='legend-empty'>\n"));
+        assert!(chart.contains("</svg>"));
+    }
+
+    #[test]
+    fn no_plots() {
+        let chart = crate::Chart::default().render();
+        assert!(chart.contains("</svg>"));
+        assert!(chart.contains("<div class='legend'>\n</div>"));
+    }
+
+    #[test]
+    fn non_finite() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let rect = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let data = [(0.0, 0.0), (5.0, f32::NAN), (10.0, 10.0)];
+        let render = |plot: Plot| {
+            let plot = plot.with_kind(PlotKind::Line);
+            let mut out = String::new();
+            plot.display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+                .unwrap();
+            out
+        };
+        let out = render(Plot::new("Skip", &domain, data));
+        assert!(out.contains("d='M0 100l100 -100'"));
+        let out = render(Plot::new("Gap", &domain, data).gap_on_non_finite());
+        assert!(out.contains("d='M0 100 M100 0'"));
+        let plot = Plot::new("Area", &domain, data)
+            .gap_on_non_finite()
+            .with_kind(PlotKind::Area);
+        let mut out = String::new();
+        plot.display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+            .unwrap();
+        assert!(out.contains("d='M0 100l0 0 0 0 M100 100l0 -100 0 100'"));
+    }
+
+    #[test]
+    fn color_and_class() {
+        use crate::Chart;
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0)];
+        let plot = Plot::new("Revenue", &domain, data)
+            .with_color("#d62728")
+            .with_class("revenue's");
+        let chart = Chart::default().with_line_plot(plot).render();
+        let attrs = " class='plot-0 plot-line revenue&apos;s' \
+            style='--color: #d62728; stroke: #d62728'";
+        assert!(chart.contains(&format!("<path{attrs} d='")));
+        let attrs = " class='plot-0 legend-line revenue&apos;s' \
+            style='--color: #d62728; stroke: #d62728'";
+        assert!(chart.contains(&format!("<path{attrs} d='M0 15h30h30'")));
+        assert!(chart.contains("class='plot-0' style='--color: #d62728'"));
+        assert!(chart.contains("<g style='fill: #d62728'><circle"));
+        // Overrides also apply without the bundled stylesheet
+        let plot = Plot::new("Bars", &domain, data).with_color("#d62728");
+        let chart = Chart::default().with_css("").with_bar_plot(plot).render();
+        assert!(chart.contains("style='--color: #d62728; fill: #d62728'"));
+    }
+
+    #[test]
+    fn line_style() {
+        use crate::Chart;
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0)];
+        let plot = Plot::new("Dashed", &domain, data)
+            .with_color("black")
+            .with_stroke_width(2.5)
+            .with_dash(&[6.0, 3.0]);
+        let chart = Chart::default().with_line_plot(plot).render();
+        let style = "style='--color: black; stroke: black; \
+            stroke-width: 2.5px; stroke-dasharray: 6 3'";
+        let line = format!("<path class='plot-0 plot-line' {style} d='");
+        assert!(chart.contains(&line));
+        let legend = format!("<path class='plot-0 legend-line' {style} d='");
+        assert!(chart.contains(&legend));
+        for width in [f32::NAN, f32::INFINITY, -1.0] {
+            let plot = Plot::new("Bad", &domain, data).with_stroke_width(width);
+            assert_eq!(plot.stroke_width, None);
+        }
+    }
+
+    #[test]
+    fn line_markers() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let rect = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let data = [(0.0, 0.0), (2.0, 1.0), (4.0, 3.0), (6.0, 2.0), (8.0, 5.0)];
+        let render = |plot: Plot| {
+            let mut out = String::new();
+            plot.display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+                .unwrap();
+            out
+        };
+        let out = render(Plot::new("Line", &domain, data));
+        assert!(!out.contains("marker-mid"));
+        let out = render(Plot::new("Line", &domain, data).with_markers());
+        assert!(out.contains("<path class='plot-0 plot-line' d='"));
+        assert!(out.contains("marker-mid='url(#marker-0)'"));
+        assert!(out.contains("d='M0 100 m20 -10 m20 -20 m20 10 m20 -30'"));
+        let out = render(Plot::new("Line", &domain, data).with_marker_every(2));
+        assert!(out.contains("d='M0 100 m40 -30 m40 -20'"));
+        // Legend swatches only have markers if the plot does
+        let legend = |plot: Plot| {
+            let mut out = String::new();
+            plot.display_legend(&mut out, 0, None).unwrap();
+            out.contains("marker-mid")
+        };
+        assert!(!legend(Plot::new("Line", &domain, data)));
+        assert!(legend(Plot::new("Line", &domain, data).with_markers()));
+        let plot = |kind| Plot::new("Plot", &domain, data).with_kind(kind);
+        assert!(legend(plot(PlotKind::Scatter)));
+        assert!(!legend(plot(PlotKind::Area)));
+        assert!(!crate::css::with_palette(1, &Default::default())
+            .contains("marker: var(--marker)"));
+    }
+
+    /// Parse absolute points from a path with `M`, `m` and `l` commands
+    ///
+    /// Points are rounded to 2 decimal places, as a renderer summing the
+    /// decimal offsets would get.
+    fn path_points(d: &str) -> Vec<(f32, f32)> {
+        let mut pts = vec![];
+        let (mut x, mut y) = (0.0, 0.0);
+        let mut relative = false;
+        let d = d.replace('M', " M ").replace(['m', 'l'], " r ");
+        let mut tokens = d.split_whitespace();
+        while let Some(tok) = tokens.next() {
+            let dx: f32 = match tok {
+                "M" | "r" => {
+                    relative = tok == "r";
+                    tokens.next().unwrap().parse().unwrap()
+                }
+                _ => tok.parse().unwrap(),
+            };
+            let dy: f32 = tokens.next().unwrap().parse().unwrap();
+            (x, y) = if relative { (x + dx, y + dy) } else { (dx, dy) };
+            (x, y) = (Coord::new(x, 2).value(), Coord::new(y, 2).value());
+            pts.push((x, y));
+        }
+        pts
+    }
+
+    #[test]
+    fn relative_paths() {
+        let domain = BBox::new([(0.0, -1.0), (100.0, 1.0)]);
+        let rect = BBox::new([(10.0, 10.0), (333.0, 217.0)]);
+        let data: Vec<(f32, f32)> = (0..100)
+            .map(|i| (i as f32, (i as f32 / 7.0).sin()))
+            .collect();
+        let scales = Scales {
+            precision: 2,
+            ..Scales::default()
+        };
+        let map = Mapping::new(&domain, rect, &scales);
+        let expected: Vec<(f32, f32)> = data
+            .iter()
+            .map(|pt| {
+                let (x, y) = map.pt(&Pt::from(*pt));
+                (x.value(), y.value())
+            })
+            .collect();
+        let bounds = |pts: &[(f32, f32)]| {
+            let bbox = BBox::new(pts.iter().cloned());
+            (bbox.x_min(), bbox.y_min(), bbox.x_max(), bbox.y_max())
+        };
+        for kind in [PlotKind::Line, PlotKind::Scatter, PlotKind::Area] {
+            let plot = Plot::new("", &domain, data.clone()).with_kind(kind);
+            let mut out = String::new();
+            plot.display(&mut out, 0, rect, &scales, &mut vec![])
+                .unwrap();
+            let d = out.split(" d='").nth(1).unwrap().split('\'').next();
+            let pts = path_points(d.unwrap());
+            let pts = match kind {
+                // Area points include the baseline at each end
+                PlotKind::Area => {
+                    let base = map.coord(map.y_pos(0.0)).value();
+                    let n = pts.len();
+                    assert_eq!(pts[0], (expected[0].0, base));
+                    assert_eq!(pts[n - 1], (expected[99].0, base));
+                    pts[1..n - 1].to_vec()
+                }
+                _ => pts,
+            };
+            assert_eq!(bounds(&pts), bounds(&expected));
+            assert_eq!(pts, expected);
+        }
+    }
+
+    #[test]
+    fn shared_scales() {
+        let domain = BBox::new([(0.0, 0.0), (50.0, 20.0)]);
+        let rect = BBox::new([(0.0, 0.0), (500.0, 300.0)]);
+        let data: Vec<(f32, f32)> =
+            (0..50).map(|i| (i as f32, (i % 7) as f32 * 3.0)).collect();
+        let plot = Plot::new("", &domain, data).with_kind(PlotKind::Line);
+        let render = |scales: &Scales| {
+            let mut out = String::new();
+            plot.display(&mut out, 0, rect, scales, &mut vec![])
+                .unwrap();
+            out
+        };
+        // Prebuilt scales on the same domain map points identically
+        let scales = Scales {
+            x: Some(Numeric::from_data(domain, |pt| pt.x())),
+            y: Some(Numeric::from_data(domain, |pt| pt.y())),
+            ..Scales::default()
+        };
+        assert_eq!(render(&scales), render(&Scales::default()));
+    }
+
+    #[test]
+    fn max_points() {
+        let domain = BBox::new([(0.0, -1.0), (100_000.0, 1.0)]);
+        let rect = BBox::new([(0.0, 0.0), (1000.0, 100.0)]);
+        let data: Vec<(f32, f32)> = (0..100_000)
+            .map(|i| (i as f32, (i as f32 / 500.0).sin()))
+            .collect();
+        let count = |kind: PlotKind| {
+            let plot = Plot::new("", &domain, data.clone())
+                .with_kind(kind)
+                .with_max_points(500);
+            let mut out = String::new();
+            plot.display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+                .unwrap();
+            let d = out.split(" d='").nth(1).unwrap().split('\'').next();
+            let d = d.unwrap().to_string();
+            assert!(d.starts_with("M0 50"));
+            path_points(&d).len()
+        };
+        assert_eq!(count(PlotKind::Line), 500);
+        assert!((490..=500).contains(&count(PlotKind::Scatter)));
+    }
+
+    #[test]
+    fn baseline() {
+        let domain = BBox::new([(0.0, 50.0), (10.0, 100.0)]);
+        let rect = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let data = [(0.0, 60.0), (10.0, 80.0)];
+        let render = |plot: Plot| {
+            let mut out = String::new();
+            plot.with_kind(PlotKind::Area)
+                .display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+                .unwrap();
+            out
+        };
+        // Zero is below the domain, so the baseline is clamped
+        let out = render(Plot::new("", &domain, data));
+        assert!(out.contains("d='M0 100l0 -20 100 -40 0 60'"));
+        let out = render(Plot::new("", &domain, data).with_baseline(75.0));
+        assert!(out.contains("d='M0 50l0 30 100 -40 0 10'"));
+    }
+
+    #[test]
+    fn trendline() {
+        use crate::Chart;
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let rect = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let data = [(2.0, 3.0), (4.0, 4.0), (6.0, 5.0)];
+        let plot = Plot::new("Fit", &domain, data)
+            .with_kind(PlotKind::Scatter)
+            .with_trendline(TrendLabel::Equation);
+        let mut out = String::new();
+        plot.display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+            .unwrap();
+        // Line extends across the domain: y = 0.5x + 2
+        assert!(out.contains("<path class='plot-0 trend' d='M0 80l100 -50'/>"));
+        let html = Chart::default().with_scatter_plot(plot).render();
+        let note = "<span class='legend-note'>y = 0.50x + 2.00</span>";
+        assert!(html.contains(note));
+        let plot = Plot::new("Fit", &domain, data);
+        assert_eq!(plot.legend_detail(), None);
+    }
+
+    #[test]
+    fn ribbon() {
+        use crate::Chart;
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let rect = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let data = [(0.0, 5.0), (2.0, 5.0), (4.0, 5.0), (6.0, 5.0)];
+        let plot = Plot::new("Forecast", &domain, data)
+            .with_kind(PlotKind::Line)
+            .with_ribbon_widths([1.0, 2.0, f32::NAN, 1.0]);
+        let mut out = String::new();
+        plot.display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+            .unwrap();
+        // Non-finite bound leaves a gap, and the ribbon is behind the line
+        let ribbon = "<path class='plot-0 plot-ribbon' d='";
+        assert_eq!(out.matches(ribbon).count(), 2);
+        assert!(out.contains(&format!("{ribbon}M0 40 20 30 20 70 0 60z'/>")));
+        assert!(out.find(ribbon).unwrap() < out.find("plot-line").unwrap());
+        let html = Chart::default().with_line_plot(plot).render();
+        assert!(html.contains(
+            "<rect class='plot-0 legend-ribbon' x='0' y='5' \
+            width='60' height='20'/>"
+        ));
+    }
+
+    #[test]
+    fn sorted_by_x() {
+        use crate::{Chart, Error};
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(4.0, 1.0), (1.0, 2.0), (4.0, 3.0), (2.0, 4.0)];
+        let plot = Plot::new("Line", &domain, data)
+            .with_ribbon_widths([1.0, 2.0, 3.0, 4.0])
+            .sorted_by_x();
+        let xy: Vec<_> = plot.data.iter().map(|pt| (pt.x(), pt.y())).collect();
+        assert_eq!(xy, [(1.0, 2.0), (2.0, 4.0), (4.0, 1.0), (4.0, 3.0)]);
+        assert_eq!(plot.ribbon[0].0.y(), 0.0);
+        let chart = Chart::default()
+            .with_line_plot(plot)
+            .with_line_plot(Plot::new("Unsorted", &domain, data))
+            .with_scatter_plot(Plot::new("Scatter", &domain, data));
+        let warnings = chart.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(&warnings[0], Error::Unsorted(n) if n == "Unsorted"));
+    }
+
+    #[test]
+    fn point_labels() {
+        use crate::Chart;
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let rect = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let data = [(2.0, 4.25), (8.0, 10.0)];
+        let plot = Plot::new("Bars", &domain, data)
+            .with_kind(PlotKind::Bar(BarSlot::default()))
+            .with_point_labels(Label::new().maximum().above().rounded_to(1));
+        let mut out = String::new();
+        plot.display_labels(&mut out, 0, rect, &Scales::default(), false)
+            .unwrap();
+        assert!(out.starts_with("<text class='plot-0 plot-label'"));
+        assert!(out.contains("<tspan x='20' y='58' dy='-0.67em'>4.2</tspan>"));
+        assert!(out.contains("<tspan x='80' y='0' dy='-0.67em'>10.0</tspan>"));
+        let plot = Plot::new("Line", &domain, data)
+            .with_point_texts(["low", "high"])
+            .with_point_labels(Label::new());
+        let html = Chart::default().with_line_plot(plot).render();
+        let body = html.split_once("<g clip-path").unwrap().1;
+        let (clipped, unclipped) = body.split_once("</g>\n</g>").unwrap();
+        assert!(!clipped.contains("plot-label"));
+        assert!(unclipped.contains("dy='0.33em'>high</tspan>"));
+    }
+
+    #[test]
+    fn marker() {
+        use crate::Chart;
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(1.0, 2.0), (5.0, 8.0)];
+        let chart = Chart::default()
+            .with_scatter_plot(
+                Plot::new("A", &domain, data)
+                    .with_marker(Marker::Diamond)
+                    .with_marker_size(8.0),
+            )
+            .with_scatter_plot(
+                Plot::new("B", &domain, data)
+                    .with_marker(Marker::Custom("<circle r='0.5'/>")),
+            )
+            .render();
+        let defs = chart.split("</defs>").next().unwrap();
+        assert!(defs.contains(
+            "<marker id='marker-0' class='plot-0' viewBox='-1 -1 2 2' \
+            markerWidth='8' markerHeight='8'>\n\
+            <path d='M0 -1 1 0 0 1 -1 0z'/>"
+        ));
+        assert!(defs.contains(
+            "<marker id='marker-1' class='plot-1' viewBox='-1 -1 2 2' \
+            markerWidth='5' markerHeight='5'>\n<circle r='0.5'/>"
+        ));
+    }
+
+    #[test]
+    fn tooltips() {
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let rect = BBox::new([(0.0, 0.0), (100.0, 100.0)]);
+        let data = [(1.0, 2.0), (f32::NAN, 3.0), (5.0, 8.0)];
+        let display = |plot: Plot| {
+            let mut out = String::new();
+            plot.display(&mut out, 0, rect, &Scales::default(), &mut vec![])
+                .unwrap();
+            out
+        };
+        let out =
+            display(Plot::new("", &domain, data).with_kind(PlotKind::Scatter));
+        assert_eq!(out.matches("<path").count(), 1);
+        assert!(!out.contains("<title>"));
+        let plot = Plot::new("", &domain, data)
+            .with_kind(PlotKind::Scatter)
+            .with_tooltips(|i, pt| format!("#{i}: {} < {}", pt.x(), pt.y()));
+        let out = display(plot);
+        assert_eq!(out.matches("<g class='plot-tooltip'>").count(), 2);
+        assert!(out.contains(
+            "<title>#2: 5 &lt; 8</title>\n<path class='plot-0 plot-scatter' \
+            marker-start='url(#marker-0)' d='M50 20'/>\n\
+            <circle class='plot-hover' cx='50' cy='20' r='15'/>"
+        ));
+        let plot = Plot::new("", &domain, data)
+            .with_markers()
+            .with_tooltips(|i, _| i.to_string());
+        let out = display(plot);
+        assert!(out.contains("plot-line"));
+        assert!(out.contains("<title>0</title>"));
+        assert!(out.contains("<title>2</title>"));
+        // Tooltips keep the markers of decimated points
+        let data: Vec<_> = (0..1000)
+            .map(|i| (i as f32 / 100.0, (i % 7) as f32))
+            .collect();
+        let plot = || {
+            Plot::new("", &domain, data.clone())
+                .with_max_points(50)
+                .with_markers()
+        };
+        let out = display(plot());
+        let markers = out.split_once("plot-scatter").unwrap().1;
+        let d = markers.split_once(" d='").unwrap().1;
+        let count = d.split_once('\'').unwrap().0.matches('m').count() + 1;
+        assert!(count <= 50);
+        let out = display(plot().with_tooltips(|i, pt| {
+            assert_eq!(data[i], (pt.x(), pt.y()));
+            i.to_string()
+        }));
+        assert_eq!(out.matches("<g class='plot-tooltip'>").count(), count);
+        assert!(out.contains("<title>999</title>"));
+    }
+
+    #[test]
+    fn without_clip() {
+        use crate::Chart;
+        let domain = BBox::new([(0.0, 0.0), (10.0, 10.0)]);
+        let data = [(5.0, 5.0), (10.0, 10.0)];
+        let plot = || {
+            Plot::new("Edge", &domain, data)
+                .with_marker(Marker::Circle)
+                .with_marker_size(20.0)
+        };
+        let clipped = Chart::default().with_scatter_plot(plot()).render();
+        let body = clipped.split_once("<g clip-path").unwrap().1;
+        let (inside, _) = body.split_once("</g>\n</g>").unwrap();
+        assert!(inside.contains("aria-label='Edge'"));
+        let chart = Chart::default().with_scatter_plot(plot().without_clip());
+        let html = chart.render();
+        let body = html.split_once("<g clip-path").unwrap().1;
+        let (inside, outside) = body.split_once("</g>\n").unwrap();
+        assert!(!inside.contains("aria-label='Edge'"));
+        let group = outside.split_once("<g aria-label='Edge'>").unwrap().1;
+        let scatter = group.split_once("</g>").unwrap().0;
+        assert!(!scatter.contains("clip-path"));
+        // Marker at the domain maximum sits on the plot area corner
+        let area = chart.plot_area();
+        let (x, y) = (area.x_mid(), area.y_mid());
+        let (dx, dy) = (area.x_max() - x, area.y_min() - y);
+        assert!(scatter.contains(&format!("d='M{x} {y} m{dx} {dy}'")));
+    }
 }
@@ -0,0 +1,73 @@
+// row.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Chart rows
+
+use crate::{
+    axis::Axis,
+    candle::Candlestick,
+    plot::{BarSlot, Plot, PlotKind, Series, Step},
+};
+
+/// Row of a faceted `Chart`
+///
+/// The chart area is split into rows, stacked vertically.  Each row has its
+/// own vertical axes and plots, while horizontal axes added to the chart are
+/// shared by all rows.
+#[derive(Default)]
+pub struct Row<'a> {
+    pub(crate) axes: Vec<Box<dyn Axis + 'a>>,
+    pub(crate) plots: Vec<Box<dyn Series + 'a>>,
+}
+
+impl<'a> Row<'a> {
+    /// Create a new row
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an `Axis` to the row
+    pub fn with_axis<A: Axis + 'a>(mut self, axis: A) -> Self {
+        self.axes.push(Box::new(axis));
+        self
+    }
+
+    /// Add an area `Plot`
+    pub fn with_area_plot(mut self, plot: Plot<'a>) -> Self {
+        self.plots.push(Box::new(plot.with_kind(PlotKind::Area)));
+        self
+    }
+
+    /// Add a bar `Plot`
+    pub fn with_bar_plot(mut self, plot: Plot<'a>) -> Self {
+        self.plots
+            .push(Box::new(plot.with_kind(PlotKind::Bar(BarSlot::default()))));
+        self
+    }
+
+    /// Add a `Candlestick` plot
+    pub fn with_candlestick_plot(mut self, plot: Candlestick<'a>) -> Self {
+        self.plots.push(Box::new(plot));
+        self
+    }
+
+    /// Add a line `Plot`
+    pub fn with_line_plot(mut self, plot: Plot<'a>) -> Self {
+        self.plots.push(Box::new(plot.with_kind(PlotKind::Line)));
+        self
+    }
+
+    /// Add a scatter `Plot`
+    pub fn with_scatter_plot(mut self, plot: Plot<'a>) -> Self {
+        self.plots.push(Box::new(plot.with_kind(PlotKind::Scatter)));
+        self
+    }
+
+    /// Add a step `Plot`
+    pub fn with_step_plot(mut self, plot: Plot<'a>) -> Self {
+        self.plots
+            .push(Box::new(plot.with_kind(PlotKind::Step(Step::Post))));
+        self
+    }
+}
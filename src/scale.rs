@@ -4,16 +4,155 @@
 // Copyright (c) 2022  Jeron A Lau
 //
 //! Scale items
+use std::{fmt, rc::Rc};
+
 use pointy::Pt;
 
-use crate::text::Tick;
+/// Scale for mapping values along an axis
+///
+/// Implement this for custom scales, such as logarithmic or time scales, and
+/// add them with [Horizontal::with_scale] or [Vertical::with_scale].  Plots
+/// bound to the axis are mapped through the scale.
+///
+/// [Horizontal::with_scale]: crate::axis::Horizontal::with_scale
+/// [Vertical::with_scale]: crate::axis::Vertical::with_scale
+pub trait Scale {
+    /// Normalize a value, from 0 at the start of the axis to 1 at the end
+    ///
+    /// Values outside of the scale are outside of that range.
+    fn normalize(&self, value: f32) -> f32;
+
+    /// Get values to place ticks at
+    fn ticks(&self) -> Vec<f32>;
+
+    /// Check if the scale is inverted, running from the end to the start
+    fn inverted(&self) -> bool {
+        false
+    }
+}
+
+/// Quantile scale, from a sample of values
+///
+/// Equal distances along the axis are equal fractions of the sample, using
+/// the empirical distribution.  Values between samples are interpolated, and
+/// values beyond the samples are clamped to the ends.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantile {
+    samples: Vec<f32>,
+    percentiles: Vec<f32>,
+}
+
+/// Custom scale, compared by pointer
+#[derive(Clone)]
+struct Custom(Rc<dyn Scale>);
+
+impl Quantile {
+    /// Create a quantile scale from sample values
+    ///
+    /// Ticks are at the 10th, 25th, 50th, 75th and 90th percentiles.
+    /// Values which are not finite are ignored, and `None` is returned if no
+    /// finite values remain.  With a single sample, every value maps to the
+    /// middle of the axis.
+    pub fn from_samples(values: &[f32]) -> Option<Self> {
+        let mut samples: Vec<f32> =
+            values.iter().copied().filter(|v| v.is_finite()).collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_by(f32::total_cmp);
+        Some(Self {
+            samples,
+            percentiles: vec![10.0, 25.0, 50.0, 75.0, 90.0],
+        })
+    }
+
+    /// Set the percentiles (0 to 100) to place ticks at
+    pub fn with_percentiles(mut self, percentiles: &[f32]) -> Self {
+        self.percentiles =
+            percentiles.iter().map(|p| p.clamp(0.0, 100.0)).collect();
+        self
+    }
+
+    /// Get the sample value at a fraction (0 to 1) of the sample
+    pub fn value(&self, fraction: f32) -> f32 {
+        let last = self.samples.len() - 1;
+        let rank = fraction.clamp(0.0, 1.0) * last as f32;
+        let i = (rank.floor() as usize).min(last);
+        let j = (i + 1).min(last);
+        let t = rank - i as f32;
+        self.samples[i] + (self.samples[j] - self.samples[i]) * t
+    }
+}
+
+impl Scale for Quantile {
+    fn normalize(&self, value: f32) -> f32 {
+        let s = &self.samples;
+        if s.len() < 2 {
+            return 0.5;
+        }
+        let lo = s.partition_point(|x| *x < value);
+        let hi = s.partition_point(|x| *x <= value);
+        let rank = if hi > lo {
+            // Equal samples share their middle rank
+            (lo + hi - 1) as f32 / 2.0
+        } else if lo == 0 {
+            0.0
+        } else if lo == s.len() {
+            (s.len() - 1) as f32
+        } else {
+            let (a, b) = (s[lo - 1], s[lo]);
+            (lo - 1) as f32 + (value - a) / (b - a)
+        };
+        rank / (s.len() - 1) as f32
+    }
+
+    fn ticks(&self) -> Vec<f32> {
+        let mut ticks: Vec<f32> = self
+            .percentiles
+            .iter()
+            .map(|p| self.value(p / 100.0))
+            .collect();
+        // Skewed samples can have equal percentiles
+        ticks.dedup();
+        ticks
+    }
+}
 
 /// Numeric scale
-#[derive(Clone, Debug)]
+///
+/// The mapping is linear, unless it has a custom scale.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Numeric {
     start: f32,
     stop: f32,
     tick_spacing: f32,
+    custom: Option<Custom>,
+}
+
+impl fmt::Debug for Custom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Custom")
+    }
+}
+
+impl PartialEq for Custom {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Scale for Numeric {
+    fn normalize(&self, value: f32) -> f32 {
+        Numeric::normalize(self, value)
+    }
+
+    fn ticks(&self) -> Vec<f32> {
+        self.tick_values()
+    }
+
+    fn inverted(&self) -> bool {
+        self.tick_spacing < 0.0
+    }
 }
 
 impl Default for Numeric {
@@ -25,6 +164,9 @@ impl Default for Numeric {
 impl Numeric {
     /// Create a new numeric scale
     pub(crate) fn new(min: f32, max: f32) -> Self {
+        if max - min <= f32::EPSILON * min.abs().max(1.0) {
+            return Self::degenerate(min);
+        }
         let tick_spacing = Self::spacing(min, max);
         let start = (min / tick_spacing).floor() * tick_spacing;
         let stop = (max / tick_spacing).ceil() * tick_spacing;
@@ -32,9 +174,82 @@ impl Numeric {
             start,
             stop,
             tick_spacing,
+            custom: None,
+        }
+    }
+
+    /// Create a numeric scale with exact bounds
+    ///
+    /// Unlike `new`, the bounds are not rounded to tick spacing.
+    pub(crate) fn exact(min: f32, max: f32) -> Self {
+        Self {
+            start: min,
+            stop: max,
+            tick_spacing: 1.0,
+            custom: None,
+        }
+    }
+
+    /// Create a numeric scale with exact bounds and "nice" tick spacing
+    pub(crate) fn range(min: f32, max: f32) -> Self {
+        let (min, max) = (min.min(max), min.max(max));
+        if max - min <= f32::EPSILON * min.abs().max(1.0) {
+            return Self::degenerate(min);
+        }
+        Self {
+            start: min,
+            stop: max,
+            tick_spacing: Self::spacing(min, max),
+            custom: None,
+        }
+    }
+
+    /// Create a scale for a zero-span domain, centered on the value
+    ///
+    /// The tick spacing is the smallest needed to display the value, so there
+    /// is a single tick at the center.
+    fn degenerate(value: f32) -> Self {
+        let tick_spacing = if value == 0.0 || !value.is_finite() {
+            1.0
+        } else {
+            10_f32.powi(-(Self::precision(value) as i32))
+        };
+        Self {
+            start: value - tick_spacing / 2.0,
+            stop: value + tick_spacing / 2.0,
+            tick_spacing,
+            custom: None,
         }
     }
 
+    /// Create a scale mapped by a custom scale
+    ///
+    /// The bounds are the outer ticks.  The tick spacing is a "nice" step
+    /// near a tenth of the closest ticks, so labels have enough precision.
+    pub(crate) fn custom(scale: Rc<dyn Scale>) -> Self {
+        let mut ticks = scale.ticks();
+        ticks.retain(|t| t.is_finite());
+        ticks.sort_by(f32::total_cmp);
+        let start = ticks.first().copied().unwrap_or(0.0);
+        let stop = ticks.last().copied().unwrap_or(1.0);
+        let gap = ticks
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .filter(|d| *d > 0.0)
+            .fold(f32::INFINITY, f32::min);
+        Self {
+            start,
+            stop,
+            tick_spacing: Self::nice_step(gap / 10.0),
+            custom: Some(Custom(scale)),
+        }
+    }
+
+    /// Check if the mapping is custom
+    pub(crate) fn is_custom(&self) -> bool {
+        self.custom.is_some()
+    }
+
     fn spacing(min: f32, max: f32) -> f32 {
         let span = max - min;
         let power = span.log10().floor() as i32;
@@ -53,15 +268,75 @@ impl Numeric {
         }
     }
 
-    fn tick_spacing(&self) -> f32 {
+    /// Get the "nice" step (1, 2 or 5 times a power of 10) nearest to `raw`
+    pub(crate) fn nice_step(raw: f32) -> f32 {
+        if raw <= 0.0 || !raw.is_finite() {
+            return 1.0;
+        }
+        let power = 10_f32.powi(raw.log10().floor() as i32);
+        let error = raw / power;
+        if error >= 50_f32.sqrt() {
+            10.0 * power
+        } else if error >= 10_f32.sqrt() {
+            5.0 * power
+        } else if error >= 2_f32.sqrt() {
+            2.0 * power
+        } else {
+            power
+        }
+    }
+
+    /// Get the smallest "nice" step which is at least `raw`
+    pub(crate) fn nice_step_above(raw: f32) -> f32 {
+        let step = Self::nice_step(raw);
+        if step >= raw * (1.0 - 1e-4) {
+            step
+        } else {
+            Self::nice_step(step * 2.1)
+        }
+    }
+
+    /// Extend the scale to include zero
+    pub(crate) fn with_zero(&self) -> Self {
+        if self.is_custom() || self.start <= 0.0 && self.stop >= 0.0 {
+            self.clone()
+        } else {
+            Numeric::new(self.start.min(0.0), self.stop.max(0.0))
+        }
+    }
+
+    /// Expand the bounds outward to multiples of a step
+    ///
+    /// A positive start within a fraction of the stop snaps to zero.
+    pub(crate) fn expanded(&self, step: f32, zero_snap: f32) -> Self {
+        if step <= 0.0 || !step.is_finite() || self.is_custom() {
+            return self.clone();
+        }
+        let mut start = (self.start / step + 1e-4).floor() * step;
+        let stop = (self.stop / step - 1e-4).ceil() * step;
+        if start > 0.0 && start <= stop * zero_snap {
+            start = 0.0;
+        }
+        Self {
+            start,
+            stop,
+            tick_spacing: self.tick_spacing,
+            custom: None,
+        }
+    }
+
+    pub(crate) fn tick_spacing(&self) -> f32 {
         self.tick_spacing
     }
 
-    fn add_tick(&self, val: f32, ticks: &mut Vec<Tick>) {
-        let value = self.normalize(val);
-        let text = format!("{}", val);
-        let tick = Tick::new(value, text);
-        ticks.push(tick);
+    /// Get the value at the center of the scale
+    pub(crate) fn center(&self) -> f32 {
+        (self.start + self.stop) / 2.0
+    }
+
+    /// Get the span between start and stop
+    pub(crate) fn span(&self) -> f32 {
+        self.stop - self.start
     }
 
     pub(crate) fn from_data<I, P>(data: I, get: fn(Pt<f32>) -> f32) -> Self
@@ -93,10 +368,18 @@ impl Numeric {
             start: self.start,
             stop: self.stop,
             tick_spacing: -self.tick_spacing,
+            custom: self.custom.clone(),
         }
     }
 
     pub(crate) fn normalize(&self, value: f32) -> f32 {
+        if let Some(Custom(custom)) = &self.custom {
+            let value = custom.normalize(value);
+            return match custom.inverted() == (self.tick_spacing < 0.0) {
+                true => value,
+                false => 1.0 - value,
+            };
+        }
         let a = self.start;
         let b = self.stop;
         if b - a > f32::EPSILON {
@@ -110,23 +393,45 @@ impl Numeric {
         }
     }
 
-    pub(crate) fn ticks(&self) -> Vec<Tick> {
-        let mut ticks = vec![];
-        let spacing = self.tick_spacing();
-        if spacing > 0.0 {
-            let mut val = self.start;
-            while val <= self.stop {
-                self.add_tick(val, &mut ticks);
-                val += spacing;
-            }
-        } else {
-            let mut val = self.stop;
-            while val >= self.start {
-                self.add_tick(val, &mut ticks);
-                val += spacing;
-            }
-        };
-        ticks
+    /// Check if a value is within the scale bounds
+    pub(crate) fn contains(&self, value: f32) -> bool {
+        if let Some(Custom(custom)) = &self.custom {
+            let value = custom.normalize(value);
+            return (-1e-4..=1.0 + 1e-4).contains(&value);
+        }
+        let margin = (self.stop - self.start).abs() * 1e-4;
+        value >= self.start - margin && value <= self.stop + margin
+    }
+
+    /// Get tick values at multiples of a step
+    ///
+    /// Custom scales ignore the step, using their own ticks.
+    pub(crate) fn tick_values_step(&self, step: f32) -> Vec<f32> {
+        if let Some(Custom(custom)) = &self.custom {
+            return custom.ticks();
+        }
+        let first = (self.start / step - 1e-4).ceil() as i32;
+        let last = (self.stop / step + 1e-4).floor() as i32;
+        (first..=last).map(|k| k as f32 * step).collect()
+    }
+
+    /// Get tick values, in order of tick spacing direction
+    pub(crate) fn tick_values(&self) -> Vec<f32> {
+        let mut values = self.tick_values_step(self.tick_spacing().abs());
+        if self.tick_spacing() < 0.0 {
+            values.reverse();
+        }
+        values
+    }
+
+    /// Get the number of decimal places needed to display a step
+    pub(crate) fn precision(step: f32) -> usize {
+        (0..9)
+            .find(|places| {
+                let s = (step * 10_f32.powi(*places)).abs();
+                s.round() >= 1.0 && (s - s.round()).abs() <= s * 1e-3
+            })
+            .unwrap_or(9) as usize
     }
 }
 
@@ -150,4 +455,102 @@ mod tests {
         assert_eq!(Numeric::new(0.0, 0.1).tick_spacing(), 0.01);
         assert_eq!(Numeric::new(0.0, 0.1).tick_spacing(), 0.01);
     }
+
+    #[test]
+    fn nice_step() {
+        assert_eq!(Numeric::nice_step(0.7), 0.5);
+        assert_eq!(Numeric::nice_step(25.0), 20.0);
+        assert_eq!(Numeric::nice_step(40.0), 50.0);
+        assert_eq!(Numeric::nice_step_above(1.5), 2.0);
+        assert_eq!(Numeric::nice_step_above(25.0), 50.0);
+        assert_eq!(Numeric::nice_step_above(60.0), 100.0);
+    }
+
+    #[test]
+    fn expanded() {
+        let scale = Numeric::exact(3.2, 96.7);
+        assert_eq!(scale.expanded(10.0, 0.0), Numeric::exact(0.0, 100.0));
+        let scale = Numeric::exact(23.0, 96.7);
+        assert_eq!(scale.expanded(10.0, 0.0), Numeric::exact(20.0, 100.0));
+        assert_eq!(scale.expanded(10.0, 0.25), Numeric::exact(0.0, 100.0));
+        let scale = Numeric::exact(-7.5, 12.0);
+        assert_eq!(scale.expanded(5.0, 0.25), Numeric::exact(-10.0, 15.0));
+    }
+
+    #[test]
+    fn with_zero() {
+        assert_eq!(
+            Numeric::new(20.0, 90.0).with_zero(),
+            Numeric::new(0.0, 90.0)
+        );
+        assert_eq!(
+            Numeric::new(-9.0, -2.0).with_zero(),
+            Numeric::new(-9.0, 0.0)
+        );
+        assert_eq!(
+            Numeric::new(-1.0, 1.0).with_zero(),
+            Numeric::new(-1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(Numeric::precision(10.0), 0);
+        assert_eq!(Numeric::precision(1.0), 0);
+        assert_eq!(Numeric::precision(0.1), 1);
+        assert_eq!(Numeric::precision(0.25), 2);
+        assert_eq!(Numeric::precision(1e-6), 6);
+    }
+
+    #[test]
+    fn quantile() {
+        let values = [1.0, 1000.0, 2.0, 3.0, 5.0, 8.0, 4.0, f32::NAN, 6.0, 7.0];
+        let scale = Quantile::from_samples(&values).unwrap();
+        // Median sample maps to the midpoint
+        assert_eq!(Scale::normalize(&scale, 5.0), 0.5);
+        assert_eq!(Scale::normalize(&scale, 1.0), 0.0);
+        assert_eq!(Scale::normalize(&scale, 1000.0), 1.0);
+        assert_eq!(Scale::normalize(&scale, 4.5), 0.4375);
+        assert_eq!(Scale::normalize(&scale, -5.0), 0.0);
+        let ticks = scale.ticks();
+        assert_eq!(ticks[..4], [1.8, 3.0, 5.0, 7.0]);
+        assert!((ticks[4] - 206.4).abs() < 1e-3);
+        let scale = scale.with_percentiles(&[0.0, 50.0, 100.0]);
+        assert_eq!(scale.ticks(), [1.0, 5.0, 1000.0]);
+        let skewed =
+            Quantile::from_samples(&[0.0, 0.0, 0.0, 0.0, 9.0]).unwrap();
+        assert_eq!(Scale::normalize(&skewed, 0.0), 0.375);
+        let ticks = skewed.ticks();
+        assert_eq!(ticks.len(), 2);
+        assert!((ticks[1] - 5.4).abs() < 1e-3);
+        // Degenerate samples
+        assert_eq!(Quantile::from_samples(&[]), None);
+        assert_eq!(Quantile::from_samples(&[f32::NAN]), None);
+        let single = Quantile::from_samples(&[3.0]).unwrap();
+        assert_eq!(Scale::normalize(&single, 10.0), 0.5);
+        assert_eq!(single.ticks(), [3.0]);
+        // Axis mapping goes through the quantile scale
+        let domain = pointy::BBox::new([(0.0, 0.0), (1000.0, 1.0)]);
+        let axis = crate::axis::Horizontal::new(domain)
+            .with_scale(Quantile::from_samples(&values).unwrap());
+        let out = crate::Chart::default().with_axis(axis).render();
+        assert!(out.contains("dy='0.33em'>1.8</tspan>"));
+        assert!(out.contains("dy='0.33em'>206.4</tspan>"));
+        let axis = crate::axis::Horizontal::new(domain).with_scale(scale);
+        let chart = crate::Chart::default().with_axis(axis);
+        let area = chart.plot_area();
+        let pt = chart.map_point((5.0, 0.0).into(), &domain);
+        assert_eq!(pt.x(), area.x_min() + area.x_span() / 2.0);
+    }
+
+    #[test]
+    fn degenerate() {
+        let scale = Numeric::new(5.0, 5.0);
+        assert_eq!(scale.normalize(5.0), 0.5);
+        assert_eq!(scale.tick_values(), [5.0]);
+        let scale = Numeric::new(5.3, 5.3);
+        assert_eq!(scale.normalize(5.3), 0.5);
+        assert_eq!(scale.tick_values().len(), 1);
+        assert_eq!(Numeric::new(0.0, 0.0).tick_values(), [0.0]);
+    }
 }
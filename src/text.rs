@@ -11,7 +11,7 @@ use std::{fmt, fmt::Write};
 
 use pointy::BBox;
 
-use crate::page::Edge;
+use crate::{number::NumberFormat, page::Edge};
 
 /// Text label point
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -46,12 +46,15 @@ pub enum Anchor {
     End,
 }
 
+/// Value labels for data points
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Label {
     point: LabelPoint,
     offset: VerticalOffset,
     anchor: Anchor,
     rounding_precision: Option<usize>,
+    number_format: NumberFormat,
+    clipped: bool,
 }
 
 pub struct Text<'a> {
@@ -60,6 +63,14 @@ pub struct Text<'a> {
     rect: Option<BBox<f32>>,
     dy: Option<f32>,
     class_name: Option<&'a str>,
+    link: Option<&'a Link>,
+}
+
+/// Hyperlink for titles, axis names and legend entries
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link {
+    href: String,
+    new_tab: bool,
 }
 
 pub struct Tspan<'a> {
@@ -69,6 +80,18 @@ pub struct Tspan<'a> {
     dy: Option<f32>,
 }
 
+/// Text escaped for XML content or attributes
+pub(crate) struct Escape<'a>(pub &'a str);
+
+/// Coordinate rounded to a number of decimal places
+///
+/// Trailing zeros are trimmed when displayed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Coord {
+    value: f32,
+    precision: u8,
+}
+
 /// Tick marks for axis labels
 #[derive(Debug, PartialEq)]
 pub struct Tick {
@@ -76,6 +99,156 @@ pub struct Tick {
     text: String,
 }
 
+impl fmt::Display for Escape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '\'' => f.write_str("&apos;")?,
+                '"' => f.write_str("&quot;")?,
+                _ => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Into<String>> From<T> for Link {
+    fn from(href: T) -> Self {
+        Link::new(href)
+    }
+}
+
+impl Link {
+    /// Create a new link to a URL
+    pub fn new(href: impl Into<String>) -> Self {
+        Self {
+            href: href.into(),
+            new_tab: false,
+        }
+    }
+
+    /// Open the link in a new tab (`target='_blank'`)
+    pub fn in_new_tab(mut self) -> Self {
+        self.new_tab = true;
+        self
+    }
+
+    /// Display the start of an `a` element
+    ///
+    /// The same markup works in both SVG and HTML.
+    pub(crate) fn display(&self, f: &mut dyn Write) -> fmt::Result {
+        write!(f, "<a href='{}'", Escape(&self.href))?;
+        if self.new_tab {
+            write!(f, " target='_blank' rel='noopener'")?;
+        }
+        write!(f, ">")
+    }
+
+    /// Display the end of an `a` element
+    pub(crate) fn display_done(&self, f: &mut dyn Write) -> fmt::Result {
+        writeln!(f, "</a>")
+    }
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f)
+    }
+}
+
+/// Get the scale factor for a number of decimal places
+fn scale(precision: u8) -> f32 {
+    const SCALES: [f32; 8] = [1.0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7];
+    SCALES
+        .get(usize::from(precision))
+        .copied()
+        .unwrap_or_else(|| 10_f32.powi(i32::from(precision)))
+}
+
+impl Coord {
+    /// Maximum encoded length
+    pub const MAX_LEN: usize = 24;
+
+    /// Create a new coordinate, rounded to `precision` decimal places
+    pub fn new(value: f32, precision: u8) -> Self {
+        let scale = scale(precision);
+        Self {
+            value: (value * scale).round() / scale,
+            precision,
+        }
+    }
+
+    /// Get the rounded value
+    pub fn value(self) -> f32 {
+        self.value
+    }
+
+    /// Write the coordinate, without `fmt` machinery
+    ///
+    /// This is used for the hottest paths, where there is a write per point.
+    pub fn write(self, f: &mut dyn Write) -> fmt::Result {
+        let mut buf = [0; Coord::MAX_LEN];
+        let len = self.encode(&mut buf);
+        if len == 0 {
+            return write!(f, "{}", self.value);
+        }
+        // Only ASCII digits, `.` and `-` were encoded
+        let text = &buf[Coord::MAX_LEN - len..];
+        f.write_str(std::str::from_utf8(text).unwrap_or_default())
+    }
+
+    /// Encode the coordinate as ASCII at the end of a buffer
+    ///
+    /// Returns the encoded length, or 0 if it can't be encoded (when it's
+    /// not finite, or too large for an integer).
+    pub fn encode(self, buf: &mut [u8; Coord::MAX_LEN]) -> usize {
+        let scaled = (self.value * scale(self.precision)).round();
+        if !scaled.is_finite() || scaled.abs() >= 1e15 || self.precision > 15 {
+            return 0;
+        }
+        let mut pos = buf.len();
+        let mut push = |b: u8| {
+            pos -= 1;
+            buf[pos] = b;
+        };
+        let mut v = (scaled as i64).unsigned_abs();
+        let mut digits = self.precision;
+        // Trim trailing zeros from the fraction
+        while digits > 0 && v.is_multiple_of(10) {
+            v /= 10;
+            digits -= 1;
+        }
+        for _ in 0..digits {
+            push(b'0' + (v % 10) as u8);
+            v /= 10;
+        }
+        if digits > 0 {
+            push(b'.');
+        }
+        loop {
+            push(b'0' + (v % 10) as u8);
+            v /= 10;
+            if v == 0 {
+                break;
+            }
+        }
+        if scaled < 0.0 {
+            push(b'-');
+        }
+        Coord::MAX_LEN - pos
+    }
+
+    /// Get the relative offset from another coordinate
+    ///
+    /// The offset is rounded again, so relative path commands have no drift.
+    pub fn delta(self, from: Coord) -> Coord {
+        Coord::new(self.value - from.value, self.precision)
+    }
+}
+
 impl Anchor {
     pub(crate) fn display(&self, f: &mut dyn Write) -> fmt::Result {
         match self {
@@ -93,20 +266,31 @@ impl Default for Label {
             offset: VerticalOffset::At,
             anchor: Anchor::Middle,
             rounding_precision: None,
+            number_format: NumberFormat::default(),
+            clipped: false,
         }
     }
 }
 
 impl Label {
+    /// Create a new label, centered on the point
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub fn label_point(&self) -> LabelPoint {
+    pub(crate) fn label_point(&self) -> LabelPoint {
         self.point
     }
 
-    pub fn vertical_offset(&self) -> f32 {
+    pub(crate) fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+
+    pub(crate) fn is_clipped(&self) -> bool {
+        self.clipped
+    }
+
+    pub(crate) fn vertical_offset(&self) -> f32 {
         match self.offset {
             VerticalOffset::Above => -1.0,
             VerticalOffset::At => 0.0,
@@ -114,40 +298,81 @@ impl Label {
         }
     }
 
+    /// Place the label at the start of a bar
     pub fn minimum(mut self) -> Self {
         self.point = LabelPoint::Minimum;
         self
     }
 
+    /// Place the label at the end of a bar
     pub fn maximum(mut self) -> Self {
         self.point = LabelPoint::Maximum;
         self
     }
 
+    /// Place the label above the point
     pub fn above(mut self) -> Self {
         self.offset = VerticalOffset::Above;
         self
     }
 
+    /// Place the label below the point
     pub fn below(mut self) -> Self {
         self.offset = VerticalOffset::Below;
         self
     }
 
+    /// Anchor the label at the start of its text
     pub fn start(mut self) -> Self {
         self.anchor = Anchor::Start;
         self
     }
 
+    /// Anchor the label at the end of its text
     pub fn end(mut self) -> Self {
         self.anchor = Anchor::End;
         self
     }
 
-    pub fn rounded(&self, value: f32) -> String {
+    /// Round values to a number of decimal places
+    pub fn rounded_to(mut self, digits: usize) -> Self {
+        self.rounding_precision = Some(digits);
+        self
+    }
+
+    /// Set the number format of values
+    pub fn with_number_format(mut self, format: NumberFormat) -> Self {
+        self.number_format = format;
+        self
+    }
+
+    /// Draw labels outside of the chart clip area (default)
+    ///
+    /// This keeps labels of points near the edges from being cut off.
+    pub fn unclipped(mut self) -> Self {
+        self.clipped = false;
+        self
+    }
+
+    /// Clip labels to the chart clip area
+    pub fn clipped(mut self) -> Self {
+        self.clipped = true;
+        self
+    }
+
+    /// Get the vertical text offset (in em)
+    pub(crate) fn dy(&self) -> f32 {
+        match self.offset {
+            VerticalOffset::Above => -0.67,
+            VerticalOffset::At => 0.33,
+            VerticalOffset::Below => 1.33,
+        }
+    }
+
+    pub(crate) fn rounded(&self, value: f32) -> String {
         match self.rounding_precision {
-            None => value.to_string(),
-            Some(digits) => format!("{:.1$}", value, digits),
+            None => self.number_format.localize(&value.to_string()),
+            Some(digits) => self.number_format.format(value, digits),
         }
     }
 }
@@ -160,6 +385,7 @@ impl<'a> Text<'a> {
             rect: None,
             dy: None,
             class_name: None,
+            link: None,
         }
     }
 
@@ -183,10 +409,18 @@ impl<'a> Text<'a> {
         self
     }
 
+    pub fn with_link(mut self, link: Option<&'a Link>) -> Self {
+        self.link = link;
+        self
+    }
+
     pub fn display(&self, f: &mut dyn Write) -> fmt::Result {
+        if let Some(link) = self.link {
+            link.display(f)?;
+        }
         write!(f, "<text")?;
         if let Some(class_name) = self.class_name {
-            write!(f, " class='{}'", class_name)?;
+            write!(f, " class='{}'", Escape(class_name))?;
         }
         if let Some(rect) = self.rect {
             self.transform(f, rect)?;
@@ -199,7 +433,11 @@ impl<'a> Text<'a> {
     }
 
     pub fn display_done(&self, f: &mut dyn Write) -> fmt::Result {
-        writeln!(f, "</text>")
+        writeln!(f, "</text>")?;
+        match self.link {
+            Some(link) => link.display_done(f),
+            None => Ok(()),
+        }
     }
 
     fn transform(&self, f: &mut dyn Write, rect: BBox<f32>) -> fmt::Result {
@@ -267,15 +505,30 @@ impl<'a> Tspan<'a> {
         if let Some(dy) = self.dy {
             write!(f, " dy='{}em'", dy)?;
         }
-        write!(f, ">{}", &self.text)?;
+        write!(f, ">{}", Escape(self.text))?;
         writeln!(f, "</tspan>")
     }
 }
 
 impl Tick {
-    pub const HLEN: i32 = Tick::LEN + 8;
-    pub const LEN: i32 = 20;
-    pub const VLEN: i32 = Tick::LEN * 2;
+    /// Approximate width of one character of tick text
+    pub const CHAR_WIDTH: f32 = 16.0;
+    /// Default vertical offset of labels (in em), centering on the tick
+    pub const DY: f32 = 0.33;
+    /// Approximate height of tick text
+    pub const HEIGHT: f32 = 40.0;
+    /// Default length of tick marks
+    pub const LEN: f32 = 20.0;
+
+    /// Get the label offset from the axis line, beside ticks of a length
+    pub fn hlen(len: f32) -> f32 {
+        len + 8.0
+    }
+
+    /// Get the label offset from the axis line, below ticks of a length
+    pub fn vlen(len: f32) -> f32 {
+        len * 2.0
+    }
 
     pub fn new<T>(value: f32, text: T) -> Self
     where
@@ -289,6 +542,11 @@ impl Tick {
         &self.text
     }
 
+    /// Get the normalized position along the axis
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
     pub fn x(&self, edge: Edge, rect: BBox<f32>, len: f32) -> f32 {
         match edge {
             Edge::Left => rect.x_max() - len,
@@ -305,9 +563,53 @@ impl Tick {
         }
     }
 
-    pub fn tspan(&self, edge: Edge, rect: BBox<f32>) -> Tspan {
-        let x = self.x(edge, rect, Tick::HLEN as f32) as i32;
-        let y = self.y(edge, rect, Tick::VLEN as f32) as i32;
-        Tspan::new(self.text()).x(x).y(y).dy(0.33)
+    /// Get a text span, offset from ticks of a length
+    ///
+    /// The `dy` is a vertical offset, in em.
+    pub fn tspan(
+        &self,
+        edge: Edge,
+        rect: BBox<f32>,
+        len: f32,
+        dy: f32,
+    ) -> Tspan<'_> {
+        let x = self.x(edge, rect, Tick::hlen(len)) as i32;
+        let y = self.y(edge, rect, Tick::vlen(len)) as i32;
+        Tspan::new(self.text()).x(x).y(y).dy(dy)
+    }
+
+    /// Get a text span for rotated labels, offset from ticks of a length
+    pub fn rotated_tspan(
+        &self,
+        edge: Edge,
+        rect: BBox<f32>,
+        len: f32,
+        dy: f32,
+    ) -> Tspan<'_> {
+        let x = self.x(edge, rect, Tick::hlen(len)) as i32;
+        let y = self.y(edge, rect, Tick::hlen(len)) as i32;
+        Tspan::new(self.text()).x(x).y(y).dy(dy)
+    }
+
+    /// Display a rotated text element, rotating around the span position
+    pub fn display_rotated(
+        f: &mut dyn Write,
+        span: Tspan,
+        edge: Edge,
+        degrees: f32,
+        class: &str,
+    ) -> fmt::Result {
+        let x = span.x.unwrap_or_default();
+        let y = span.y.unwrap_or_default();
+        let anchor = match edge {
+            Edge::Top => Anchor::Start,
+            _ => Anchor::End,
+        };
+        write!(f, "<text class='{}'", Escape(class))?;
+        write!(f, " transform='rotate({} {} {})'", -degrees, x, y)?;
+        anchor.display(f)?;
+        write!(f, ">")?;
+        span.display(f)?;
+        writeln!(f, "</text>")
     }
 }
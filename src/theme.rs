@@ -0,0 +1,153 @@
+// theme.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Chart themes
+
+use std::{fmt, fmt::Write};
+
+use crate::css::TABLEAU;
+
+/// Number of plot classes styled by a theme or the default stylesheet
+pub(crate) const PLOT_CLASSES: usize = 10;
+
+/// Theme for chart colors
+///
+/// Colors are CSS color values, such as `#4E79A7` or `gray`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    background: String,
+    grid: String,
+    axis: String,
+    text: String,
+    title: String,
+    palette: Vec<String>,
+}
+
+/// Tableau 10 color blind palette
+const COLOR_BLIND: [&str; 10] = [
+    "#006BA4", "#FF800E", "#ABABAB", "#595959", "#5F9ED1", "#C85200",
+    "#898989", "#A2C8EC", "#FFBC79", "#CFCFCF",
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl Theme {
+    /// Create a theme with dark text on a light background
+    pub fn light() -> Self {
+        Self {
+            background: "#eee".into(),
+            grid: "gray".into(),
+            axis: "#333".into(),
+            text: "#333".into(),
+            title: "#333".into(),
+            palette: TABLEAU.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    /// Create a theme with light text on a dark background
+    pub fn dark() -> Self {
+        Self {
+            background: "#333".into(),
+            axis: "#eee".into(),
+            text: "#eee".into(),
+            title: "#eee".into(),
+            ..Self::light()
+        }
+    }
+
+    /// Create a light theme with a color blind safe palette
+    pub fn colorblind() -> Self {
+        Self {
+            palette: COLOR_BLIND.iter().map(|c| c.to_string()).collect(),
+            ..Self::light()
+        }
+    }
+
+    /// Set the background color
+    pub fn with_background(mut self, color: impl Into<String>) -> Self {
+        self.background = color.into();
+        self
+    }
+
+    /// Set the grid line color
+    pub fn with_grid(mut self, color: impl Into<String>) -> Self {
+        self.grid = color.into();
+        self
+    }
+
+    /// Set the axis line color
+    pub fn with_axis(mut self, color: impl Into<String>) -> Self {
+        self.axis = color.into();
+        self
+    }
+
+    /// Set the text color, for tick labels and axis names
+    pub fn with_text(mut self, color: impl Into<String>) -> Self {
+        self.text = color.into();
+        self
+    }
+
+    /// Set the title color
+    pub fn with_title(mut self, color: impl Into<String>) -> Self {
+        self.title = color.into();
+        self
+    }
+
+    /// Set the series palette
+    ///
+    /// Plots are assigned colors in order, repeating if there are more plots
+    /// than colors.  An empty palette is ignored.
+    pub fn with_palette<I, C>(mut self, palette: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<String>,
+    {
+        let palette: Vec<String> =
+            palette.into_iter().map(Into::into).collect();
+        if !palette.is_empty() {
+            self.palette = palette;
+        }
+        self
+    }
+
+    /// Display theme as CSS
+    pub(crate) fn display(&self, f: &mut dyn Write) -> fmt::Result {
+        writeln!(f, "* {{")?;
+        writeln!(f, "  --bg: {};", self.background)?;
+        writeln!(f, "  --fg: {};", self.text)?;
+        writeln!(f, "}}")?;
+        writeln!(f, ".title, .subtitle {{\n  fill: {};\n}}", self.title)?;
+        writeln!(
+            f,
+            ".axis-line, .plot-border {{\n  stroke: {};\n}}",
+            self.axis
+        )?;
+        writeln!(f, ".grid-x, .grid-y {{\n  stroke: {};\n}}", self.grid)?;
+        for i in 0..PLOT_CLASSES {
+            let color = &self.palette[i % self.palette.len()];
+            writeln!(f, ".plot-{i} {{\n  --color: {color};\n}}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette() {
+        let theme = Theme::dark().with_palette(["red", "blue"]);
+        let mut css = String::new();
+        theme.display(&mut css).unwrap();
+        assert!(css.contains("--bg: #333;"));
+        assert!(css.contains(".plot-0 {\n  --color: red;\n}"));
+        assert!(css.contains(".plot-9 {\n  --color: blue;\n}"));
+        assert_eq!(Theme::colorblind().palette.len(), 10);
+    }
+}
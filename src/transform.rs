@@ -0,0 +1,207 @@
+// transform.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Data transforms
+//!
+//! Transforms return new points with the same `X` values, ready to plot.
+
+use pointy::Pt;
+
+/// Handling of points before a full window is available
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Edges {
+    /// Drop points without a full window, shrinking the output
+    #[default]
+    Shrink,
+    /// Average the points available in a partial window
+    Partial,
+}
+
+/// Smooth data with a trailing moving average
+///
+/// Each point is the mean `Y` of the last `window` points, up to and
+/// including it.  A window of 0 is treated as 1.
+pub fn moving_average<I, P>(
+    data: I,
+    window: usize,
+    edges: Edges,
+) -> Vec<Pt<f32>>
+where
+    I: IntoIterator<Item = P>,
+    P: Into<Pt<f32>>,
+{
+    let pts: Vec<Pt<f32>> = data.into_iter().map(Into::into).collect();
+    let window = window.max(1);
+    let (mut sum, mut bad) = (0.0, 0);
+    let mut out = Vec::with_capacity(pts.len());
+    for (i, pt) in pts.iter().enumerate() {
+        accumulate(&mut sum, &mut bad, pt.y(), 1);
+        if i >= window {
+            accumulate(&mut sum, &mut bad, pts[i - window].y(), -1);
+        }
+        let count = (i + 1).min(window);
+        if count == window || edges == Edges::Partial {
+            let y = if bad > 0 {
+                f32::NAN
+            } else {
+                (sum / count as f64) as f32
+            };
+            out.push(Pt::new(pt.x(), y));
+        }
+    }
+    out
+}
+
+/// Add a value to a running sum
+///
+/// Non-finite values are counted instead, so they don't poison the sum.
+fn accumulate(sum: &mut f64, bad: &mut i32, y: f32, sign: i32) {
+    if y.is_finite() {
+        *sum += f64::from(sign) * f64::from(y);
+    } else {
+        *bad += sign;
+    }
+}
+
+/// Smooth data with an exponential moving average
+///
+/// `alpha` is the weight of each new point, from 0 to 1.  The first point is
+/// unchanged.  Non-finite `Y` values produce `NaN` at that point only, and
+/// are skipped by the average.
+pub fn exponential<I, P>(data: I, alpha: f32) -> Vec<Pt<f32>>
+where
+    I: IntoIterator<Item = P>,
+    P: Into<Pt<f32>>,
+{
+    let alpha = alpha.clamp(0.0, 1.0);
+    let mut smooth = None;
+    data.into_iter()
+        .map(|pt| {
+            let pt: Pt<f32> = pt.into();
+            if !pt.y().is_finite() {
+                return Pt::new(pt.x(), f32::NAN);
+            }
+            let y = match smooth {
+                Some(s) => alpha * pt.y() + (1.0 - alpha) * s,
+                None => pt.y(),
+            };
+            smooth = Some(y);
+            Pt::new(pt.x(), y)
+        })
+        .collect()
+}
+
+/// Get the cumulative sum of `Y` values
+///
+/// Sums are accumulated as `f64`, so long series don't lose precision.
+/// Non-finite `Y` values produce `NaN` at that point only, and are skipped
+/// by the sum.
+pub fn cumsum<I, P>(data: I) -> Vec<Pt<f32>>
+where
+    I: IntoIterator<Item = P>,
+    P: Into<Pt<f32>>,
+{
+    let mut sum = 0.0;
+    data.into_iter()
+        .map(|pt| {
+            let pt: Pt<f32> = pt.into();
+            if !pt.y().is_finite() {
+                return Pt::new(pt.x(), f32::NAN);
+            }
+            sum += f64::from(pt.y());
+            Pt::new(pt.x(), sum as f32)
+        })
+        .collect()
+}
+
+/// Get the difference of each `Y` value from the previous one
+///
+/// The output has one fewer point than the input, unless `zero_first` is
+/// set, which keeps the first point with a difference of zero.
+pub fn diff<I, P>(data: I, zero_first: bool) -> Vec<Pt<f32>>
+where
+    I: IntoIterator<Item = P>,
+    P: Into<Pt<f32>>,
+{
+    let mut prev: Option<Pt<f32>> = None;
+    data.into_iter()
+        .filter_map(|pt| {
+            let pt: Pt<f32> = pt.into();
+            let out = match prev {
+                Some(p) => Some(Pt::new(pt.x(), pt.y() - p.y())),
+                None if zero_first => Some(Pt::new(pt.x(), 0.0)),
+                None => None,
+            };
+            prev = Some(pt);
+            out
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ys(pts: &[Pt<f32>]) -> Vec<(f32, f32)> {
+        pts.iter().map(|pt| (pt.x(), pt.y())).collect()
+    }
+
+    #[test]
+    fn moving() {
+        let data = [(1.0, 2.0), (2.0, 4.0), (3.0, 6.0), (4.0, 2.0)];
+        let avg = moving_average(data, 3, Edges::Shrink);
+        assert_eq!(ys(&avg), [(3.0, 4.0), (4.0, 4.0)]);
+        let avg = moving_average(data, 2, Edges::Partial);
+        assert_eq!(ys(&avg), [(1.0, 2.0), (2.0, 3.0), (3.0, 5.0), (4.0, 4.0)]);
+        assert_eq!(moving_average(data, 5, Edges::Shrink), []);
+        assert_eq!(moving_average(data, 0, Edges::Shrink).len(), 4);
+        let data = [(1.0, 2.0), (2.0, f32::NAN), (3.0, 6.0), (4.0, 2.0)];
+        let avg = moving_average(data, 2, Edges::Shrink);
+        assert!(avg[0].y().is_nan() && avg[1].y().is_nan());
+        assert_eq!(avg[2].y(), 4.0);
+    }
+
+    #[test]
+    fn exponential_smoothing() {
+        let data = [(1.0, 2.0), (2.0, 4.0), (3.0, 0.0)];
+        let smooth = exponential(data, 0.5);
+        assert_eq!(ys(&smooth), [(1.0, 2.0), (2.0, 3.0), (3.0, 1.5)]);
+        assert_eq!(ys(&exponential(data, 1.0)), data);
+        let data = [
+            (1.0, f32::NAN),
+            (2.0, 2.0),
+            (3.0, f32::INFINITY),
+            (4.0, 4.0),
+            (5.0, 0.0),
+        ];
+        let smooth = exponential(data, 0.5);
+        assert!(smooth[0].y().is_nan() && smooth[2].y().is_nan());
+        assert_eq!(smooth[1].y(), 2.0);
+        assert_eq!(smooth[3].y(), 3.0);
+        assert_eq!(smooth[4].y(), 1.5);
+    }
+
+    #[test]
+    fn cumulative() {
+        let data = [(3.0, 1.0), (1.0, 2.0), (2.0, 3.0)];
+        assert_eq!(ys(&cumsum(data)), [(3.0, 1.0), (1.0, 3.0), (2.0, 6.0)]);
+        // Accumulating in f32 would lose every small value
+        let data = (0..100).map(|i| (i as f32, if i == 0 { 1e8 } else { 1.0 }));
+        let sum = cumsum(data);
+        assert_eq!(sum.last().unwrap().y(), (1e8 + 99.0) as f32);
+        let data = [(1.0, 1.0), (2.0, f32::NAN), (3.0, f32::INFINITY)];
+        let sum = cumsum(data.into_iter().chain([(4.0, 2.0)]));
+        assert!(sum[1].y().is_nan() && sum[2].y().is_nan());
+        assert_eq!((sum[0].y(), sum[3].y()), (1.0, 3.0));
+    }
+
+    #[test]
+    fn difference() {
+        let data = [(3.0, 1.0), (1.0, 3.0), (2.0, 6.0)];
+        assert_eq!(ys(&diff(data, false)), [(1.0, 2.0), (2.0, 3.0)]);
+        let d = diff(data, true);
+        assert_eq!(ys(&d), [(3.0, 0.0), (1.0, 2.0), (2.0, 3.0)]);
+        assert_eq!(ys(&diff(cumsum(data), false)), [(1.0, 3.0), (2.0, 6.0)]);
+    }
+}
@@ -0,0 +1,157 @@
+// trend.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Trend lines
+
+use pointy::Pt;
+
+/// Legend label for a trend line
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TrendLabel {
+    /// No legend label
+    #[default]
+    None,
+    /// Slope and intercept, such as `y = 2x + 1`
+    Equation,
+    /// Coefficient of determination, such as `R² = 0.95`
+    RSquared,
+}
+
+/// Ordinary least squares fit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Fit {
+    /// Line with a slope and intercept
+    Line { slope: f32, intercept: f32, r2: f32 },
+    /// Vertical line, for data with no `X` variance
+    Vertical { x: f32 },
+}
+
+impl Fit {
+    /// Fit a line to points, ignoring non-finite values
+    ///
+    /// Sums are accumulated as `f64` for long series.
+    pub(crate) fn linear<'p>(
+        pts: impl Iterator<Item = &'p Pt<f32>>,
+    ) -> Option<Self> {
+        let pts: Vec<(f64, f64)> = pts
+            .filter(|pt| pt.x().is_finite() && pt.y().is_finite())
+            .map(|pt| (f64::from(pt.x()), f64::from(pt.y())))
+            .collect();
+        if pts.is_empty() {
+            return None;
+        }
+        let n = pts.len() as f64;
+        let x_mean = pts.iter().map(|p| p.0).sum::<f64>() / n;
+        let y_mean = pts.iter().map(|p| p.1).sum::<f64>() / n;
+        let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+        for (x, y) in &pts {
+            let (dx, dy) = (x - x_mean, y - y_mean);
+            sxx += dx * dx;
+            sxy += dx * dy;
+            syy += dy * dy;
+        }
+        // Variance is relative to the magnitude of the values
+        let scale = x_mean.abs().max(1.0);
+        if sxx / n <= (scale * f64::from(f32::EPSILON)).powi(2) {
+            return Some(Fit::Vertical { x: x_mean as f32 });
+        }
+        let slope = sxy / sxx;
+        let intercept = y_mean - slope * x_mean;
+        let r2 = if syy > 0.0 {
+            sxy * sxy / (sxx * syy)
+        } else {
+            1.0
+        };
+        Some(Fit::Line {
+            slope: slope as f32,
+            intercept: intercept as f32,
+            r2: r2 as f32,
+        })
+    }
+
+    /// Get end points of the fit within a domain
+    pub(crate) fn ends(
+        self,
+        (x_min, x_max): (f32, f32),
+        (y_min, y_max): (f32, f32),
+    ) -> [(f32, f32); 2] {
+        match self {
+            Fit::Line {
+                slope, intercept, ..
+            } => [
+                (x_min, slope * x_min + intercept),
+                (x_max, slope * x_max + intercept),
+            ],
+            Fit::Vertical { x } => [(x, y_min), (x, y_max)],
+        }
+    }
+
+    /// Get the legend label
+    pub(crate) fn label(self, label: TrendLabel) -> Option<String> {
+        match (label, self) {
+            (TrendLabel::None, _) => None,
+            (TrendLabel::Equation, Fit::Vertical { x }) => {
+                Some(format!("x = {x:.2}"))
+            }
+            (
+                TrendLabel::Equation,
+                Fit::Line {
+                    slope, intercept, ..
+                },
+            ) => {
+                let sign = if intercept < 0.0 { '-' } else { '+' };
+                let intercept = intercept.abs();
+                Some(format!("y = {slope:.2}x {sign} {intercept:.2}"))
+            }
+            (TrendLabel::RSquared, Fit::Line { r2, .. }) => {
+                Some(format!("R² = {r2:.2}"))
+            }
+            (TrendLabel::RSquared, Fit::Vertical { .. }) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fit(data: &[(f32, f32)]) -> Option<Fit> {
+        let pts: Vec<Pt<f32>> = data.iter().map(|p| Pt::from(*p)).collect();
+        Fit::linear(pts.iter())
+    }
+
+    #[test]
+    fn linear() {
+        let line = fit(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (f32::NAN, 0.0)]);
+        let line = line.unwrap();
+        assert_eq!(
+            line,
+            Fit::Line {
+                slope: 2.0,
+                intercept: 1.0,
+                r2: 1.0
+            }
+        );
+        assert_eq!(
+            line.ends((-1.0, 3.0), (0.0, 0.0)),
+            [(-1.0, -1.0), (3.0, 7.0)]
+        );
+        let label = line.label(TrendLabel::Equation).unwrap();
+        assert_eq!(label, "y = 2.00x + 1.00");
+        let line = fit(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]).unwrap();
+        assert_eq!(line.label(TrendLabel::RSquared).unwrap(), "R² = 0.00");
+        assert_eq!(fit(&[]), None);
+    }
+
+    #[test]
+    fn vertical() {
+        let line = fit(&[(5.0, 1.0), (5.0, 3.0), (5.0, 2.0)]).unwrap();
+        assert_eq!(line, Fit::Vertical { x: 5.0 });
+        assert_eq!(
+            line.ends((0.0, 10.0), (0.0, 4.0)),
+            [(5.0, 0.0), (5.0, 4.0)]
+        );
+        assert_eq!(line.label(TrendLabel::RSquared), None);
+    }
+}
@@ -0,0 +1,149 @@
+// waterfall.rs
+//
+// Copyright (c) 2022  Jeron A Lau
+//
+//! Waterfall charts
+
+use std::{fmt, fmt::Write};
+
+use pointy::BBox;
+
+use crate::{
+    page::Edge,
+    plot::{Mapping, Scales, Series, BAR_FILL},
+    text::{Anchor, Text, Tspan},
+};
+
+/// Waterfall plot of labeled deltas
+///
+/// Each delta is drawn as a floating bar starting where the previous bar
+/// ended, followed by a bar for the final total.  Bars are placed at `X`
+/// values `0`, `1`, `2` and so on, and the domain is computed from the running
+/// totals (see [`Waterfall::domain`]).
+pub struct Waterfall<'a> {
+    name: &'a str,
+    deltas: &'a [(&'a str, f32)],
+    total: &'a str,
+    domain: BBox<f32>,
+}
+
+impl<'a> Waterfall<'a> {
+    /// Create a new waterfall plot
+    pub fn new(name: &'a str, deltas: &'a [(&'a str, f32)]) -> Self {
+        let mut domain =
+            BBox::new([(-0.5, 0.0), (deltas.len() as f32 + 0.5, 0.0)]);
+        let mut total = 0.0;
+        for (_, delta) in deltas {
+            total += delta;
+            domain.extend([(0.0, total)]);
+        }
+        Self {
+            name,
+            deltas,
+            total: "Total",
+            domain,
+        }
+    }
+
+    /// Set the label of the total bar
+    pub fn with_total_label(mut self, total: &'a str) -> Self {
+        self.total = total;
+        self
+    }
+
+    /// Get the domain computed from the running totals
+    ///
+    /// This can be used to create axes for the plot.
+    pub fn domain(&self) -> BBox<f32> {
+        self.domain
+    }
+
+    /// Get the bars, as (label, start, end, class) tuples
+    fn bars(&self) -> Vec<(&'a str, f32, f32, &'static str)> {
+        let mut bars = Vec::with_capacity(self.deltas.len() + 1);
+        let mut total = 0.0;
+        for (label, delta) in self.deltas {
+            let class = if *delta >= 0.0 {
+                "waterfall-up"
+            } else {
+                "waterfall-down"
+            };
+            bars.push((*label, total, total + delta, class));
+            total += delta;
+        }
+        bars.push((self.total, 0.0, total, "waterfall-total"));
+        bars
+    }
+}
+
+impl Series for Waterfall<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn display(
+        &self,
+        f: &mut dyn Write,
+        num: usize,
+        rect: BBox<f32>,
+        scales: &Scales,
+        _stack: &mut Vec<f32>,
+    ) -> fmt::Result {
+        let map = Mapping::new(&self.domain, rect, scales);
+        let x0 = map.x_map(0.0);
+        let x1 = map.x_map(1.0);
+        let width = ((x1 - x0).abs() as f32 * BAR_FILL).round() as i32;
+        let bars = self.bars();
+
+        writeln!(f, "<g class='plot-{num} plot-waterfall'>")?;
+
+        write!(f, "<path class='waterfall-connector' d='")?;
+        for (i, (_, _, end, _)) in bars.iter().enumerate() {
+            if i + 1 < bars.len() {
+                let x = map.x_map(i as f32) + width / 2;
+                let y = map.y_map(*end);
+                let x1 = map.x_map((i + 1) as f32) - width / 2;
+                write!(f, "M{x} {y}H{x1}")?;
+            }
+        }
+        writeln!(f, "'/>")?;
+
+        for (i, (_, start, end, class)) in bars.iter().enumerate() {
+            let x = map.x_map(i as f32) - width / 2;
+            let y0 = map.y_map(*start);
+            let y1 = map.y_map(*end);
+            write!(f, "<rect class='{class}' x='{x}' y='{}'", y0.min(y1))?;
+            writeln!(f, " width='{width}' height='{}'/>", (y1 - y0).abs())?;
+        }
+
+        let text = Text::new(Edge::Top)
+            .with_anchor(Anchor::Middle)
+            .with_class_name("waterfall-label");
+        text.display(f)?;
+        for (i, (label, start, end, _)) in bars.iter().enumerate() {
+            let x = map.x_map(i as f32);
+            let y = map.y_map(start.max(*end));
+            Tspan::new(label).x(x).y(y).dy(-0.5).display(f)?;
+        }
+        text.display_done(f)?;
+
+        writeln!(f, "</g>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_totals() {
+        let deltas = [("A", 10.0), ("B", -4.0), ("C", 7.0)];
+        let waterfall = Waterfall::new("Flow", &deltas);
+        let bars = waterfall.bars();
+        assert_eq!(bars[1], ("B", 10.0, 6.0, "waterfall-down"));
+        assert_eq!(bars[2], ("C", 6.0, 13.0, "waterfall-up"));
+        assert_eq!(bars[3], ("Total", 0.0, 13.0, "waterfall-total"));
+        assert_eq!(waterfall.domain().y_min(), 0.0);
+        assert_eq!(waterfall.domain().y_max(), 13.0);
+    }
+}